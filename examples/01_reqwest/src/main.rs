@@ -17,7 +17,7 @@ fn main() {
     let input_trimmed = input.trim();
 
     // Search for the anime.
-    let anime = client.search_anime(|f| f.filter("text", input_trimmed))
+    let anime = client.search_anime(|f| f.text(input_trimmed))
         .expect("Error searching for anime");
 
     // Print out the response of the request.
@@ -30,7 +30,7 @@ fn main() {
         .build(&core.handle());
 
     // Search for the anime and return the response.
-    let runner = client.search_anime(|f| f.filter("text", input_trimmed))
+    let runner = client.search_anime(|f| f.text(input_trimmed))
         .expect("Error making request")
         .and_then(|res| {
             res.body().for_each(|chunk| {
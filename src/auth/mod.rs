@@ -0,0 +1,429 @@
+//! Authentication with the API through its OAuth2 token endpoint.
+//!
+//! An [`Auth`] is given to a [`KitsuClient`], which then obtains a [`Token`]
+//! when it is first needed, sends it in the `Authorization` header of every
+//! request, and renews it when it expires or the API responds with status
+//! code 401.
+//!
+//! Two grants are supported: the resource owner password grant, using the
+//! user's email address (or slug) and password, and the refresh token grant,
+//! using the refresh token of a previously obtained token.
+//!
+//! The token is kept in a [`TokenStore`], which is in memory by default. A
+//! [`FileTokenStore`] persists it, so that a session survives restarts and
+//! can be shared between processes.
+//!
+//! Only one request renews the token at a time. Requests rejected while it
+//! is being renewed wait for the new token, and a request rejected with a
+//! token that has since been replaced in the store, such as by another
+//! process, is sent again with the stored token instead.
+//!
+//! # Examples
+//!
+//! Log in with an email address and password before getting a user:
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "reqwest")]
+//! # extern crate reqwest;
+//! #
+//! # #[cfg(feature = "reqwest")]
+//! # fn main() {
+//! #
+//! use kitsu::auth::Auth;
+//! use kitsu::{KitsuClient, KitsuReqwestRequester};
+//! use reqwest::Client;
+//!
+//! let auth = Auth::password("user@example.com", "hunter2");
+//! let client = KitsuClient::new(Client::new()).auth(auth);
+//!
+//! let user = client.get_user(1).expect("Error getting user");
+//!
+//! // Save the token to skip logging in next time.
+//! let token = client.auth_token().expect("Error loading token");
+//! # }
+//! #
+//! # #[cfg(not(feature = "reqwest"))]
+//! # fn main() { }
+//! ```
+//!
+//! Keep the session in a file, only logging in when there is no token in it
+//! or the token can not be refreshed:
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "reqwest")]
+//! # extern crate reqwest;
+//! #
+//! # #[cfg(feature = "reqwest")]
+//! # fn main() {
+//! #
+//! use kitsu::auth::{Auth, FileTokenStore};
+//! use kitsu::{KitsuClient, KitsuReqwestRequester};
+//! use reqwest::Client;
+//!
+//! let auth = Auth::password("user@example.com", "hunter2")
+//!     .store(FileTokenStore::new("/home/user/.config/my-app/token.json"));
+//! let client = KitsuClient::new(Client::new()).auth(auth);
+//!
+//! let user = client.get_user(1).expect("Error getting user");
+//! # }
+//! #
+//! # #[cfg(not(feature = "reqwest"))]
+//! # fn main() { }
+//! ```
+//!
+//! [`Auth`]: struct.Auth.html
+//! [`FileTokenStore`]: struct.FileTokenStore.html
+//! [`KitsuClient`]: ../client/struct.KitsuClient.html
+//! [`Token`]: struct.Token.html
+//! [`TokenStore`]: trait.TokenStore.html
+
+mod store;
+
+pub use self::store::{FileTokenStore, MemoryTokenStore, TokenStore};
+
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::{Error, Result};
+
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+use std::mem;
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+use std::sync::{Arc, Mutex};
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+use crate::endpoint::{self, Endpoint};
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+use crate::transport::HttpRequest;
+
+/// The URL of the API's OAuth2 token endpoint.
+pub const TOKEN_URL: &str = "https://kitsu.io/api/oauth/token";
+
+/// How long before a token's expiry it is considered expired, so that it is
+/// not used for a request that arrives after it expires.
+const EXPIRY_LEEWAY: Duration = Duration::from_secs(60);
+
+/// An OAuth2 access token returned by the API's token endpoint.
+#[derive(Clone, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Token {
+    /// The token to send in the `Authorization` header.
+    pub access_token: String,
+    /// When the token was created, in seconds since the Unix epoch.
+    #[serde(default)]
+    pub created_at: u64,
+    /// How many seconds after its creation the token expires.
+    pub expires_in: u64,
+    /// The token to use to obtain a new token once this one expires.
+    pub refresh_token: Option<String>,
+    /// The scope of the token.
+    ///
+    /// # Examples
+    ///
+    /// `public`
+    pub scope: Option<String>,
+    /// The type of the token. This should always be `bearer`.
+    pub token_type: String,
+}
+
+impl Token {
+    /// When the token expires.
+    pub fn expires_at(&self) -> SystemTime {
+        let expires_at = self.created_at.saturating_add(self.expires_in);
+
+        UNIX_EPOCH + Duration::from_secs(expires_at)
+    }
+
+    /// Whether the token has expired, or will expire within the next minute.
+    pub fn is_expired(&self) -> bool {
+        match self.expires_at().duration_since(SystemTime::now()) {
+            Ok(remaining) => remaining < EXPIRY_LEEWAY,
+            Err(_) => true,
+        }
+    }
+}
+
+impl Debug for Token {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("Token")
+            .field("access_token", &"[REDACTED]")
+            .field("created_at", &self.created_at)
+            .field("expires_in", &self.expires_in)
+            .field("refresh_token", &self.refresh_token.as_ref().map(|_| {
+                "[REDACTED]"
+            }))
+            .field("scope", &self.scope)
+            .field("token_type", &self.token_type)
+            .finish()
+    }
+}
+
+/// The credentials used to authenticate requests, and the store of the token
+/// obtained with them.
+///
+/// The token is shared between clones of the [`KitsuClient`] it is given to.
+///
+/// [`KitsuClient`]: ../client/struct.KitsuClient.html
+pub struct Auth {
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    credentials: Option<(String, String)>,
+    #[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+    renewal: Mutex<RenewalState>,
+    store: Box<dyn TokenStore>,
+    token_url: String,
+}
+
+impl Auth {
+    /// Creates an authenticator that logs in with the password grant when a
+    /// token is first needed, and again whenever the token can not be
+    /// refreshed.
+    ///
+    /// The username is the user's email address or slug.
+    pub fn password(username: &str, password: &str) -> Self {
+        Auth {
+            credentials: Some((username.to_owned(), password.to_owned())),
+            ..Auth::from_store(MemoryTokenStore::new())
+        }
+    }
+
+    /// Creates an authenticator from a previously obtained token, refreshing
+    /// it with its refresh token when it expires.
+    pub fn token(token: Token) -> Self {
+        Auth::from_store(MemoryTokenStore::with_token(token))
+    }
+
+    /// Creates an authenticator using the token in a store, refreshing it with
+    /// its refresh token when it expires.
+    pub fn from_store<S: TokenStore + 'static>(store: S) -> Self {
+        Auth {
+            client_id: None,
+            client_secret: None,
+            credentials: None,
+            #[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+            renewal: Mutex::default(),
+            store: Box::new(store),
+            token_url: TOKEN_URL.to_owned(),
+        }
+    }
+
+    /// Sets the OAuth2 client credentials to send to the token endpoint.
+    pub fn client(mut self, client_id: &str, client_secret: &str) -> Self {
+        self.client_id = Some(client_id.to_owned());
+        self.client_secret = Some(client_secret.to_owned());
+
+        self
+    }
+
+    /// Sets the store that the token is loaded from and saved to, replacing
+    /// the current one.
+    ///
+    /// A token held by the replaced store is discarded.
+    pub fn store<S: TokenStore + 'static>(mut self, store: S) -> Self {
+        self.store = Box::new(store);
+
+        self
+    }
+
+    /// Sets the URL of the token endpoint, [`TOKEN_URL`] by default.
+    ///
+    /// [`TOKEN_URL`]: constant.TOKEN_URL.html
+    pub fn token_url(mut self, token_url: &str) -> Self {
+        self.token_url = token_url.to_owned();
+
+        self
+    }
+
+    /// Loads the current token from the store, if one has been obtained.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TokenStore`] if the token could not be loaded.
+    ///
+    /// [`Error::TokenStore`]: ../enum.Error.html#variant.TokenStore
+    pub fn current_token(&self) -> Result<Option<Token>> {
+        self.store.load().map_err(Error::TokenStore)
+    }
+}
+
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+impl Auth {
+    /// Checks whether the token must be renewed, before sending a request if
+    /// `rejected` is `None`, or after the API rejected a request otherwise.
+    ///
+    /// The token is usable if it has not expired, or if it is not the token
+    /// that the rejected request was sent with. If another caller is renewing
+    /// it, `wake` is called once that renewal finishes, and the token should
+    /// then be checked again.
+    pub(crate) fn renewal<W>(
+        auth: &Arc<Auth>,
+        rejected: Option<&HttpRequest>,
+        wake: W,
+    ) -> Result<Renewal>
+        where W: FnOnce() + Send + 'static {
+        // The store is loaded while holding the lock, so that a token saved
+        // by a renewal is always seen once that renewal has finished.
+        let mut state = auth.renewal.lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let token = auth.current_token()?;
+        let usable = match (&token, rejected) {
+            (Some(token), Some(request)) => {
+                let authorization = format!("Bearer {}", token.access_token);
+
+                request.header("Authorization") != Some(&authorization)
+            },
+            (Some(token), None) => !token.is_expired(),
+            (None, _) => false,
+        };
+
+        if usable {
+            return Ok(Renewal::Usable);
+        }
+
+        if state.renewing {
+            state.waiters.push(Box::new(wake));
+
+            return Ok(Renewal::Pending);
+        }
+
+        let refresh_token = token.as_ref()
+            .and_then(|token| token.refresh_token.as_deref());
+        let grants = auth.grants(refresh_token)?;
+        state.renewing = true;
+
+        Ok(Renewal::Grants(RenewalGuard(Arc::clone(auth)), grants))
+    }
+
+    /// Sets the `Authorization` header of a request to the current token, if
+    /// there is one.
+    pub(crate) fn authorize(&self, request: &mut HttpRequest) -> Result<()> {
+        if let Some(token) = self.current_token()? {
+            request.headers.retain(|(name, _)| {
+                !name.eq_ignore_ascii_case("Authorization")
+            });
+            request.headers.push((
+                "Authorization".to_owned(),
+                format!("Bearer {}", token.access_token),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Saves a newly obtained token to the store.
+    pub(crate) fn update(&self, mut token: Token) -> Result<()> {
+        if token.created_at == 0 {
+            token.created_at = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|now| now.as_secs())
+                .unwrap_or_default();
+        }
+
+        self.store.save(&token).map_err(Error::TokenStore)
+    }
+
+    /// The refresh token grant if there is a refresh token, followed by the
+    /// password grant if there are credentials, so that the password grant is
+    /// used when the refresh token has been revoked or has expired.
+    fn grants(&self, refresh_token: Option<&str>)
+        -> Result<Vec<Endpoint<Token>>> {
+        let request = TokenRequest {
+            client_id: self.client_id.as_deref(),
+            client_secret: self.client_secret.as_deref(),
+            grant_type: "refresh_token",
+            password: None,
+            refresh_token: None,
+            username: None,
+        };
+        let mut grants = Vec::new();
+
+        if let Some(refresh_token) = refresh_token {
+            grants.push(endpoint::token(&self.token_url, &TokenRequest {
+                refresh_token: Some(refresh_token),
+                ..request
+            })?);
+        }
+
+        if let Some((ref username, ref password)) = self.credentials {
+            grants.push(endpoint::token(&self.token_url, &TokenRequest {
+                grant_type: "password",
+                password: Some(password),
+                username: Some(username),
+                ..request
+            })?);
+        }
+
+        Ok(grants)
+    }
+}
+
+impl Debug for Auth {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("Auth")
+            .field("client_id", &self.client_id)
+            .field("has_credentials", &self.credentials.is_some())
+            .field("store", &self.store)
+            .field("token_url", &self.token_url)
+            .finish()
+    }
+}
+
+/// Whether the token is being renewed, and the callers waiting for the
+/// renewal to finish.
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+#[derive(Default)]
+struct RenewalState {
+    renewing: bool,
+    waiters: Vec<Box<dyn FnOnce() + Send>>,
+}
+
+/// What to do before sending a request with the token, as returned by
+/// [`Auth::renewal`].
+///
+/// [`Auth::renewal`]: struct.Auth.html#method.renewal
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+pub(crate) enum Renewal {
+    /// The stored token can be used.
+    Usable,
+    /// Another caller is renewing the token.
+    Pending,
+    /// The token must be renewed with each grant in turn, while holding the
+    /// guard.
+    Grants(RenewalGuard, Vec<Endpoint<Token>>),
+}
+
+/// A claim on renewing the token, waking the callers waiting for the renewal
+/// when it is dropped.
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+pub(crate) struct RenewalGuard(Arc<Auth>);
+
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+impl Drop for RenewalGuard {
+    fn drop(&mut self) {
+        let waiters = {
+            let mut state = self.0.renewal.lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            state.renewing = false;
+
+            mem::take(&mut state.waiters)
+        };
+
+        for wake in waiters {
+            wake();
+        }
+    }
+}
+
+/// The body of a request to the token endpoint.
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+#[derive(Serialize)]
+pub(crate) struct TokenRequest<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_secret: Option<&'a str>,
+    grant_type: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    password: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    refresh_token: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<&'a str>,
+}
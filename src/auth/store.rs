@@ -0,0 +1,149 @@
+//! Storage for the token obtained by an [`Auth`], so that it can outlive the
+//! process that obtained it.
+//!
+//! [`Auth`]: ../struct.Auth.html
+
+use std::fmt::Debug;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use super::Token;
+use crate::file;
+
+/// A place that the token of an [`Auth`] is loaded from before every request,
+/// and saved to whenever a new token is obtained.
+///
+/// Because the token is loaded before every request, a store shared between
+/// processes lets all of them use the token obtained by any one of them.
+///
+/// [`Auth`]: struct.Auth.html
+pub trait TokenStore: Debug + Send + Sync {
+    /// Loads the stored token, if there is one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token could not be read.
+    fn load(&self) -> io::Result<Option<Token>>;
+
+    /// Saves a token, replacing the stored one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token could not be written.
+    fn save(&self, token: &Token) -> io::Result<()>;
+}
+
+/// A store keeping the token in memory, which is the default store of an
+/// [`Auth`].
+///
+/// [`Auth`]: struct.Auth.html
+#[derive(Debug, Default)]
+pub struct MemoryTokenStore {
+    token: Mutex<Option<Token>>,
+}
+
+impl MemoryTokenStore {
+    /// Creates a store with no token.
+    pub fn new() -> Self {
+        MemoryTokenStore::default()
+    }
+
+    /// Creates a store holding a token.
+    pub fn with_token(token: Token) -> Self {
+        MemoryTokenStore {
+            token: Mutex::new(Some(token)),
+        }
+    }
+}
+
+impl TokenStore for MemoryTokenStore {
+    fn load(&self) -> io::Result<Option<Token>> {
+        Ok(self.token.lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone())
+    }
+
+    fn save(&self, token: &Token) -> io::Result<()> {
+        *self.token.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) =
+            Some(token.clone());
+
+        Ok(())
+    }
+}
+
+/// A store keeping the token in a JSON file.
+///
+/// The file is re-read on every load, so that tokens saved by other
+/// processes are picked up. Tokens are saved by writing to a temporary file
+/// next to the store's file and renaming it over the store's file, so that a
+/// partially written token is never loaded.
+///
+/// On Unix, the file and the temporary files it is written from are only
+/// readable and writable by their owner (mode `0o600`).
+#[derive(Clone, Debug)]
+pub struct FileTokenStore {
+    path: PathBuf,
+}
+
+impl FileTokenStore {
+    /// Creates a store for the file at a path, which does not need to exist
+    /// yet.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        FileTokenStore {
+            path: path.into(),
+        }
+    }
+
+    /// The path of the store's file.
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl TokenStore for FileTokenStore {
+    fn load(&self) -> io::Result<Option<Token>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(ref why) if why.kind() == ErrorKind::NotFound => {
+                return Ok(None);
+            },
+            Err(why) => return Err(why),
+        };
+
+        serde_json::from_reader(file)
+            .map(Some)
+            .map_err(|why| io::Error::new(ErrorKind::InvalidData, why))
+    }
+
+    fn save(&self, token: &Token) -> io::Result<()> {
+        let json = serde_json::to_vec_pretty(token)
+            .map_err(|why| io::Error::new(ErrorKind::InvalidData, why))?;
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        file::replace(&self.path, &private_options(), &json)
+    }
+}
+
+/// Options creating a new file that only its owner can read and write.
+#[cfg(unix)]
+fn private_options() -> OpenOptions {
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let mut options = OpenOptions::new();
+    options.create_new(true).write(true).mode(0o600);
+
+    options
+}
+
+#[cfg(not(unix))]
+fn private_options() -> OpenOptions {
+    let mut options = OpenOptions::new();
+    options.create_new(true).write(true);
+
+    options
+}
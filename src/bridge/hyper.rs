@@ -7,23 +7,75 @@
 //! [`KitsuRequester`]: trait.KitsuRequester.html
 
 use futures::future::{self, Future};
+use futures::sync::oneshot;
 use futures::Stream;
 use hyper::client::{Client as HyperClient, Connect};
 use hyper::error::Error as HyperError;
-use hyper::Uri;
-use serde_json;
+use hyper::{Method as HyperMethod, Request, Uri};
+use serde::de::DeserializeOwned;
 use std::str::FromStr;
-use ::builder::Search;
-use ::model::*;
-use ::{API_URL, Error};
-
-macro_rules! try_uri {
-    ($uri:ident) => {
-        match Uri::from_str($uri) {
-            Ok(v) => v,
-            Err(why) => return Box::new(future::err(Error::Uri(why))),
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::vec::IntoIter;
+use tokio_timer::Delay;
+use crate::auth::{Auth, Renewal, Token};
+use crate::builder::Search;
+use crate::cache::Lookup;
+use crate::client::KitsuClient;
+use crate::endpoint::{self, requester_methods, Endpoint, IntoEndpoint};
+use crate::middleware::Chain;
+use crate::model::Resource;
+use crate::pagination::hyper::{ItemStream, PageStream};
+use crate::rate_limit::RateLimiter;
+use crate::retry::RetryPolicy;
+use crate::trace::{self, RequestSpan};
+use crate::transport::{FuturesHttpTransport, HttpRequest, HttpResponse, Method};
+use crate::Error;
+
+/// Declares or implements the methods generated from the table in
+/// `requester_methods!`.
+macro_rules! methods {
+    ($mode:ident $(
+        $(#[$attr:meta])*
+        fn $name:ident[$($generic:tt)*]($($arg:ident: $ty:ty),* $(,)?)
+            -> $output:ty
+            where [$($bound:tt)*]
+            => $endpoint:expr;
+    )*) => {
+        $(
+            methods!(@$mode
+                [$(#[$attr])*]
+                $name[$($generic)*]($($arg: $ty),*) -> $output
+                where [$($bound)*]
+                => $endpoint);
+        )*
+    };
+    (@declare [$($attr:tt)*] $name:ident[$($generic:tt)*]($($arg:ident: $ty:ty),*)
+        -> $output:ty where [$($bound:tt)*] => $endpoint:expr) => {
+        $($attr)*
+        fn $name<$($generic)*>(&self, $($arg: $ty),*)
+            -> Box<dyn Future<Item = $output, Error = Error>>
+            where $($bound)*;
+    };
+    (@client [$($attr:tt)*] $name:ident[$($generic:tt)*]($($arg:ident: $ty:ty),*)
+        -> $output:ty where [$($bound:tt)*] => $endpoint:expr) => {
+        fn $name<$($generic)*>(&self, $($arg: $ty),*)
+            -> Box<dyn Future<Item = $output, Error = Error>>
+            where $($bound)* {
+            match IntoEndpoint::into_endpoint($endpoint) {
+                Ok(endpoint) => execute(self, endpoint),
+                Err(why) => Box::new(future::err(why)),
+            }
         }
-    }
+    };
+    (@bare [$($attr:tt)*] $name:ident[$($generic:tt)*]($($arg:ident: $ty:ty),*)
+        -> $output:ty where [$($bound:tt)*] => $endpoint:expr) => {
+        fn $name<$($generic)*>(&self, $($arg: $ty),*)
+            -> Box<dyn Future<Item = $output, Error = Error>>
+            where $($bound)* {
+            KitsuClient::new(self.clone()).$name($($arg),*)
+        }
+    };
 }
 
 /// Trait which defines the methods necessary to interact with the service.
@@ -37,463 +89,460 @@ macro_rules! try_uri {
 /// use kitsu::KitsuHyperRequester;
 /// ```
 ///
-/// At this point, the methods will be on your Hyper Client.
+/// At this point, the methods will be on your Hyper Client, sending requests
+/// to [`API_URL`].
+///
+/// To use a different base URL or send additional headers, wrap the Client
+/// in a [`KitsuClient`], which also implements this trait. A `KitsuClient`
+/// wrapping any other [`FuturesHttpTransport`] implements it too.
+///
+/// Get an anime with the id of 1:
+///
+/// ```rust,ignore
+/// extern crate hyper;
+/// extern crate hyper_tls;
+/// extern crate kitsu;
+/// extern crate tokio_core;
+///
+/// use hyper_tls::HttpsConnector;
+/// use kitsu::KitsuHyperRequester;
+/// use hyper::Client;
+/// use std::env;
+/// use tokio_core::reactor::Core;
+///
+/// let mut core = Core::new()?;
+///
+/// let connector = HttpsConnector::new(1, &core.handle())?;
+/// let client = Client::configure()
+///     .connector(connector)
+///     .build(&core.handle());
+///
+/// let anime_id = 1;
+///
+/// let runner = client.get_anime(anime_id)
+///     .map(|anime| {
+///         println!(
+///             "The anime's name is '{}'",
+///             anime.data.attributes.canonical_title,
+///         );
+///     })
+///     .map_err(|why| {
+///         println!("Error with the request: {:?}", why);
+///     });
+///
+/// core.run(runner)?;
+/// ```
+///
+/// Search for a manga with the name "Orange", using the same `client` and
+/// `core`:
+///
+/// ```rust,ignore
+/// let manga_name = "Orange";
+///
+/// let runner = client.search_manga(|f| f.text(manga_name))
+///     .map(|resp| {
+///         println!(
+///             "There are {} results",
+///             resp.data.len(),
+///         );
+///     })
+///     .map_err(|why| {
+///         println!("Error with the request: {:?}", why);
+///     });
+///
+/// core.run(runner)?;
+/// ```
+///
+/// [`API_URL`]: ../../constant.API_URL.html
+/// [`FuturesHttpTransport`]: ../../transport/trait.FuturesHttpTransport.html
+/// [`KitsuClient`]: ../../client/struct.KitsuClient.html
+//
+// Note: These doc examples can not be tested due to the reliance on
+// tokio_core. Instead, they are taken from example `02_hyper` and should
+// roughly match it to ensure accuracy.
 pub trait KitsuRequester {
-    /// Gets an anime using its id.
-    ///
-    /// # Examples
-    ///
-    /// Get an anime with the id of 1:
-    ///
-    /// ```rust,ignore
-    /// extern crate hyper;
-    /// extern crate hyper_tls;
-    /// extern crate kitsu;
-    /// extern crate tokio_core;
-    ///
-    /// use hyper_tls::HttpsConnector;
-    /// use kitsu::KitsuHyperRequester;
-    /// use hyper::Client;
-    /// use std::env;
-    /// use tokio_core::reactor::Core;
-    ///
-    /// let mut core = Core::new()?;
-    ///
-    /// let connector = HttpsConnector::new(1, &core.handle())?;
-    /// let client = Client::configure()
-    ///     .connector(connector)
-    ///     .build(&core.handle());
-    ///
-    /// let anime_id = 1;
-    ///
-    /// let runner = client.get_anime(anime_id)
-    ///     .map(|anime| {
-    ///         println!(
-    ///             "The anime's name is '{}'",
-    ///             anime.data.attributes.canonical_title,
-    ///         );
-    ///     })
-    ///     .map_err(|why| {
-    ///         println!("Error with the request: {:?}", why);
-    ///     });
-    ///
-    /// core.run(runner)?;
-    /// ```
-    ///
-    // Note: This doc example can not be tested due to the reliance on
-    // tokio_core. Instead, this is taken from example `02_hyper` and should
-    // roughly match it to ensure accuracy.
-    fn get_anime(&self, id: u64)
-        -> Box<Future<Item = Response<Anime>, Error = Error>>;
+    requester_methods!(methods, declare);
 
-    /// Gets a character using its id.
-    fn get_character(&self, id: u64)
-        -> Box<Future<Item = Response<Character>, Error = Error>>;
-
-    /// Gets a manga using its id.
-    ///
-    /// # Examples
+    /// Searches for resources of any type, returning a stream over every
+    /// page of results.
     ///
-    /// Get a manga with the id of 1:
+    /// The search may be a [`Search`], a [`Query`], or the typed search
+    /// builder of the resource, such as an [`AnimeSearch`].
     ///
-    /// ```rust,ignore
-    /// extern crate hyper;
-    /// extern crate hyper_tls;
-    /// extern crate kitsu;
-    /// extern crate tokio_core;
+    /// Pages are requested as the stream is polled, following the `next`
+    /// link of each page, or by offset with [`PageStream::prefetch`].
     ///
-    /// use hyper_tls::HttpsConnector;
-    /// use kitsu::KitsuHyperRequester;
-    /// use hyper::Client;
-    /// use std::env;
-    /// use tokio_core::reactor::Core;
-    ///
-    /// let mut core = Core::new()?;
-    ///
-    /// let connector = HttpsConnector::new(1, &core.handle())?;
-    /// let client = Client::configure()
-    ///     .connector(connector)
-    ///     .build(&core.handle());
-    ///
-    /// let manga_id = 1;
-    ///
-    /// let runner = client.get_manga(manga_id)
-    ///     .map(|manga| {
-    ///         println!(
-    ///             "The manga's name is '{}'",
-    ///             manga.data.attributes.canonical_title,
-    ///         );
-    ///     })
-    ///     .map_err(|why| {
-    ///         println!("Error with the request: {:?}", why);
-    ///     });
-    ///
-    /// core.run(runner)?;
-    /// ```
-    ///
-    // Note: This doc example can not be tested due to the reliance on
-    // tokio_core. Instead, this is taken from example `02_hyper` and should
-    // roughly match it to ensure accuracy.
-    fn get_manga(&self, id: u64)
-        -> Box<Future<Item = Response<Manga>, Error = Error>>;
+    /// [`AnimeSearch`]: ../../builder/struct.AnimeSearch.html
+    /// [`PageStream::prefetch`]: ../../pagination/hyper/struct.PageStream.html#method.prefetch
+    /// [`Query`]: ../../builder/struct.Query.html
+    /// [`Search`]: ../../builder/struct.Search.html
+    fn search_pages<T, S>(&self, search: S) -> PageStream<T>
+        where T: Resource + 'static, S: Into<Search>;
 
-    // Gets a producer using their id.
-    ///
-    /// # Examples
-    ///
-    /// Get a producer with the id of 1:
-    ///
-    /// ```rust,ignore
-    /// extern crate hyper;
-    /// extern crate hyper_tls;
-    /// extern crate kitsu;
-    /// extern crate tokio_core;
-    ///
-    /// use hyper_tls::HttpsConnector;
-    /// use kitsu::KitsuHyperRequester;
-    /// use hyper::Client;
-    /// use std::env;
-    /// use tokio_core::reactor::Core;
-    ///
-    /// let mut core = Core::new()?;
-    ///
-    /// let connector = HttpsConnector::new(1, &core.handle())?;
-    /// let client = Client::configure()
-    ///     .connector(connector)
-    ///     .build(&core.handle());
-    ///
-    /// let producer_id = 1;
-    ///
-    /// let runner = client.get_producer(producer_id)
-    ///     .map(|user| {
-    ///         println!(
-    ///             "The producers's name is '{}'",
-    ///             producer.data.attributes.name,
-    ///         );
-    ///     })
-    ///     .map_err(|why| {
-    ///         println!("Error with the request: {:?}", why);
-    ///     });
-    ///
-    /// core.run(runner)?;
-    /// ```
-    ///
-    // Note: This doc example can not be tested due to the reliance on
-    // tokio_core. Instead, this is taken from example `02_hyper` and should
-    // roughly match it to ensure accuracy.
-    fn get_producer(&self, id: u64)
-        -> Box<Future<Item = Response<Producer>, Error = Error>>;
-
-    /// Gets a user using their id.
-    ///
-    /// # Examples
-    ///
-    /// Get a user with the id of 1:
-    ///
-    /// ```rust,ignore
-    /// extern crate hyper;
-    /// extern crate hyper_tls;
-    /// extern crate kitsu;
-    /// extern crate tokio_core;
-    ///
-    /// use hyper_tls::HttpsConnector;
-    /// use kitsu::KitsuHyperRequester;
-    /// use hyper::Client;
-    /// use std::env;
-    /// use tokio_core::reactor::Core;
-    ///
-    /// let mut core = Core::new()?;
-    ///
-    /// let connector = HttpsConnector::new(1, &core.handle())?;
-    /// let client = Client::configure()
-    ///     .connector(connector)
-    ///     .build(&core.handle());
-    ///
-    /// let user_id = 1;
-    ///
-    /// let runner = client.get_user(user_id)
-    ///     .map(|user| {
-    ///         println!(
-    ///             "The user's name is '{}'",
-    ///             user.data.attributes.name,
-    ///         );
-    ///     })
-    ///     .map_err(|why| {
-    ///         println!("Error with the request: {:?}", why);
-    ///     });
-    ///
-    /// core.run(runner)?;
-    /// ```
+    /// Searches for resources of any type, returning a stream over the
+    /// results of every page.
     ///
-    // Note: This doc example can not be tested due to the reliance on
-    // tokio_core. Instead, this is taken from example `02_hyper` and should
-    // roughly match it to ensure accuracy.
-    fn get_user(&self, id: u64)
-        -> Box<Future<Item = Response<User>, Error = Error>>;
-
-    /// Searches for an anime using the passed [Search] builder.
+    /// The search may be a [`Search`], a [`Query`], or the typed search
+    /// builder of the resource, such as an [`AnimeSearch`].
     ///
     /// # Examples
     ///
-    /// Search for an anime with the name "Beyond the Boundary":
+    /// Print the titles of the first 50 anime with "bebop" in their titles,
+    /// keeping two pages in flight ahead of the current one:
     ///
     /// ```rust,ignore
-    /// extern crate hyper;
-    /// extern crate hyper_tls;
-    /// extern crate kitsu;
-    /// extern crate tokio_core;
-    ///
-    /// use hyper_tls::HttpsConnector;
-    /// use kitsu::KitsuHyperRequester;
-    /// use hyper::Client;
-    /// use std::env;
-    /// use tokio_core::reactor::Core;
-    ///
-    /// let mut core = Core::new()?;
+    /// use kitsu::builder::AnimeSearch;
+    /// use kitsu::model::Anime;
     ///
-    /// let connector = HttpsConnector::new(1, &core.handle())?;
-    /// let client = Client::configure()
-    ///     .connector(connector)
-    ///     .build(&core.handle());
+    /// let search = AnimeSearch::default().text("bebop");
     ///
-    /// let anime_name = "Beyond the Boundary";
+    /// let runner = client.search_all::<Anime, _>(search)
+    ///     .page_size(20)
+    ///     .prefetch(2)
+    ///     .max_items(50)
+    ///     .for_each(|anime| {
+    ///         println!("{}", anime.attributes.canonical_title);
     ///
-    /// let runner = client.search_anime(|f| f.filter("text", anime_name))
-    ///     .map(|resp| {
-    ///         println!(
-    ///             "There are {} results",
-    ///             resp.data.len(),
-    ///         );
-    ///     })
-    ///     .map_err(|why| {
-    ///         println!("Error with the request: {:?}", why);
+    ///         Ok(())
     ///     });
     ///
     /// core.run(runner)?;
     /// ```
     ///
-    // Note: This doc example can not be tested due to the reliance on
-    // tokio_core. Instead, this is taken from example `02_hyper` and should
-    // roughly match it to ensure accuracy.
-    fn search_anime<F: FnOnce(Search) -> Search>(&self, f: F)
-        -> Box<Future<Item = Response<Vec<Anime>>, Error = Error>>;
+    /// [`AnimeSearch`]: ../../builder/struct.AnimeSearch.html
+    /// [`Query`]: ../../builder/struct.Query.html
+    /// [`Search`]: ../../builder/struct.Search.html
+    fn search_all<T, S>(&self, search: S) -> ItemStream<T>
+        where T: Resource + 'static, S: Into<Search>;
+}
 
-    /// Searches for a character using the passed search builder.
-    fn search_characters<F: FnOnce(Search) -> Search>(&self, f: F)
-        -> Box<Future<Item = Response<Vec<Character>>, Error = Error>>;
+impl<B, C: Connect> KitsuRequester for HyperClient<C, B>
+    where B: From<Vec<u8>> + Stream<Error = HyperError> + 'static,
+          B::Item: AsRef<[u8]> {
+    requester_methods!(methods, bare);
 
-    /// Searches for a manga using the passed [Search] builder.
-    ///
-    /// # Examples
-    ///
-    /// Search for a manga with the name "Orange":
-    ///
-    /// ```rust,ignore
-    /// extern crate hyper;
-    /// extern crate hyper_tls;
-    /// extern crate kitsu;
-    /// extern crate tokio_core;
-    ///
-    /// use hyper_tls::HttpsConnector;
-    /// use kitsu::KitsuHyperRequester;
-    /// use hyper::Client;
-    /// use std::env;
-    /// use tokio_core::reactor::Core;
-    ///
-    /// let mut core = Core::new()?;
-    ///
-    /// let connector = HttpsConnector::new(1, &core.handle())?;
-    /// let client = Client::configure()
-    ///     .connector(connector)
-    ///     .build(&core.handle());
-    ///
-    /// let manga_name = "Orange";
-    ///
-    /// let runner = client.search_manga(|f| f.filter("text", manga_name))
-    ///     .map(|resp| {
-    ///         println!(
-    ///             "There are {} results",
-    ///             resp.data.len(),
-    ///         );
-    ///     })
-    ///     .map_err(|why| {
-    ///         println!("Error with the request: {:?}", why);
-    ///     });
-    ///
-    /// core.run(runner)?;
-    /// ```
-    ///
-    // Note: This doc example can not be tested due to the reliance on
-    // tokio_core. Instead, this is taken from example `02_hyper` and should
-    // roughly match it to ensure accuracy.
-    fn search_manga<F: FnOnce(Search) -> Search>(&self, f: F)
-        -> Box<Future<Item = Response<Vec<Manga>>, Error = Error>>;
+    fn search_pages<T, S>(&self, search: S) -> PageStream<T>
+        where T: Resource + 'static, S: Into<Search> {
+        KitsuClient::new(self.clone()).search_pages(search)
+    }
 
-    /// Searches for a user using the passed [`Search`] builder.
-    ///
-    /// # Examples
-    ///
-    /// Search for a user with the name "Bob":
-    ///
-    /// ```rust,ignore
-    /// extern crate hyper;
-    /// extern crate hyper_tls;
-    /// extern crate kitsu;
-    /// extern crate tokio_core;
-    ///
-    /// use hyper_tls::HttpsConnector;
-    /// use kitsu::KitsuHyperRequester;
-    /// use hyper::Client;
-    /// use std::env;
-    /// use tokio_core::reactor::Core;
-    ///
-    /// let mut core = Core::new()?;
-    ///
-    /// let connector = HttpsConnector::new(1, &core.handle())?;
-    /// let client = Client::configure()
-    ///     .connector(connector)
-    ///     .build(&core.handle());
-    ///
-    /// let user_name = "Bob";
-    ///
-    /// let runner = client.search_users(|f| f.filter("name", user_name))
-    ///     .map(|resp| {
-    ///         println!(
-    ///             "There are {} results",
-    ///             resp.data.len(),
-    ///         );
-    ///     })
-    ///     .map_err(|why| {
-    ///         println!("Error with the request: {:?}", why);
-    ///     });
-    ///
-    /// core.run(runner)?;
-    /// ```
-    ///
-    /// [`Search`]: ../builder/struct.Search.html
-    ///
-    // Note: This doc example can not be tested due to the reliance on
-    // tokio_core. Instead, this is taken from example `02_hyper` and should
-    // roughly match it to ensure accuracy.
-    fn search_users<F: FnOnce(Search) -> Search>(&self, f: F)
-        -> Box<Future<Item = Response<Vec<User>>, Error = Error>>;
+    fn search_all<T, S>(&self, search: S) -> ItemStream<T>
+        where T: Resource + 'static, S: Into<Search> {
+        KitsuClient::new(self.clone()).search_all(search)
+    }
 }
 
-impl<B, C: Connect> KitsuRequester for HyperClient<C, B>
-    where B: Stream<Error = HyperError> + 'static, B::Item: AsRef<[u8]> {
-    fn get_anime(&self, id: u64)
-        -> Box<Future<Item = Response<Anime>, Error = Error>> {
-        let url = format!("{}/anime/{}", API_URL, id);
-        let c = &url;
-        let uri = try_uri!(c);
-
-        Box::new(self.get(uri)
-            .and_then(|res| res.body().concat2())
-            .map_err(From::from)
-            .and_then(|body| serde_json::from_slice(&body).map_err(From::from)))
-    }
+impl<C: FuturesHttpTransport + Clone + 'static> KitsuRequester for KitsuClient<C> {
+    requester_methods!(methods, client);
 
-    fn get_character(&self, id: u64)
-        -> Box<Future<Item = Response<Character>, Error = Error>> {
-        let url = format!("{}/characters/{}", API_URL, id);
-        let c = &url;
-        let uri = try_uri!(c);
+    fn search_pages<T, S>(&self, search: S) -> PageStream<T>
+        where T: Resource + 'static, S: Into<Search> {
+        let endpoint = endpoint::search_resources::<T>(search.into());
+        let client = self.clone();
 
-        Box::new(self.get(uri)
-            .and_then(|res| res.body().concat2())
-            .map_err(From::from)
-            .and_then(|body| serde_json::from_slice(&body).map_err(From::from)))
+        PageStream::new(endpoint.path, move |path| {
+            execute(&client, endpoint::page(path))
+        })
     }
 
-    fn get_manga(&self, id: u64)
-        -> Box<Future<Item = Response<Manga>, Error = Error>> {
-        let url = format!("{}/manga/{}", API_URL, id);
-        let c = &url;
-        let uri = try_uri!(c);
-
-        Box::new(self.get(uri)
-            .and_then(|res| res.body().concat2())
-            .map_err(From::from)
-            .and_then(|body| serde_json::from_slice(&body).map_err(From::from)))
+    fn search_all<T, S>(&self, search: S) -> ItemStream<T>
+        where T: Resource + 'static, S: Into<Search> {
+        self.search_pages(search).items()
     }
+}
 
-    fn get_producer(&self, id: u64)
-        -> Box<Future<Item = Response<Producer>, Error = Error>> {
-        let url = format!("{}/producer/{}", API_URL, id);
-        let c = &url;
-        let uri = try_uri!(c);
+/// Sends the request for an endpoint through the client's middleware and
+/// cache, and decodes its response.
+///
+/// The request's span is entered while the request is set up, and again each
+/// time the returned future is polled.
+fn execute<T, C>(client: &KitsuClient<C>, endpoint: Endpoint<T>)
+    -> Box<dyn Future<Item = T, Error = Error>>
+    where T: DeserializeOwned + 'static,
+          C: FuturesHttpTransport + Clone + 'static {
+    let mut request = client.request(endpoint);
+    let span = RequestSpan::new(&request, &client.base_url);
+
+    let sent = span.in_scope(|| {
+        if client.middleware.is_empty() {
+            return send_cached(client, request);
+        }
 
-        Box::new(self.get(uri)
-            .and_then(|res| res.body().concat2())
-            .map_err(From::from)
-            .and_then(|body| serde_json::from_slice(&body).map_err(From::from)))
-    }
+        let mut chain = Chain::new(&client.middleware);
+        let before = chain.before(&mut request);
+        let sent: Box<dyn Future<Item = _, Error = _>> = match before {
+            Ok(Some(response)) => Box::new(future::ok(response)),
+            Ok(None) => send_cached(client, request.clone()),
+            Err(why) => Box::new(future::err(why)),
+        };
 
-    fn get_user(&self, id: u64)
-        -> Box<Future<Item = Response<User>, Error = Error>> {
-        let url = format!("{}/users/{}", API_URL, id);
-        let c = &url;
-        let uri = try_uri!(c);
+        Box::new(sent.then(move |outcome| chain.after(&request, outcome)))
+    });
 
-        Box::new(self.get(uri)
-            .and_then(|res| res.body().concat2())
-            .map_err(From::from)
-            .and_then(|body| serde_json::from_slice(&body).map_err(From::from)))
-    }
+    let traced = span.clone();
+    let decoded = sent.then(move |outcome| {
+        span.record(&outcome);
 
-    fn search_anime<F: FnOnce(Search) -> Search>(&self, f: F)
-        -> Box<Future<Item = Response<Vec<Anime>>, Error = Error>> {
-        let params = f(Search::default()).0;
+        span.decode(outcome?)
+    });
 
-        let url = format!("{}/anime?{}", API_URL, params);
-        let c = &url;
-        let uri = try_uri!(c);
+    Box::new(traced.instrument_futures(decoded))
+}
 
-        Box::new(self.get(uri)
-            .and_then(|res| res.body().concat2())
-            .map_err(From::from)
-            .and_then(|body| serde_json::from_slice(&body).map_err(From::from)))
+/// Sends a request, or resolves to a fresh response from the client's
+/// [`HttpCache`] instead.
+///
+/// [`HttpCache`]: ../../cache/struct.HttpCache.html
+fn send_cached<C>(client: &KitsuClient<C>, mut request: HttpRequest)
+    -> Box<dyn Future<Item = HttpResponse, Error = Error>>
+    where C: FuturesHttpTransport + Clone + 'static {
+    let cache = match client.cache {
+        Some(ref cache) => cache.clone(),
+        None => return send_authorized(client, request),
+    };
+    let authorized = client.is_authorized(&request);
+    let stale = match cache.lookup(&mut request, authorized) {
+        Lookup::Fresh(response) => return Box::new(future::ok(response)),
+        Lookup::Miss(stale) => stale,
+    };
+    let (method, url) = (request.method, request.url.clone());
+
+    Box::new(send_authorized(client, request).map(move |response| {
+        cache.update(method, &url, authorized, stale, response)
+    }))
+}
+
+/// Sends a request, authenticating it if the client has an [`Auth`].
+///
+/// A token is obtained first if there is no usable one, and the request is
+/// sent again with a new token if the API rejects the current one.
+///
+/// [`Auth`]: ../../auth/struct.Auth.html
+fn send_authorized<C>(client: &KitsuClient<C>, request: HttpRequest)
+    -> Box<dyn Future<Item = HttpResponse, Error = Error>>
+    where C: FuturesHttpTransport + Clone + 'static {
+    let auth = match client.auth {
+        Some(ref auth) => Arc::clone(auth),
+        None => return send_retrying(client, request),
+    };
+    let pending = renew(client, &auth, None);
+    let client = client.clone();
+
+    Box::new(pending.and_then(move |_| {
+        let mut request = request;
+        auth.authorize(&mut request)?;
+
+        Ok((auth, client, request))
+    }).and_then(|(auth, client, request)| {
+        send_retrying(&client, request.clone()).and_then(move |response| {
+            if response.status != 401 {
+                return Box::new(future::ok(response))
+                    as Box<dyn Future<Item = _, Error = _>>;
+            }
+
+            let renewal = renew(&client, &auth, Some(request.clone()));
+
+            Box::new(renewal.and_then(move |renewed| {
+                if !renewed {
+                    return Box::new(future::ok(response))
+                        as Box<dyn Future<Item = _, Error = _>>;
+                }
+
+                let mut request = request;
+
+                match auth.authorize(&mut request) {
+                    Ok(()) => send_retrying(&client, request),
+                    Err(why) => Box::new(future::err(why)),
+                }
+            }))
+        })
+    }))
+}
+
+/// Renews the token of an [`Auth`] unless it is usable, waiting for a renewal
+/// by another request to finish instead of starting another.
+///
+/// Resolves to whether there is a token to send the request with, which is
+/// not the case if there were no grants to renew it with.
+///
+/// [`Auth`]: ../../auth/struct.Auth.html
+fn renew<C>(
+    client: &KitsuClient<C>,
+    auth: &Arc<Auth>,
+    rejected: Option<HttpRequest>,
+) -> Box<dyn Future<Item = bool, Error = Error>>
+    where C: FuturesHttpTransport + Clone + 'static {
+    let (sender, receiver) = oneshot::channel();
+    let renewal = Auth::renewal(auth, rejected.as_ref(), move || {
+        let _ = sender.send(());
+    });
+
+    match renewal {
+        Ok(Renewal::Usable) => Box::new(future::ok(true)),
+        Ok(Renewal::Pending) => {
+            let auth = Arc::clone(auth);
+            let client = client.clone();
+
+            Box::new(receiver.then(move |_| renew(&client, &auth, rejected)))
+        },
+        Ok(Renewal::Grants(_, ref grants)) if grants.is_empty() => {
+            Box::new(future::ok(false))
+        },
+        Ok(Renewal::Grants(guard, grants)) => {
+            let renewed = authenticate(client, auth, grants.into_iter());
+
+            Box::new(renewed.map(move |()| {
+                drop(guard);
+
+                true
+            }))
+        },
+        Err(why) => Box::new(future::err(why)),
     }
+}
 
-    fn search_characters<F: FnOnce(Search) -> Search>(&self, f: F)
-        -> Box<Future<Item = Response<Vec<Character>>, Error = Error>> {
-        let params = f(Search::default()).0;
+/// Requests a token with each grant in turn until one succeeds, storing it in
+/// the [`Auth`] once it is obtained.
+///
+/// The error of the last grant is returned if every grant fails.
+///
+/// [`Auth`]: ../../auth/struct.Auth.html
+fn authenticate<C>(
+    client: &KitsuClient<C>,
+    auth: &Arc<Auth>,
+    mut grants: IntoIter<Endpoint<Token>>,
+) -> Box<dyn Future<Item = (), Error = Error>>
+    where C: FuturesHttpTransport + Clone + 'static {
+    let grant = match grants.next() {
+        Some(grant) => grant,
+        None => return Box::new(future::ok(())),
+    };
+    let auth = Arc::clone(auth);
+    let client = client.clone();
+
+    Box::new(send_retrying(&client, client.request(grant))
+        .and_then(endpoint::decode)
+        .then(move |token| match token {
+            Ok(token) => Box::new(future::result(auth.update(token))),
+            Err(why) if grants.len() == 0 => Box::new(future::err(why)),
+            Err(_) => authenticate(&client, &auth, grants),
+        }))
+}
+
+/// Sends a request, sending it again after a delay for as long as the
+/// client's [`RetryPolicy`] allows.
+///
+/// [`RetryPolicy`]: ../../retry/struct.RetryPolicy.html
+fn send_retrying<C>(client: &KitsuClient<C>, request: HttpRequest)
+    -> Box<dyn Future<Item = HttpResponse, Error = Error>>
+    where C: FuturesHttpTransport + Clone + 'static {
+    match client.retry {
+        Some(ref policy) => retry(client.clone(), policy.clone(), request, 1),
+        None => send_limited(client, request),
+    }
+}
 
-        let url = format!("{}/characters?{}", API_URL, params);
-        let c = &url;
-        let uri = try_uri!(c);
+/// Sends an attempt of a request, and then retries it if the policy allows.
+fn retry<C>(
+    client: KitsuClient<C>,
+    policy: RetryPolicy,
+    request: HttpRequest,
+    attempt: u32,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>>
+    where C: FuturesHttpTransport + Clone + 'static {
+    Box::new(send_limited(&client, request.clone()).then(move |outcome| {
+        let delay = match policy.delay(&request, attempt, &outcome) {
+            Some(delay) => delay,
+            None => {
+                return Box::new(future::result(outcome))
+                    as Box<dyn Future<Item = _, Error = _>>;
+            },
+        };
+
+        trace::retry(attempt + 1, delay);
+
+        Box::new(client.client.sleep(delay).and_then(move |()| {
+            retry(client, policy, request, attempt + 1)
+        }))
+    }))
+}
 
-        Box::new(self.get(uri)
-            .and_then(|res| res.body().concat2())
-            .map_err(From::from)
-            .and_then(|body| serde_json::from_slice(&body).map_err(From::from)))
+/// Sends a request once the client's [`RateLimiter`] allows it.
+///
+/// [`RateLimiter`]: ../../rate_limit/struct.RateLimiter.html
+fn send_limited<C>(client: &KitsuClient<C>, request: HttpRequest)
+    -> Box<dyn Future<Item = HttpResponse, Error = Error>>
+    where C: FuturesHttpTransport + Clone + 'static {
+    let delay = client.rate_limit
+        .as_ref()
+        .map_or(Duration::from_secs(0), RateLimiter::reserve);
+
+    if delay == Duration::from_secs(0) {
+        return client.client.send(request);
     }
 
-    fn search_manga<F: FnOnce(Search) -> Search>(&self, f: F)
-        -> Box<Future<Item = Response<Vec<Manga>>, Error = Error>> {
-        let params = f(Search::default()).0;
+    let transport = client.client.clone();
 
-        let url = format!("{}/manga?{}", API_URL, params);
-        let c = &url;
-        let uri = try_uri!(c);
+    Box::new(client.client.sleep(delay).and_then(move |()| {
+        transport.send(request)
+    }))
+}
 
-        Box::new(self.get(uri)
-            .and_then(|res| res.body().concat2())
-            .map_err(From::from)
-            .and_then(|body| serde_json::from_slice(&body).map_err(From::from)))
-    }
+impl<B, C: Connect> FuturesHttpTransport for HyperClient<C, B>
+    where B: From<Vec<u8>> + Stream<Error = HyperError> + 'static,
+          B::Item: AsRef<[u8]> {
+    /// Sends a request with the hyper Client, collecting the response's
+    /// status, headers, and body.
+    fn send(&self, request: HttpRequest)
+        -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+        let uri = match Uri::from_str(&request.url) {
+            Ok(uri) => uri,
+            Err(why) => return Box::new(future::err(Error::from(why))),
+        };
+        let method = match request.method {
+            Method::Delete => HyperMethod::Delete,
+            Method::Get => HyperMethod::Get,
+            Method::Patch => HyperMethod::Patch,
+            Method::Post => HyperMethod::Post,
+        };
+
+        let mut hyper_request = Request::new(method, uri);
+
+        for (name, value) in request.headers {
+            hyper_request.headers_mut().append_raw(name, value);
+        }
 
-    fn search_users<F: FnOnce(Search) -> Search>(&self, f: F)
-        -> Box<Future<Item = Response<Vec<User>>, Error = Error>> {
-        let params = f(Search::default()).0;
+        if let Some(body) = request.body {
+            hyper_request.set_body(body);
+        }
 
-        let url = format!("{}/users?{}", API_URL, params);
-        let c = &url;
-        let uri = try_uri!(c);
+        Box::new(self.request(hyper_request)
+            .and_then(|res| {
+                let status = res.status().as_u16();
+                let headers = res.headers()
+                    .iter()
+                    .map(|header| (header.name().to_owned(), header.value_string()))
+                    .collect::<Vec<_>>();
+
+                res.body().concat2().map(move |body| HttpResponse {
+                    body: body.to_vec(),
+                    headers,
+                    status,
+                })
+            })
+            .map_err(From::from))
+    }
 
-        Box::new(self.get(uri)
-            .and_then(|res| res.body().concat2())
-            .map_err(From::from)
-            .and_then(|body| serde_json::from_slice(&body).map_err(From::from)))
+    /// Resolves after a duration on the timer of the event loop the future
+    /// is run on, such as the `tokio-core` Core driving the Client.
+    ///
+    /// Resolves to [`Error::Transport`] if it is run outside of one.
+    ///
+    /// [`Error::Transport`]: ../../enum.Error.html#variant.Transport
+    fn sleep(&self, duration: Duration)
+        -> Box<dyn Future<Item = (), Error = Error>> {
+        Box::new(Delay::new(Instant::now() + duration).map_err(|why| {
+            Error::Transport(Box::new(why))
+        }))
     }
 }
@@ -4,3 +4,5 @@
 pub mod hyper;
 #[cfg(feature = "reqwest")]
 pub mod reqwest;
+#[cfg(feature = "reqwest_async")]
+pub mod reqwest_async;
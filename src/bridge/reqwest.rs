@@ -6,13 +6,54 @@
 //!
 //! [`KitsuRequester`]: trait.KitsuRequester.html
 
-use std::io::Read;
-use reqwest::{Client as ReqwestClient, RequestBuilder, StatusCode, Url};
-use serde::de::DeserializeOwned;
-use serde_json;
-use ::builder::Search;
-use ::{API_URL, Error, Result};
-use ::model::{Anime, Character, Manga, Producer, Response, User};
+use reqwest::{Client as ReqwestClient, Method as ReqwestMethod, Url};
+use crate::builder::Search;
+use crate::client::KitsuClient;
+use crate::endpoint::{self, requester_methods, IntoEndpoint};
+use crate::pagination::{Items, Pages};
+use crate::Result;
+use crate::model::Resource;
+use crate::transport::{HttpRequest, HttpResponse, HttpTransport, Method};
+
+/// Declares or implements the methods generated from the table in
+/// `requester_methods!`.
+macro_rules! methods {
+    ($mode:ident $(
+        $(#[$attr:meta])*
+        fn $name:ident[$($generic:tt)*]($($arg:ident: $ty:ty),* $(,)?)
+            -> $output:ty
+            where [$($bound:tt)*]
+            => $endpoint:expr;
+    )*) => {
+        $(
+            methods!(@$mode
+                [$(#[$attr])*]
+                $name[$($generic)*]($($arg: $ty),*) -> $output
+                where [$($bound)*]
+                => $endpoint);
+        )*
+    };
+    (@declare [$($attr:tt)*] $name:ident[$($generic:tt)*]($($arg:ident: $ty:ty),*)
+        -> $output:ty where [$($bound:tt)*] => $endpoint:expr) => {
+        $($attr)*
+        fn $name<$($generic)*>(&self, $($arg: $ty),*) -> Result<$output>
+            where $($bound)*;
+    };
+    (@client [$($attr:tt)*] $name:ident[$($generic:tt)*]($($arg:ident: $ty:ty),*)
+        -> $output:ty where [$($bound:tt)*] => $endpoint:expr) => {
+        fn $name<$($generic)*>(&self, $($arg: $ty),*) -> Result<$output>
+            where $($bound)* {
+            self.execute(IntoEndpoint::into_endpoint($endpoint)?)
+        }
+    };
+    (@bare [$($attr:tt)*] $name:ident[$($generic:tt)*]($($arg:ident: $ty:ty),*)
+        -> $output:ty where [$($bound:tt)*] => $endpoint:expr) => {
+        fn $name<$($generic)*>(&self, $($arg: $ty),*) -> Result<$output>
+            where $($bound)* {
+            KitsuClient::new(self).$name($($arg),*)
+        }
+    };
+}
 
 /// Trait which defines the methods necessary to interact with the service.
 ///
@@ -25,463 +66,243 @@ use ::model::{Anime, Character, Manga, Producer, Response, User};
 /// use kitsu::KitsuReqwestRequester;
 /// ```
 ///
-/// At this point, the methods will be on your Reqwest Client.
+/// At this point, the methods will be on your Reqwest Client, sending
+/// requests to [`API_URL`].
+///
+/// To use a different base URL or send additional headers, wrap the Client
+/// in a [`KitsuClient`], which also implements this trait. A `KitsuClient`
+/// wrapping any other [`HttpTransport`] implements it too.
+///
+/// Get an anime using its id:
+///
+/// ```rust,no_run
+/// extern crate kitsu;
+/// extern crate reqwest;
+///
+/// use kitsu::KitsuReqwestRequester;
+/// use reqwest::Client;
+///
+/// fn main() {
+///     // Create the reqwest Client.
+///     let client = Client::new();
+///
+///     let anime_id = 1;
+///
+///     // Get the anime.
+///     let anime = client.get_anime(anime_id)
+///         .expect("Error getting anime");
+///
+///     // Do something with anime
+/// }
+/// ```
+///
+/// Search for a user by name:
+///
+/// ```rust,no_run
+/// extern crate kitsu;
+/// extern crate reqwest;
+///
+/// use kitsu::KitsuReqwestRequester;
+/// use reqwest::Client;
+///
+/// fn main() {
+///     // Create the reqwest Client.
+///     let client = Client::new();
+///
+///     let user_name = "Billy";
+///
+///     // Search for the user.
+///     let user = client.search_users(|f| f.name(user_name))
+///         .expect("Error searching for user");
+///
+///     // Do something with users
+/// }
+/// ```
+///
+/// Get an anime along with its genres, and then the genres again by
+/// following the anime's relationship:
+///
+/// ```rust,no_run
+/// extern crate kitsu;
+/// extern crate reqwest;
+///
+/// use kitsu::model::{Anime, Genre, Response};
+/// use kitsu::KitsuReqwestRequester;
+/// use reqwest::Client;
+///
+/// fn main() {
+///     let client = Client::new();
+///
+///     let anime = client.get_resource::<Anime, _>(1, |f| f.include("genres"))
+///         .expect("Error getting anime");
+///     let genres: Vec<Genre> = anime.included.all()
+///         .expect("Error decoding genres");
+///
+///     if let Some(ref genres) = anime.data.relationships.genres {
+///         let genres: Response<Vec<Genre>> = client
+///             .fetch_related(genres)
+///             .expect("Error getting genres");
+///     }
+/// }
+/// ```
+///
+/// Add the anime with the id of 1 to the library of the user with the id of
+/// 1, as currently watching:
+///
+/// ```rust,no_run
+/// extern crate kitsu;
+/// extern crate reqwest;
+///
+/// use kitsu::auth::Auth;
+/// use kitsu::model::{LibraryEntryStatus, LibraryMedia};
+/// use kitsu::{KitsuClient, KitsuReqwestRequester};
+/// use reqwest::Client;
+///
+/// fn main() {
+///     let client = KitsuClient::new(Client::new())
+///         .auth(Auth::password("user@example.com", "hunter2"));
+///
+///     let entry = client.create_library_entry(
+///         1,
+///         LibraryMedia::Anime(1),
+///         |e| e.status(LibraryEntryStatus::Current).progress(1),
+///     ).expect("Error creating library entry");
+///
+///     // Do something with the entry
+/// }
+/// ```
+///
+/// [`API_URL`]: ../../constant.API_URL.html
+/// [`HttpTransport`]: ../../transport/trait.HttpTransport.html
+/// [`KitsuClient`]: ../../client/struct.KitsuClient.html
 pub trait KitsuRequester {
-    /// Gets an anime using its id.
-    ///
-    /// # Examples
-    ///
-    ///
-    ///
-    /// ```rust,no_run
-    /// extern crate kitsu;
-    /// extern crate reqwest;
-    ///
-    /// use kitsu::KitsuReqwestRequester;
-    /// use reqwest::Client;
-    ///
-    /// fn main() {
-    ///     // Create the reqwest Client.
-    ///     let client = Client::new();
-    ///
-    ///     let anime_id = 1;
-    ///
-    ///     // Get the anime.
-    ///     let anime = client.get_anime(anime_id)
-    ///         .expect("Error getting anime");
-    ///
-    ///     // Do something with anime
-    /// }
-    /// ```
-    ///
-    /// # Errors
-    ///
-    /// Returns [`Error::Json`] if there was an error parsing the response
-    /// body.
-    ///
-    /// Returns [`Error::ReqwestBad`] if the request was otherwise bad for some
-    /// reason, containing the response.
-    ///
-    /// Returns [`Error::ReqwestInvalid`] if the response was a non-OK (status
-    /// code 200) response, containing the response.
-    ///
-    /// Returns [`Error::ReqwestParse`] if there was an error parsing the image
-    /// parameters into a valid URL.
-    ///
-    /// Returns [`Error::ReqwestUnauthorized`] if the authorization token was
-    /// invalid.
-    ///
-    /// [`Error::Json`]: ../enum.Error.html#variant.Json
-    /// [`Error::NoParamsSpecified`]: ../enum.Error.html#variant.NoParamsSpecified
-    /// [`Error::ReqwestBad`]: ../enum.Error.html#variant.ReqwestBad
-    /// [`Error::ReqwestInvalid`]: ../enum.Error.html#variant.ReqwestInvalid
-    /// [`Error::ReqwestParse`]: ../enum.Error.html#variant.ReqwestParse
-    /// [`Error::ReqwestUnauthorized`]: ../enum.Error.html#variant.ReqwestUnauthorized
-    fn get_anime(&self, id: u64) -> Result<Response<Anime>>;
-
-    /// Gets a character using its id.
-    fn get_character(&self, id: u64) -> Result<Response<Character>>;
+    requester_methods!(methods, declare);
 
-    /// Gets a manga using its id.
-    ///
-    /// # Examples
-    ///
-    ///
-    ///
-    /// ```rust,no_run
-    /// extern crate kitsu;
-    /// extern crate reqwest;
-    ///
-    /// use kitsu::KitsuReqwestRequester;
-    /// use reqwest::Client;
-    ///
-    /// fn main() {
-    ///     // Create the reqwest Client.
-    ///     let client = Client::new();
-    ///
-    ///     let manga_id = 1;
-    ///
-    ///     // Get the manga.
-    ///     let manga = client.get_anime(manga_id)
-    ///         .expect("Error getting manga");
-    ///
-    ///     // Do something with manga
-    /// }
-    /// ```
-    ///
-    /// # Errors
-    ///
-    /// Returns [`Error::Json`] if there was an error parsing the response
-    /// body.
+    /// Searches for resources of any type, returning an iterator over every
+    /// page of results.
     ///
-    /// Returns [`Error::ReqwestBad`] if the request was otherwise bad for some
-    /// reason, containing the response.
+    /// The search may be a [`Search`], a [`Query`], or the typed search
+    /// builder of the resource, such as an [`AnimeSearch`].
     ///
-    /// Returns [`Error::ReqwestInvalid`] if the response was a non-OK (status
-    /// code 200) response, containing the response.
+    /// Pages are requested lazily, following the `next` link of each page.
     ///
-    /// Returns [`Error::ReqwestParse`] if there was an error parsing the image
-    /// parameters into a valid URL.
-    ///
-    /// Returns [`Error::ReqwestUnauthorized`] if the authorization token was
-    /// invalid.
-    ///
-    /// [`Error::Json`]: ../enum.Error.html#variant.Json
-    /// [`Error::NoParamsSpecified`]: ../enum.Error.html#variant.NoParamsSpecified
-    /// [`Error::ReqwestBad`]: ../enum.Error.html#variant.ReqwestBad
-    /// [`Error::ReqwestInvalid`]: ../enum.Error.html#variant.ReqwestInvalid
-    /// [`Error::ReqwestParse`]: ../enum.Error.html#variant.ReqwestParse
-    /// [`Error::ReqwestUnauthorized`]: ../enum.Error.html#variant.ReqwestUnauthorized
-    fn get_manga(&self, id: u64) -> Result<Response<Manga>>;
+    /// [`AnimeSearch`]: ../../builder/struct.AnimeSearch.html
+    /// [`Query`]: ../../builder/struct.Query.html
+    /// [`Search`]: ../../builder/struct.Search.html
+    fn search_pages<T, S>(&self, search: S) -> Pages<'_, T>
+        where T: Resource, S: Into<Search>;
 
-    /// Gets a producer using its id
-    /// 
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// extern crate kitsu;
-    /// extern crate reqwest;
-    ///
-    /// use kitsu::KitsuReqwestRequester;
-    /// use reqwest::Client;
+    /// Searches for resources of any type, returning an iterator over the
+    /// results of every page.
     ///
-    /// fn main() {
-    ///     // Create the reqwest Client.
-    ///     let client = Client::new();
-    ///
-    ///     let producer_id = 1;
-    ///
-    ///     // Get the anime.
-    ///     let anime = client.get_producer(producer_id)
-    ///         .expect("Error getting producer");
-    ///
-    ///     // Do something with producer
-    /// }
-    /// ```
+    /// The search may be a [`Search`], a [`Query`], or the typed search
+    /// builder of the resource, such as an [`AnimeSearch`].
     ///
-    /// # Errors
-    ///
-    /// Returns [`Error::Json`] if there was an error parsing the response
-    /// body.
-    ///
-    /// Returns [`Error::ReqwestBad`] if the request was otherwise bad for some
-    /// reason, containing the response.
-    ///
-    /// Returns [`Error::ReqwestInvalid`] if the response was a non-OK (status
-    /// code 200) response, containing the response.
-    ///
-    /// Returns [`Error::ReqwestParse`] if there was an error parsing the image
-    /// parameters into a valid URL.
-    ///
-    /// Returns [`Error::ReqwestUnauthorized`] if the authorization token was
-    /// invalid.
-    ///
-    /// [`Error::Json`]: ../enum.Error.html#variant.Json
-    /// [`Error::NoParamsSpecified`]: ../enum.Error.html#variant.NoParamsSpecified
-    /// [`Error::ReqwestBad`]: ../enum.Error.html#variant.ReqwestBad
-    /// [`Error::ReqwestInvalid`]: ../enum.Error.html#variant.ReqwestInvalid
-    /// [`Error::ReqwestParse`]: ../enum.Error.html#variant.ReqwestParse
-    /// [`Error::ReqwestUnauthorized`]: ../enum.Error.html#variant.ReqwestUnauthorized
-    fn get_producer(&self, id: u64) -> Result<Response<Producer>>;
-
-    /// Gets a user using their id.
+    /// Pages are requested lazily, following the `next` link of each page.
     ///
     /// # Examples
     ///
-    ///
-    ///
-    /// ```rust,no_run
-    /// extern crate kitsu;
-    /// extern crate reqwest;
-    ///
-    /// use kitsu::KitsuReqwestRequester;
-    /// use reqwest::Client;
-    ///
-    /// fn main() {
-    ///     // Create the reqwest Client.
-    ///     let client = Client::new();
-    ///
-    ///     let user_id = 1;
-    ///
-    ///     // Get the user.
-    ///     let user = client.get_anime(user_id)
-    ///         .expect("Error getting user");
-    ///
-    ///     // Do something with user
-    /// }
-    /// ```
-    ///
-    /// # Errors
-    ///
-    /// Returns [`Error::Json`] if there was an error parsing the response
-    /// body.
-    ///
-    /// Returns [`Error::ReqwestBad`] if the request was otherwise bad for some
-    /// reason, containing the response.
-    ///
-    /// Returns [`Error::ReqwestInvalid`] if the response was a non-OK (status
-    /// code 200) response, containing the response.
-    ///
-    /// Returns [`Error::ReqwestParse`] if there was an error parsing the image
-    /// parameters into a valid URL.
-    ///
-    /// Returns [`Error::ReqwestUnauthorized`] if the authorization token was
-    /// invalid.
-    ///
-    /// [`Error::Json`]: ../enum.Error.html#variant.Json
-    /// [`Error::NoParamsSpecified`]: ../enum.Error.html#variant.NoParamsSpecified
-    /// [`Error::ReqwestBad`]: ../enum.Error.html#variant.ReqwestBad
-    /// [`Error::ReqwestInvalid`]: ../enum.Error.html#variant.ReqwestInvalid
-    /// [`Error::ReqwestParse`]: ../enum.Error.html#variant.ReqwestParse
-    /// [`Error::ReqwestUnauthorized`]: ../enum.Error.html#variant.ReqwestUnauthorized
-    fn get_user(&self, id: u64) -> Result<Response<User>>;
-
-    /// Gets an anime using its id.
-    ///
-    /// # Examples
-    ///
-    ///
+    /// Get the first 50 anime with "bebop" in their titles:
     ///
     /// ```rust,no_run
     /// extern crate kitsu;
     /// extern crate reqwest;
     ///
+    /// use kitsu::builder::AnimeSearch;
+    /// use kitsu::model::Anime;
     /// use kitsu::KitsuReqwestRequester;
     /// use reqwest::Client;
     ///
     /// fn main() {
-    ///     // Create the reqwest Client.
     ///     let client = Client::new();
+    ///     let search = AnimeSearch::default().text("bebop");
     ///
-    ///     let anime_name = "Your Lie in April";
-    ///
-    ///     // Search for the anime.
-    ///     let anime = client.search_anime(|f| f.filter("text", anime_name))
+    ///     let anime = client.search_all::<Anime, _>(search)
+    ///         .page_size(20)
+    ///         .max_items(50)
+    ///         .collect::<Result<Vec<_>, _>>()
     ///         .expect("Error searching for anime");
-    ///
-    ///     // Do something with anime
     /// }
     /// ```
     ///
-    /// # Errors
-    ///
-    /// Returns [`Error::Json`] if there was an error parsing the response
-    /// body.
-    ///
-    /// Returns [`Error::ReqwestBad`] if the request was otherwise bad for some
-    /// reason, containing the response.
-    ///
-    /// Returns [`Error::ReqwestInvalid`] if the response was a non-OK (status
-    /// code 200) response, containing the response.
-    ///
-    /// Returns [`Error::ReqwestParse`] if there was an error parsing the image
-    /// parameters into a valid URL.
-    ///
-    /// Returns [`Error::ReqwestUnauthorized`] if the authorization token was
-    /// invalid.
-    ///
-    /// [`Error::Json`]: ../enum.Error.html#variant.Json
-    /// [`Error::NoParamsSpecified`]: ../enum.Error.html#variant.NoParamsSpecified
-    /// [`Error::ReqwestBad`]: ../enum.Error.html#variant.ReqwestBad
-    /// [`Error::ReqwestInvalid`]: ../enum.Error.html#variant.ReqwestInvalid
-    /// [`Error::ReqwestParse`]: ../enum.Error.html#variant.ReqwestParse
-    /// [`Error::ReqwestUnauthorized`]: ../enum.Error.html#variant.ReqwestUnauthorized
-    fn search_anime<F: FnOnce(Search) -> Search>(&self, f: F) ->
-        Result<Response<Vec<Anime>>>;
-
-    /// Searches for a character.
-    fn search_characters<F: FnOnce(Search) -> Search>(&self, f: F)
-        -> Result<Response<Vec<Character>>>;
-
-    /// Gets an anime using its id.
-    ///
-    /// # Examples
-    ///
-    ///
-    ///
-    /// ```rust,no_run
-    /// extern crate kitsu;
-    /// extern crate reqwest;
-    ///
-    /// use kitsu::KitsuReqwestRequester;
-    /// use reqwest::Client;
-    ///
-    /// fn main() {
-    ///     // Create the reqwest Client.
-    ///     let client = Client::new();
-    ///
-    ///     let manga_name = "Say I Love You";
-    ///
-    ///     // Search for the manga.
-    ///     let manga = client.search_manga(|f| f.filter("text", manga_name))
-    ///         .expect("Error getting manga");
-    ///
-    ///     // Do something with manga
-    /// }
-    /// ```
-    ///
-    /// # Errors
-    ///
-    /// Returns [`Error::Json`] if there was an error parsing the response
-    /// body.
-    ///
-    /// Returns [`Error::ReqwestBad`] if the request was otherwise bad for some
-    /// reason, containing the response.
-    ///
-    /// Returns [`Error::ReqwestInvalid`] if the response was a non-OK (status
-    /// code 200) response, containing the response.
-    ///
-    /// Returns [`Error::ReqwestParse`] if there was an error parsing the image
-    /// parameters into a valid URL.
-    ///
-    /// Returns [`Error::ReqwestUnauthorized`] if the authorization token was
-    /// invalid.
-    ///
-    /// [`Error::Json`]: ../enum.Error.html#variant.Json
-    /// [`Error::NoParamsSpecified`]: ../enum.Error.html#variant.NoParamsSpecified
-    /// [`Error::ReqwestBad`]: ../enum.Error.html#variant.ReqwestBad
-    /// [`Error::ReqwestInvalid`]: ../enum.Error.html#variant.ReqwestInvalid
-    /// [`Error::ReqwestParse`]: ../enum.Error.html#variant.ReqwestParse
-    /// [`Error::ReqwestUnauthorized`]: ../enum.Error.html#variant.ReqwestUnauthorized
-    fn search_manga<F: FnOnce(Search) -> Search>(&self, f: F) ->
-        Result<Response<Vec<Manga>>>;
-
-    /// Gets an anime using its id.
-    ///
-    /// # Examples
-    ///
-    ///
-    ///
-    /// ```rust,no_run
-    /// extern crate kitsu;
-    /// extern crate reqwest;
-    ///
-    /// use kitsu::KitsuReqwestRequester;
-    /// use reqwest::Client;
-    ///
-    /// fn main() {
-    ///     // Create the reqwest Client.
-    ///     let client = Client::new();
-    ///
-    ///     let user_name = "Billy";
-    ///
-    ///     // Search for the user.
-    ///     let user = client.search_users(|f| f.filter("name", user_name))
-    ///         .expect("Error searching for user");
-    ///
-    ///     // Do something with users
-    /// }
-    /// ```
-    ///
-    /// # Errors
-    ///
-    /// Returns [`Error::Json`] if there was an error parsing the response
-    /// body.
-    ///
-    /// Returns [`Error::ReqwestBad`] if the request was otherwise bad for some
-    /// reason, containing the response.
-    ///
-    /// Returns [`Error::ReqwestInvalid`] if the response was a non-OK (status
-    /// code 200) response, containing the response.
-    ///
-    /// Returns [`Error::ReqwestParse`] if there was an error parsing the image
-    /// parameters into a valid URL.
-    ///
-    /// Returns [`Error::ReqwestUnauthorized`] if the authorization token was
-    /// invalid.
-    ///
-    /// [`Error::Json`]: ../enum.Error.html#variant.Json
-    /// [`Error::NoParamsSpecified`]: ../enum.Error.html#variant.NoParamsSpecified
-    /// [`Error::ReqwestBad`]: ../enum.Error.html#variant.ReqwestBad
-    /// [`Error::ReqwestInvalid`]: ../enum.Error.html#variant.ReqwestInvalid
-    /// [`Error::ReqwestParse`]: ../enum.Error.html#variant.ReqwestParse
-    /// [`Error::ReqwestUnauthorized`]: ../enum.Error.html#variant.ReqwestUnauthorized
-    fn search_users<F: FnOnce(Search) -> Search>(&self, f: F) ->
-        Result<Response<Vec<User>>>;
+    /// [`AnimeSearch`]: ../../builder/struct.AnimeSearch.html
+    /// [`Query`]: ../../builder/struct.Query.html
+    /// [`Search`]: ../../builder/struct.Search.html
+    fn search_all<T, S>(&self, search: S) -> Items<'_, T>
+        where T: Resource, S: Into<Search>;
 }
 
 impl KitsuRequester for ReqwestClient {
-    fn get_anime(&self, id: u64) -> Result<Response<Anime>> {
-        let uri = Url::parse(&format!("{}/anime/{}", API_URL, id.to_string()))?;
+    requester_methods!(methods, bare);
 
-        handle_request::<Response<Anime>>(self.get(uri))
-    }
-
-    fn get_character(&self, id: u64) -> Result<Response<Character>> {
-        let uri = Url::parse(&format!("{}/characters/{}", API_URL, id.to_string()))?;
+    fn search_pages<T, S>(&self, search: S) -> Pages<'_, T>
+        where T: Resource, S: Into<Search> {
+        let endpoint = endpoint::search_resources::<T>(search.into());
 
-        handle_request::<Response<Character>>(self.get(uri))
+        Pages::new(KitsuClient::new(self as &dyn HttpTransport), endpoint.path)
     }
 
-    fn get_manga(&self, id: u64) -> Result<Response<Manga>> {
-        let uri = Url::parse(&format!("{}/manga/{}", API_URL, id.to_string()))?;
-
-        handle_request::<Response<Manga>>(self.get(uri))
+    fn search_all<T, S>(&self, search: S) -> Items<'_, T>
+        where T: Resource, S: Into<Search> {
+        self.search_pages(search).items()
     }
+}
 
-    fn get_user(&self, id: u64) -> Result<Response<User>> {
-        let uri = Url::parse(&format!("{}/users/{}", API_URL, id.to_string()))?;
-
-        handle_request::<Response<User>>(self.get(uri))
-    }
-
-    fn get_producer(&self, id: u64) -> Result<Response<Producer>> {
-        let uri = Url::parse(&format!("{}/producers/{}", API_URL, id.to_string()))?;
-
-        handle_request::<Response<Producer>>(self.get(uri))
-    }
+impl<C: HttpTransport> KitsuRequester for KitsuClient<C> {
+    requester_methods!(methods, client);
 
-    fn search_anime<F: FnOnce(Search) -> Search>(&self, f: F) ->
-        Result<Response<Vec<Anime>>> {
-        let params = f(Search::default()).0;
-        let uri = Url::parse(&format!("{}/anime?{}", API_URL, params))?;
+    fn search_pages<T, S>(&self, search: S) -> Pages<'_, T>
+        where T: Resource, S: Into<Search> {
+        let endpoint = endpoint::search_resources::<T>(search.into());
+        let client = self.with_client(&self.client as &dyn HttpTransport);
 
-        handle_request::<Response<Vec<Anime>>>(self.get(uri))
+        Pages::new(client, endpoint.path)
     }
 
-    fn search_characters<F: FnOnce(Search) -> Search>(&self, f: F) ->
-        Result<Response<Vec<Character>>> {
-        let params = f(Search::default()).0;
-        let uri = Url::parse(&format!("{}/characters?{}", API_URL, params))?;
-
-        handle_request::<Response<Vec<Character>>>(self.get(uri))
-    }
-
-    fn search_manga<F: FnOnce(Search) -> Search>(&self, f: F) ->
-        Result<Response<Vec<Manga>>> {
-        let params = f(Search::default()).0;
-        let uri = Url::parse(&format!("{}/manga?{}", API_URL, params))?;
-
-        handle_request::<Response<Vec<Manga>>>(self.get(uri))
-    }
-
-    fn search_users<F: FnOnce(Search) -> Search>(&self, f: F) ->
-        Result<Response<Vec<User>>> {
-        let params = f(Search::default()).0;
-        let uri = Url::parse(&format!("{}/users?{}", API_URL, params))?;
-
-        handle_request::<Response<Vec<User>>>(self.get(uri))
+    fn search_all<T, S>(&self, search: S) -> Items<'_, T>
+        where T: Resource, S: Into<Search> {
+        self.search_pages(search).items()
     }
 }
 
-fn handle_request<T: DeserializeOwned>(request: RequestBuilder) -> Result<T> {
-    let response = request.send()?;
-
-    match response.status() {
-        StatusCode::OK => {},
-        StatusCode::BAD_REQUEST => {
-            return Err(Error::ReqwestBad(Box::new(response)));
-        },
-        StatusCode::UNAUTHORIZED => {
-            return Err(Error::ReqwestUnauthorized(Box::new(response)));
-        },
-        _ => return Err(Error::ReqwestInvalid(Box::new(response))),
+impl HttpTransport for ReqwestClient {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let url = Url::parse(&request.url)?;
+        let method = match request.method {
+            Method::Delete => ReqwestMethod::DELETE,
+            Method::Get => ReqwestMethod::GET,
+            Method::Patch => ReqwestMethod::PATCH,
+            Method::Post => ReqwestMethod::POST,
+        };
+
+        let mut builder = self.request(method, url);
+
+        for (name, value) in &request.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+
+        let mut response = builder.send()?;
+        let status = response.status().as_u16();
+        let headers = response.headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                let value = value.to_str().ok()?;
+
+                Some((name.as_str().to_owned(), value.to_owned()))
+            })
+            .collect();
+
+        let mut body = Vec::new();
+        response.copy_to(&mut body)?;
+
+        Ok(HttpResponse {
+            body,
+            headers,
+            status,
+        })
     }
-
-    from_reader(response)
-}
-
-#[inline]
-fn from_reader<T: DeserializeOwned, U: Read>(reader: U) -> Result<T> {
-    serde_json::from_reader(reader).map_err(From::from)
 }
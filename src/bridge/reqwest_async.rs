@@ -0,0 +1,298 @@
+//! Bridge to provide a client implementation for the asynchronous `reqwest`
+//! Client, built on `std` futures.
+//!
+//! # Examples
+//!
+//! Refer to the documentation for [`KitsuRequester`].
+//!
+//! [`KitsuRequester`]: trait.KitsuRequester.html
+
+use reqwest_async::{
+    Client as ReqwestClient,
+    Error as ReqwestError,
+    Method as ReqwestMethod,
+};
+use std::future::Future;
+use crate::builder::Search;
+use crate::client::KitsuClient;
+use crate::endpoint::{self, requester_methods, IntoEndpoint};
+use crate::model::Resource;
+use crate::pagination::{ItemStream, PageStream};
+use crate::transport::{
+    AsyncHttpTransport,
+    BoxFuture,
+    HttpRequest,
+    HttpResponse,
+    Method,
+};
+use crate::{Error, Result};
+
+/// Declares or implements the methods generated from the table in
+/// `requester_methods!`.
+macro_rules! methods {
+    ($mode:ident $(
+        $(#[$attr:meta])*
+        fn $name:ident[$($generic:tt)*]($($arg:ident: $ty:ty),* $(,)?)
+            -> $output:ty
+            where [$($bound:tt)*]
+            => $endpoint:expr;
+    )*) => {
+        $(
+            methods!(@$mode
+                [$(#[$attr])*]
+                $name[$($generic)*]($($arg: $ty),*) -> $output
+                where [$($bound)*]
+                => $endpoint);
+        )*
+    };
+    (@declare [$($attr:tt)*] $name:ident[$($generic:tt)*]($($arg:ident: $ty:ty),*)
+        -> $output:ty where [$($bound:tt)*] => $endpoint:expr) => {
+        $($attr)*
+        fn $name<$($generic)*>(&self, $($arg: $ty),*)
+            -> impl Future<Output = Result<$output>> + Send
+            where $($bound)*;
+    };
+    (@client [$($attr:tt)*] $name:ident[$($generic:tt)*]($($arg:ident: $ty:ty),*)
+        -> $output:ty where [$($bound:tt)*] => $endpoint:expr) => {
+        fn $name<$($generic)*>(&self, $($arg: $ty),*)
+            -> impl Future<Output = Result<$output>> + Send
+            where $($bound)* {
+            let endpoint = IntoEndpoint::into_endpoint($endpoint);
+
+            async move { self.execute_async(endpoint?).await }
+        }
+    };
+    (@bare [$($attr:tt)*] $name:ident[$($generic:tt)*]($($arg:ident: $ty:ty),*)
+        -> $output:ty where [$($bound:tt)*] => $endpoint:expr) => {
+        fn $name<$($generic)*>(&self, $($arg: $ty),*)
+            -> impl Future<Output = Result<$output>> + Send
+            where $($bound)* {
+            let endpoint = IntoEndpoint::into_endpoint($endpoint);
+
+            async move { KitsuClient::new(self).execute_async(endpoint?).await }
+        }
+    };
+}
+
+/// Trait which defines the methods necessary to interact with the service.
+///
+/// The returned futures are `Send`, so they can be spawned onto a
+/// multi-threaded runtime.
+///
+/// # Examples
+///
+/// To bring in the implemenation for the asynchronous `reqwest` Client,
+/// simply use the trait:
+///
+/// ```rust,no_run
+/// use kitsu::KitsuAsyncRequester;
+/// ```
+///
+/// At this point, the methods will be on your Reqwest Client, sending
+/// requests to [`API_URL`].
+///
+/// To use a different base URL or send additional headers, wrap the Client
+/// in a [`KitsuClient`], which also implements this trait. A `KitsuClient`
+/// wrapping any other [`AsyncHttpTransport`] implements it too.
+///
+/// Get an anime using its id:
+///
+/// ```rust,no_run
+/// # extern crate reqwest_async as reqwest;
+/// #
+/// use kitsu::KitsuAsyncRequester;
+/// use reqwest::Client;
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     // Create the reqwest Client.
+///     let client = Client::new();
+///
+///     let anime_id = 1;
+///
+///     // Get the anime.
+///     let anime = client.get_anime(anime_id).await
+///         .expect("Error getting anime");
+///
+///     // Do something with anime
+/// }
+/// ```
+///
+/// Search for an anime by name:
+///
+/// ```rust,no_run
+/// # extern crate reqwest_async as reqwest;
+/// #
+/// use kitsu::KitsuAsyncRequester;
+/// use reqwest::Client;
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     // Create the reqwest Client.
+///     let client = Client::new();
+///
+///     let anime_name = "Your Lie in April";
+///
+///     // Search for the anime.
+///     let anime = client.search_anime(|f| f.text(anime_name))
+///         .await
+///         .expect("Error searching for anime");
+///
+///     // Do something with anime
+/// }
+/// ```
+///
+/// [`API_URL`]: ../../constant.API_URL.html
+/// [`AsyncHttpTransport`]: ../../transport/trait.AsyncHttpTransport.html
+/// [`KitsuClient`]: ../../client/struct.KitsuClient.html
+pub trait KitsuRequester {
+    requester_methods!(methods, declare);
+
+    /// Searches for resources of any type, returning a stream over every
+    /// page of results.
+    ///
+    /// The search may be a [`Search`], a [`Query`], or the typed search
+    /// builder of the resource, such as an [`AnimeSearch`].
+    ///
+    /// Pages are requested as the stream is polled, following the `next`
+    /// link of each page, or by offset with [`PageStream::prefetch`].
+    ///
+    /// [`AnimeSearch`]: ../../builder/struct.AnimeSearch.html
+    /// [`PageStream::prefetch`]: ../../pagination/struct.PageStream.html#method.prefetch
+    /// [`Query`]: ../../builder/struct.Query.html
+    /// [`Search`]: ../../builder/struct.Search.html
+    fn search_pages<T, S>(&self, search: S) -> PageStream<'_, T>
+        where T: Resource + 'static, S: Into<Search>;
+
+    /// Searches for resources of any type, returning a stream over the
+    /// results of every page.
+    ///
+    /// The search may be a [`Search`], a [`Query`], or the typed search
+    /// builder of the resource, such as an [`AnimeSearch`].
+    ///
+    /// # Examples
+    ///
+    /// Collect up to 100 anime with "bebop" in their titles, keeping two
+    /// pages in flight ahead of the current one:
+    ///
+    /// ```rust,no_run
+    /// # extern crate reqwest_async as reqwest;
+    /// #
+    /// use futures_util::TryStreamExt;
+    /// use kitsu::builder::AnimeSearch;
+    /// use kitsu::model::Anime;
+    /// use kitsu::KitsuAsyncRequester;
+    /// use reqwest::Client;
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     let client = Client::new();
+    ///     let search = AnimeSearch::default().text("bebop");
+    ///
+    ///     let anime = client.search_all::<Anime, _>(search)
+    ///         .page_size(20)
+    ///         .prefetch(2)
+    ///         .max_items(100)
+    ///         .try_collect::<Vec<_>>()
+    ///         .await
+    ///         .expect("Error searching for anime");
+    /// }
+    /// ```
+    ///
+    /// [`AnimeSearch`]: ../../builder/struct.AnimeSearch.html
+    /// [`Query`]: ../../builder/struct.Query.html
+    /// [`Search`]: ../../builder/struct.Search.html
+    fn search_all<T, S>(&self, search: S) -> ItemStream<'_, T>
+        where T: Resource + 'static, S: Into<Search>;
+}
+
+impl KitsuRequester for ReqwestClient {
+    requester_methods!(methods, bare);
+
+    fn search_pages<T, S>(&self, search: S) -> PageStream<'_, T>
+        where T: Resource + 'static, S: Into<Search> {
+        let endpoint = endpoint::search_resources::<T>(search.into());
+
+        PageStream::new(endpoint.path, move |path| Box::pin(async move {
+            KitsuClient::new(self).execute_async(endpoint::page(path)).await
+        }))
+    }
+
+    fn search_all<T, S>(&self, search: S) -> ItemStream<'_, T>
+        where T: Resource + 'static, S: Into<Search> {
+        self.search_pages(search).items()
+    }
+}
+
+impl<C: AsyncHttpTransport> KitsuRequester for KitsuClient<C> {
+    requester_methods!(methods, client);
+
+    fn search_pages<T, S>(&self, search: S) -> PageStream<'_, T>
+        where T: Resource + 'static, S: Into<Search> {
+        let endpoint = endpoint::search_resources::<T>(search.into());
+
+        PageStream::new(endpoint.path, move |path| {
+            Box::pin(self.execute_async(endpoint::page(path)))
+        })
+    }
+
+    fn search_all<T, S>(&self, search: S) -> ItemStream<'_, T>
+        where T: Resource + 'static, S: Into<Search> {
+        self.search_pages(search).items()
+    }
+}
+
+impl AsyncHttpTransport for ReqwestClient {
+    fn send(&self, request: HttpRequest)
+        -> BoxFuture<'_, Result<HttpResponse>> {
+        Box::pin(async move {
+            let method = match request.method {
+                Method::Delete => ReqwestMethod::DELETE,
+                Method::Get => ReqwestMethod::GET,
+                Method::Patch => ReqwestMethod::PATCH,
+                Method::Post => ReqwestMethod::POST,
+            };
+
+            let mut builder = self.request(method, request.url.as_str());
+
+            for (name, value) in &request.headers {
+                builder = builder.header(name.as_str(), value.as_str());
+            }
+
+            if let Some(body) = request.body {
+                builder = builder.body(body);
+            }
+
+            let response = builder.send().await.map_err(from_reqwest)?;
+            let status = response.status().as_u16();
+            let headers = response.headers()
+                .iter()
+                .filter_map(|(name, value)| {
+                    let value = value.to_str().ok()?;
+
+                    Some((name.as_str().to_owned(), value.to_owned()))
+                })
+                .collect();
+            let body = response.bytes().await.map_err(from_reqwest)?;
+
+            Ok(HttpResponse {
+                body: body.to_vec(),
+                headers,
+                status,
+            })
+        })
+    }
+}
+
+/// Converts an error from `reqwest`, distinguishing requests that could not
+/// be built from those that could not be sent.
+fn from_reqwest(err: ReqwestError) -> Error {
+    if err.is_builder() {
+        Error::InvalidRequest {
+            response: None,
+            source: Some(Box::new(err)),
+        }
+    } else {
+        Error::Transport(Box::new(err))
+    }
+}
@@ -1,15 +1,36 @@
 //! A set of builders for ease of use with optional parameters around the API.
 
-use percent_encoding::{self, DEFAULT_ENCODE_SET};
-use std::fmt::Write;
+use percent_encoding::{self, EncodeSet, DEFAULT_ENCODE_SET};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result as FmtResult, Write};
+use std::ops::{Bound, RangeBounds};
+use std::str::FromStr;
+use crate::model::{AgeRating, AnimeType, LibraryEntryStatus, MangaType, Season};
+use crate::{Error, Result};
+
+/// The characters encoded in the keys and values of query parameters: those
+/// of the default set, and those with a meaning in a query string.
+#[derive(Clone, Copy)]
+struct ParamEncodeSet;
+
+impl EncodeSet for ParamEncodeSet {
+    fn contains(&self, byte: u8) -> bool {
+        match byte {
+            b'%' | b'&' | b'+' | b'=' => true,
+            _ => DEFAULT_ENCODE_SET.contains(byte),
+        }
+    }
+}
 
 /// Filters search results.
 ///
-/// The following are filters in addition to each search type's fields:
+/// Filters are passed to the API as-is, so a misspelled filter is ignored
+/// rather than rejected. Searches for a specific type of resource use a typed
+/// builder instead, such as [`AnimeSearch`], which only allows the filters
+/// that the API supports for the type.
 ///
-/// - `search_anime`: `season`, `streamers`, `text`
-/// - `search_manga]: `text`
-#[derive(Default)]
+/// [`AnimeSearch`]: struct.AnimeSearch.html
+#[derive(Clone, Debug, Default)]
 pub struct Search(pub String);
 
 impl Search {
@@ -17,11 +38,11 @@ impl Search {
     pub fn filter(mut self, key: &str, value: &str) -> Self {
         let key = percent_encoding::utf8_percent_encode(
             key,
-            DEFAULT_ENCODE_SET,
+            ParamEncodeSet,
         );
         let value = percent_encoding::utf8_percent_encode(
             value,
-            DEFAULT_ENCODE_SET,
+            ParamEncodeSet,
         );
 
         let _ = write!(self.0, "&filter[{}]={}", key, value);
@@ -29,6 +50,69 @@ impl Search {
         self
     }
 
+    /// Includes related resources in the response, using the names of the
+    /// relationships to include.
+    ///
+    /// Relationships of related resources can be included by joining their
+    /// names with a period (`'.'`), and multiple relationships can be
+    /// included by joining them with a comma (`','`), such as
+    /// `genres,castings.character`. The included resources are available in
+    /// [`Response::included`], and can be resolved with
+    /// [`Relationship::resolve`].
+    ///
+    /// [`Relationship::resolve`]: ../model/struct.Relationship.html#method.resolve
+    /// [`Response::included`]: ../model/struct.Response.html#structfield.included
+    pub fn include(mut self, relationships: &str) -> Self {
+        let relationships = percent_encoding::utf8_percent_encode(
+            relationships,
+            ParamEncodeSet,
+        );
+
+        let _ = write!(self.0, "&include={}", relationships);
+
+        self
+    }
+
+    /// Limits the attributes and relationships returned for resources of a
+    /// type to the named fields.
+    ///
+    /// The type is that of resources in JSON:API documents, such as `anime`
+    /// or `users`, which is available as [`Resource::TYPE`]. Fields are
+    /// named as they are in the API, such as `canonicalTitle`.
+    ///
+    /// Resources with some of their attributes omitted can not be
+    /// deserialized into their full models, so request them as a
+    /// [`Partial`] model.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kitsu::builder::Search;
+    ///
+    /// let search = Search::default()
+    ///     .fields("anime", &["canonicalTitle", "posterImage"]);
+    ///
+    /// assert_eq!(search.0, "&fields[anime]=canonicalTitle,posterImage");
+    /// ```
+    ///
+    /// [`Partial`]: ../model/struct.Partial.html
+    /// [`Resource::TYPE`]: ../model/trait.Resource.html#associatedconstant.TYPE
+    pub fn fields(mut self, kind: &str, fields: &[&str]) -> Self {
+        let kind = percent_encoding::utf8_percent_encode(
+            kind,
+            ParamEncodeSet,
+        );
+        let fields = fields.join(",");
+        let fields = percent_encoding::utf8_percent_encode(
+            &fields,
+            ParamEncodeSet,
+        );
+
+        let _ = write!(self.0, "&fields[{}]={}", kind, fields);
+
+        self
+    }
+
     /// Sets a limit to the number of results that can be returned.
     ///
     /// This is used for pagination, in conjunction with [`offset`].
@@ -54,15 +138,866 @@ impl Search {
     /// Sets a sorting order to use by specifying fields.
     ///
     /// `id` will sort ascending, while `-id` will sort descending. Multiple
-    /// sorters can be provided by joining with a comma (`','`).
+    /// sorters can be provided by joining with a comma (`','`). Use
+    /// [`sort_by`] to build the order from typed fields instead.
+    ///
+    /// [`sort_by`]: #method.sort_by
     pub fn sort(mut self, sort: &str) -> Self {
         let sort = percent_encoding::utf8_percent_encode(
             sort,
-            DEFAULT_ENCODE_SET,
+            ParamEncodeSet,
         );
 
         let _ = write!(self.0, "&sort={}", sort);
 
         self
     }
+
+    /// Sets a sorting order to use from a typed [`Sort`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kitsu::builder::{AnimeSortField, Search, Sort};
+    ///
+    /// let search = Search::default().sort_by(
+    ///     Sort::desc(AnimeSortField::AverageRating)
+    ///         .then_asc(AnimeSortField::StartDate),
+    /// );
+    ///
+    /// assert_eq!(search.0, "&sort=-averageRating,startDate");
+    /// ```
+    ///
+    /// [`Sort`]: struct.Sort.html
+    pub fn sort_by<F: SortField>(mut self, sort: Sort<F>) -> Self {
+        let _ = write!(self.0, "&sort={}", sort);
+
+        self
+    }
+}
+
+/// A structured search query, which can be stored, compared, and converted
+/// to and from the query strings of the API's URLs.
+///
+/// A `Query` is converted into a [`Search`], or any typed search builder, to
+/// make a request with it, and is rendered as a query string by its
+/// `Display` implementation. Parameters are rendered in a consistent order,
+/// so equal queries render the same query string.
+///
+/// # Examples
+///
+/// Save the query of the next page of a search, and resume it later:
+///
+/// ```rust
+/// use kitsu::builder::{AnimeSearch, Query};
+///
+/// let next = "https://kitsu.io/api/edge/anime?filter%5Btext%5D=bebop\
+///             &page%5Blimit%5D=10&page%5Boffset%5D=10";
+///
+/// let query = Query::parse(next).unwrap();
+/// assert_eq!(query.filters["text"], "bebop");
+/// assert_eq!(query.offset, Some(10));
+///
+/// let search = AnimeSearch::from(query.clone());
+///
+/// assert_eq!(
+///     query.to_string(),
+///     "filter[text]=bebop&page[limit]=10&page[offset]=10",
+/// );
+/// ```
+///
+/// [`Search`]: struct.Search.html
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Query {
+    /// The fields to return for resources of a type, keyed by type.
+    ///
+    /// Refer to [`Search::fields`].
+    ///
+    /// [`Search::fields`]: struct.Search.html#method.fields
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, Vec<String>>,
+    /// The filters to apply, keyed by name.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub filters: BTreeMap<String, String>,
+    /// The names of the relationships to include.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    /// The number of results to return per page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+    /// The number of results to skip.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    /// Any other parameters, keyed by name, which are kept as they are.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub other: BTreeMap<String, String>,
+    /// The fields to sort by, each prefixed with a `-` if sorting in
+    /// descending order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sort: Vec<String>,
+}
+
+impl Query {
+    /// Parses the query of a URL, such as the `next` link of a page of
+    /// results, or a query string without its leading `?`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`] if a parameter is not valid
+    /// percent-encoded UTF-8, or if the page limit or offset is not a
+    /// number.
+    ///
+    /// [`Error::InvalidRequest`]: ../enum.Error.html#variant.InvalidRequest
+    pub fn parse(url: &str) -> Result<Self> {
+        let query = match url.find('?') {
+            Some(idx) => &url[idx + 1..],
+            None if url.contains("://") || url.starts_with('/') => "",
+            None => url,
+        };
+        let query = query.split('#').next().unwrap_or("");
+
+        let mut parsed = Query::default();
+
+        for param in query.split('&').filter(|param| !param.is_empty()) {
+            let mut parts = param.splitn(2, '=');
+            let key = decode(parts.next().unwrap_or(""))?;
+            let value = decode(parts.next().unwrap_or(""))?;
+
+            if let Some(name) = bracketed(&key, "filter") {
+                parsed.filters.insert(name.to_owned(), value);
+            } else if let Some(kind) = bracketed(&key, "fields") {
+                parsed.fields.insert(kind.to_owned(), split_list(&value));
+            } else if key == "include" {
+                parsed.include.extend(split_list(&value));
+            } else if key == "sort" {
+                parsed.sort.extend(split_list(&value));
+            } else if key == "page[limit]" {
+                parsed.limit = Some(parse_number(&key, &value)?);
+            } else if key == "page[offset]" {
+                parsed.offset = Some(parse_number(&key, &value)?);
+            } else {
+                parsed.other.insert(key, value);
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+impl Display for Query {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let search = Search::from(self.clone());
+
+        f.write_str(search.0.trim_start_matches('&'))
+    }
+}
+
+impl FromStr for Query {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Query::parse(s)
+    }
+}
+
+impl From<Query> for Search {
+    fn from(query: Query) -> Self {
+        let mut search = Search::default();
+
+        for (name, value) in &query.filters {
+            search = search.filter(name, value);
+        }
+
+        if !query.include.is_empty() {
+            search = search.include(&query.include.join(","));
+        }
+
+        for (kind, fields) in &query.fields {
+            let fields = fields.iter().map(String::as_str).collect::<Vec<_>>();
+
+            search = search.fields(kind, &fields);
+        }
+
+        if !query.sort.is_empty() {
+            search = search.sort(&query.sort.join(","));
+        }
+
+        if let Some(limit) = query.limit {
+            search = search.limit(limit);
+        }
+
+        if let Some(offset) = query.offset {
+            search = search.offset(offset);
+        }
+
+        for (key, value) in &query.other {
+            let _ = write!(
+                search.0,
+                "&{}={}",
+                percent_encoding::utf8_percent_encode(key, ParamEncodeSet),
+                percent_encoding::utf8_percent_encode(value, ParamEncodeSet),
+            );
+        }
+
+        search
+    }
+}
+
+/// Decodes a percent-encoded query parameter's key or value, in which a `+`
+/// is a space.
+fn decode(encoded: &str) -> Result<String> {
+    let encoded = encoded.replace('+', " ");
+
+    percent_encoding::percent_decode(encoded.as_bytes())
+        .decode_utf8()
+        .map(|decoded| decoded.into_owned())
+        .map_err(|why| Error::InvalidRequest {
+            response: None,
+            source: Some(Box::new(why)),
+        })
+}
+
+/// Retrieves the name within the brackets of a key such as `filter[text]`.
+fn bracketed<'a>(key: &'a str, prefix: &str) -> Option<&'a str> {
+    if !key.starts_with(prefix) {
+        return None;
+    }
+
+    let rest = &key[prefix.len()..];
+
+    if rest.len() > 2 && rest.starts_with('[') && rest.ends_with(']') {
+        Some(&rest[1..rest.len() - 1])
+    } else {
+        None
+    }
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value.split(',')
+        .filter(|item| !item.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+fn parse_number(key: &str, value: &str) -> Result<u64> {
+    value.parse().map_err(|_| Error::InvalidRequest {
+        response: None,
+        source: Some(format!("Invalid number for {}: {}", key, value).into()),
+    })
+}
+
+/// Implements the methods of [`Search`] for a typed search builder wrapping
+/// one, so that options without a typed method can still be set.
+///
+/// [`Search`]: struct.Search.html
+macro_rules! search_methods {
+    ($builder:ident, $field:ident) => {
+        impl $builder {
+            /// Filters results by a key and value.
+            ///
+            /// Refer to [`Search::filter`].
+            ///
+            /// [`Search::filter`]: struct.Search.html#method.filter
+            pub fn filter(self, key: &str, value: &str) -> Self {
+                $builder(self.0.filter(key, value))
+            }
+
+            /// Includes related resources in the response.
+            ///
+            /// Refer to [`Search::include`].
+            ///
+            /// [`Search::include`]: struct.Search.html#method.include
+            pub fn include(self, relationships: &str) -> Self {
+                $builder(self.0.include(relationships))
+            }
+
+            /// Limits the fields returned for resources of a type.
+            ///
+            /// Refer to [`Search::fields`].
+            ///
+            /// [`Search::fields`]: struct.Search.html#method.fields
+            pub fn fields(self, kind: &str, fields: &[&str]) -> Self {
+                $builder(self.0.fields(kind, fields))
+            }
+
+            /// Sets a limit to the number of results that can be returned.
+            ///
+            /// Refer to [`Search::limit`].
+            ///
+            /// [`Search::limit`]: struct.Search.html#method.limit
+            pub fn limit(self, limit: u64) -> Self {
+                $builder(self.0.limit(limit))
+            }
+
+            /// Sets an offset to the number of results that can be returned.
+            ///
+            /// Refer to [`Search::offset`].
+            ///
+            /// [`Search::offset`]: struct.Search.html#method.offset
+            pub fn offset(self, offset: u64) -> Self {
+                $builder(self.0.offset(offset))
+            }
+
+            /// Sets a sorting order to use by specifying fields.
+            ///
+            /// Refer to [`Search::sort`].
+            ///
+            /// [`Search::sort`]: struct.Search.html#method.sort
+            pub fn sort(self, sort: &str) -> Self {
+                $builder(self.0.sort(sort))
+            }
+
+            /// Sets a sorting order to use from a typed [`Sort`] of the
+            /// fields that results of this type can be sorted by.
+            ///
+            /// Refer to [`Search::sort_by`].
+            ///
+            /// [`Search::sort_by`]: struct.Search.html#method.sort_by
+            /// [`Sort`]: struct.Sort.html
+            pub fn sort_by(self, sort: Sort<$field>) -> Self {
+                $builder(self.0.sort_by(sort))
+            }
+        }
+
+        impl From<Search> for $builder {
+            fn from(search: Search) -> Self {
+                $builder(search)
+            }
+        }
+
+        impl From<$builder> for Search {
+            fn from(builder: $builder) -> Self {
+                builder.0
+            }
+        }
+
+        impl From<Query> for $builder {
+            fn from(query: Query) -> Self {
+                $builder(query.into())
+            }
+        }
+    };
+}
+
+/// Filters anime search results.
+///
+/// # Examples
+///
+/// ```rust
+/// use kitsu::builder::{AnimeSearch, Search};
+/// use kitsu::model::{AgeRating, AnimeType, Season};
+///
+/// let search = AnimeSearch::default()
+///     .season(Season::Winter)
+///     .season_year(2018)
+///     .age_rating(&[AgeRating::G, AgeRating::PG])
+///     .subtype(AnimeType::TV)
+///     .average_rating(80..);
+///
+/// assert_eq!(
+///     Search::from(search).0,
+///     "&filter[season]=winter&filter[seasonYear]=2018\
+///      &filter[ageRating]=G,PG&filter[subtype]=TV&filter[averageRating]=80..",
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AnimeSearch(Search);
+
+impl AnimeSearch {
+    /// Filters anime by age ratings, matching anime with any of them.
+    pub fn age_rating(self, ratings: &[AgeRating]) -> Self {
+        let ratings = ratings.iter()
+            .map(AgeRating::as_str)
+            .collect::<Vec<_>>();
+
+        self.filter("ageRating", &ratings.join(","))
+    }
+
+    /// Filters anime by their average rating, as a percentage.
+    ///
+    /// Both ends of the range are optional, so `80..` matches anime rated at
+    /// least 80%. An empty range, such as `..0` or `80..10`, matches no
+    /// anime.
+    pub fn average_rating<R: RangeBounds<u8>>(self, rating: R) -> Self {
+        self.filter("averageRating", &range(&rating))
+    }
+
+    /// Filters anime by the slugs of categories, matching anime in all of
+    /// them.
+    pub fn categories(self, categories: &[&str]) -> Self {
+        self.filter("categories", &categories.join(","))
+    }
+
+    /// Filters anime by the season they started airing in.
+    pub fn season(self, season: Season) -> Self {
+        self.filter("season", season.as_str())
+    }
+
+    /// Filters anime by the year of the season they started airing in.
+    pub fn season_year(self, year: u16) -> Self {
+        self.filter("seasonYear", &year.to_string())
+    }
+
+    /// Filters anime by the names of streaming services, matching anime
+    /// available on any of them.
+    pub fn streamers(self, streamers: &[&str]) -> Self {
+        self.filter("streamers", &streamers.join(","))
+    }
+
+    /// Filters anime by their type.
+    pub fn subtype(self, subtype: AnimeType) -> Self {
+        self.filter("subtype", subtype.as_str())
+    }
+
+    /// Searches for anime by text, matching their titles.
+    pub fn text(self, text: &str) -> Self {
+        self.filter("text", text)
+    }
+}
+
+search_methods!(AnimeSearch, AnimeSortField);
+
+/// Filters character search results.
+#[derive(Clone, Debug, Default)]
+pub struct CharacterSearch(Search);
+
+impl CharacterSearch {
+    /// Searches for characters by name.
+    pub fn name(self, name: &str) -> Self {
+        self.filter("name", name)
+    }
+
+    /// Filters characters by their slug.
+    pub fn slug(self, slug: &str) -> Self {
+        self.filter("slug", slug)
+    }
+}
+
+search_methods!(CharacterSearch, CharacterSortField);
+
+/// Filters manga search results.
+#[derive(Clone, Debug, Default)]
+pub struct MangaSearch(Search);
+
+impl MangaSearch {
+    /// Filters manga by their average rating, as a percentage.
+    ///
+    /// Refer to [`AnimeSearch::average_rating`].
+    ///
+    /// [`AnimeSearch::average_rating`]: struct.AnimeSearch.html#method.average_rating
+    pub fn average_rating<R: RangeBounds<u8>>(self, rating: R) -> Self {
+        self.filter("averageRating", &range(&rating))
+    }
+
+    /// Filters manga by the slugs of categories, matching manga in all of
+    /// them.
+    pub fn categories(self, categories: &[&str]) -> Self {
+        self.filter("categories", &categories.join(","))
+    }
+
+    /// Filters manga by their type.
+    pub fn subtype(self, subtype: MangaType) -> Self {
+        self.filter("subtype", subtype.as_str())
+    }
+
+    /// Searches for manga by text, matching their titles.
+    pub fn text(self, text: &str) -> Self {
+        self.filter("text", text)
+    }
+}
+
+search_methods!(MangaSearch, MangaSortField);
+
+/// Filters user search results.
+#[derive(Clone, Debug, Default)]
+pub struct UserSearch(Search);
+
+impl UserSearch {
+    /// Searches for users by name.
+    pub fn name(self, name: &str) -> Self {
+        self.filter("name", name)
+    }
+
+    /// Filters users by their slug, the name used in their profile's URL.
+    pub fn slug(self, slug: &str) -> Self {
+        self.filter("slug", slug)
+    }
+}
+
+search_methods!(UserSearch, UserSortField);
+
+/// The direction to sort results in by a field.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SortDirection {
+    /// Sorts results from the lowest value to the highest.
+    Ascending,
+    /// Sorts results from the highest value to the lowest.
+    Descending,
+}
+
+/// A field of a type of resource that results can be sorted by.
+pub trait SortField {
+    /// The name of the field in the API.
+    fn name(&self) -> &str;
+}
+
+/// A sorting order of one or more fields, each with a direction.
+///
+/// Results are sorted by the first field, with ties broken by each following
+/// field in turn.
+///
+/// # Examples
+///
+/// Sort anime by popularity, and then by the newest first:
+///
+/// ```rust
+/// use kitsu::builder::{AnimeSortField, Sort};
+///
+/// let sort = Sort::asc(AnimeSortField::PopularityRank)
+///     .then_desc(AnimeSortField::StartDate);
+///
+/// assert_eq!(sort.to_string(), "popularityRank,-startDate");
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Sort<F> {
+    keys: Vec<(F, SortDirection)>,
+}
+
+impl<F: SortField> Sort<F> {
+    /// Sorts by a field in a direction.
+    pub fn new(field: F, direction: SortDirection) -> Self {
+        Sort {
+            keys: vec![(field, direction)],
+        }
+    }
+
+    /// Sorts by a field in ascending order.
+    pub fn asc(field: F) -> Self {
+        Sort::new(field, SortDirection::Ascending)
+    }
+
+    /// Sorts by a field in descending order.
+    pub fn desc(field: F) -> Self {
+        Sort::new(field, SortDirection::Descending)
+    }
+
+    /// Breaks ties by another field in a direction.
+    pub fn then(mut self, field: F, direction: SortDirection) -> Self {
+        self.keys.push((field, direction));
+
+        self
+    }
+
+    /// Breaks ties by another field in ascending order.
+    pub fn then_asc(self, field: F) -> Self {
+        self.then(field, SortDirection::Ascending)
+    }
+
+    /// Breaks ties by another field in descending order.
+    pub fn then_desc(self, field: F) -> Self {
+        self.then(field, SortDirection::Descending)
+    }
+
+    /// The fields to sort by, in order of precedence.
+    pub fn keys(&self) -> &[(F, SortDirection)] {
+        &self.keys
+    }
+}
+
+impl<F: SortField> Display for Sort<F> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        for (idx, &(ref field, direction)) in self.keys.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(",")?;
+            }
+
+            if direction == SortDirection::Descending {
+                f.write_str("-")?;
+            }
+
+            f.write_str(field.name())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The fields that anime can be sorted by.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum AnimeSortField {
+    /// The average rating of the anime.
+    AverageRating,
+    /// When the anime was added to the API.
+    CreatedAt,
+    /// When the anime finished airing.
+    EndDate,
+    /// The number of episodes of the anime.
+    EpisodeCount,
+    /// The number of users that have favorited the anime.
+    FavoritesCount,
+    /// The id of the anime.
+    Id,
+    /// The rank of the anime by popularity, where 1 is the most popular.
+    PopularityRank,
+    /// The rank of the anime by rating, where 1 is the highest rated.
+    RatingRank,
+    /// When the anime started airing.
+    StartDate,
+    /// When the anime was last updated in the API.
+    UpdatedAt,
+    /// The number of users that have the anime in their library.
+    UserCount,
+}
+
+impl SortField for AnimeSortField {
+    fn name(&self) -> &str {
+        match *self {
+            AnimeSortField::AverageRating => "averageRating",
+            AnimeSortField::CreatedAt => "createdAt",
+            AnimeSortField::EndDate => "endDate",
+            AnimeSortField::EpisodeCount => "episodeCount",
+            AnimeSortField::FavoritesCount => "favoritesCount",
+            AnimeSortField::Id => "id",
+            AnimeSortField::PopularityRank => "popularityRank",
+            AnimeSortField::RatingRank => "ratingRank",
+            AnimeSortField::StartDate => "startDate",
+            AnimeSortField::UpdatedAt => "updatedAt",
+            AnimeSortField::UserCount => "userCount",
+        }
+    }
+}
+
+/// The fields that characters can be sorted by.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CharacterSortField {
+    /// When the character was added to the API.
+    CreatedAt,
+    /// The id of the character.
+    Id,
+    /// The name of the character.
+    Name,
+    /// When the character was last updated in the API.
+    UpdatedAt,
+}
+
+impl SortField for CharacterSortField {
+    fn name(&self) -> &str {
+        match *self {
+            CharacterSortField::CreatedAt => "createdAt",
+            CharacterSortField::Id => "id",
+            CharacterSortField::Name => "name",
+            CharacterSortField::UpdatedAt => "updatedAt",
+        }
+    }
+}
+
+/// The fields that manga can be sorted by.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MangaSortField {
+    /// The average rating of the manga.
+    AverageRating,
+    /// The number of chapters of the manga.
+    ChapterCount,
+    /// When the manga was added to the API.
+    CreatedAt,
+    /// When the manga finished publishing.
+    EndDate,
+    /// The number of users that have favorited the manga.
+    FavoritesCount,
+    /// The id of the manga.
+    Id,
+    /// The rank of the manga by popularity, where 1 is the most popular.
+    PopularityRank,
+    /// The rank of the manga by rating, where 1 is the highest rated.
+    RatingRank,
+    /// When the manga started publishing.
+    StartDate,
+    /// When the manga was last updated in the API.
+    UpdatedAt,
+    /// The number of users that have the manga in their library.
+    UserCount,
+    /// The number of volumes of the manga.
+    VolumeCount,
+}
+
+impl SortField for MangaSortField {
+    fn name(&self) -> &str {
+        match *self {
+            MangaSortField::AverageRating => "averageRating",
+            MangaSortField::ChapterCount => "chapterCount",
+            MangaSortField::CreatedAt => "createdAt",
+            MangaSortField::EndDate => "endDate",
+            MangaSortField::FavoritesCount => "favoritesCount",
+            MangaSortField::Id => "id",
+            MangaSortField::PopularityRank => "popularityRank",
+            MangaSortField::RatingRank => "ratingRank",
+            MangaSortField::StartDate => "startDate",
+            MangaSortField::UpdatedAt => "updatedAt",
+            MangaSortField::UserCount => "userCount",
+            MangaSortField::VolumeCount => "volumeCount",
+        }
+    }
+}
+
+/// The fields that users can be sorted by.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum UserSortField {
+    /// When the user signed up.
+    CreatedAt,
+    /// The number of users following the user.
+    FollowersCount,
+    /// The number of users the user follows.
+    FollowingCount,
+    /// The id of the user.
+    Id,
+    /// The number of minutes the user has spent watching anime.
+    LifeSpentOnAnime,
+    /// The name of the user.
+    Name,
+    /// When the user last updated their profile.
+    UpdatedAt,
+}
+
+impl SortField for UserSortField {
+    fn name(&self) -> &str {
+        match *self {
+            UserSortField::CreatedAt => "createdAt",
+            UserSortField::FollowersCount => "followersCount",
+            UserSortField::FollowingCount => "followingCount",
+            UserSortField::Id => "id",
+            UserSortField::LifeSpentOnAnime => "lifeSpentOnAnime",
+            UserSortField::Name => "name",
+            UserSortField::UpdatedAt => "updatedAt",
+        }
+    }
+}
+
+/// Formats a range as the API's inclusive `start..end` filter, where either
+/// end may be omitted.
+///
+/// An empty range is formatted as `1..0`, which no value falls within, so
+/// that the filter matches nothing rather than being rejected.
+fn range<R: RangeBounds<u8>>(range: &R) -> String {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => Some(i16::from(start)),
+        Bound::Excluded(&start) => Some(i16::from(start) + 1),
+        Bound::Unbounded => None,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => Some(i16::from(end)),
+        Bound::Excluded(&end) => Some(i16::from(end) - 1),
+        Bound::Unbounded => None,
+    };
+
+    if start.unwrap_or(0) > end.unwrap_or(i16::from(u8::MAX)) {
+        return "1..0".to_owned();
+    }
+
+    let bound = |value: Option<i16>| value.map_or_else(String::new, |v| v.to_string());
+
+    format!("{}..{}", bound(start), bound(end))
+}
+
+/// The attributes to set when creating or updating a library entry.
+///
+/// Only attributes that have been set are sent, so when updating, every other
+/// attribute of the entry is left unchanged.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryEntryBuilder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    finished_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    private: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    progress: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rating_twenty: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reconsume_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reconsuming: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    started_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<LibraryEntryStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    volumes_owned: Option<u32>,
+}
+
+impl LibraryEntryBuilder {
+    /// Sets when the user finished the media, as an ISO 8601 timestamp.
+    pub fn finished_at(mut self, finished_at: &str) -> Self {
+        self.finished_at = Some(finished_at.to_owned());
+
+        self
+    }
+
+    /// Sets the user's notes about the media.
+    pub fn notes(mut self, notes: &str) -> Self {
+        self.notes = Some(notes.to_owned());
+
+        self
+    }
+
+    /// Sets whether the entry is only visible to the user.
+    pub fn private(mut self, private: bool) -> Self {
+        self.private = Some(private);
+
+        self
+    }
+
+    /// Sets the number of episodes or chapters the user has consumed.
+    pub fn progress(mut self, progress: u32) -> Self {
+        self.progress = Some(progress);
+
+        self
+    }
+
+    /// Sets the user's rating of the media, from `2` to `20`.
+    ///
+    /// Ratings outside of that range are rejected by the API.
+    pub fn rating_twenty(mut self, rating: u8) -> Self {
+        self.rating_twenty = Some(rating);
+
+        self
+    }
+
+    /// Sets the number of times the user has rewatched or reread the media.
+    pub fn reconsume_count(mut self, reconsume_count: u32) -> Self {
+        self.reconsume_count = Some(reconsume_count);
+
+        self
+    }
+
+    /// Sets whether the user is currently rewatching or rereading the media.
+    pub fn reconsuming(mut self, reconsuming: bool) -> Self {
+        self.reconsuming = Some(reconsuming);
+
+        self
+    }
+
+    /// Sets when the user started the media, as an ISO 8601 timestamp.
+    pub fn started_at(mut self, started_at: &str) -> Self {
+        self.started_at = Some(started_at.to_owned());
+
+        self
+    }
+
+    /// Sets the status of the entry.
+    pub fn status(mut self, status: LibraryEntryStatus) -> Self {
+        self.status = Some(status);
+
+        self
+    }
+
+    /// Sets the number of volumes of the manga the user owns.
+    pub fn volumes_owned(mut self, volumes_owned: u32) -> Self {
+        self.volumes_owned = Some(volumes_owned);
+
+        self
+    }
 }
@@ -0,0 +1,370 @@
+//! Caching responses to `GET` requests, keyed by their URL.
+//!
+//! An [`HttpCache`] given to a [`KitsuClient`] stores successful responses
+//! to `GET` requests. The API's `Cache-Control` header decides how long a
+//! response stays fresh: a fresh response is returned without sending a
+//! request at all, while a stale response that came with an `ETag` is
+//! revalidated by sending its tag in the `If-None-Match` header, reusing the
+//! stored body if the API responds with `304 Not Modified`. Responses marked
+//! `no-store` or with a `Vary: *` header are never stored, and those marked
+//! `no-cache` are always revalidated. A `304 Not Modified` response only
+//! changes how long the stored response stays fresh if it has a `max-age` or
+//! `no-cache` directive of its own.
+//!
+//! Responses are keyed by their URL alone, so by default only responses that
+//! are the same for everyone are cached. Requests that are authenticated,
+//! either through an [`Auth`] or with an `Authorization` header, bypass the
+//! cache, and responses marked `private` or varying by the `Authorization`
+//! header are not stored. A cache that is only used by clients authenticated
+//! as the same user can store them too with [`HttpCache::private`].
+//!
+//! A successful request of any other method removes the stored response for
+//! its URL, so that updating a library entry does not leave a stale copy of
+//! it behind.
+//!
+//! Responses are kept in a [`CacheStore`]. The [`MemoryCacheStore`] keeps a
+//! number of the most recently used responses in memory, while the
+//! [`FileCacheStore`] keeps responses in a directory, so that they survive
+//! restarts and can be shared between processes.
+//!
+//! Failing to load or save a response never fails a request; the request is
+//! sent as if nothing was stored.
+//!
+//! # Examples
+//!
+//! Keep up to 500 responses in memory:
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "reqwest")]
+//! # extern crate reqwest;
+//! #
+//! # #[cfg(feature = "reqwest")]
+//! # fn main() {
+//! #
+//! use kitsu::cache::HttpCache;
+//! use kitsu::{KitsuClient, KitsuReqwestRequester};
+//! use reqwest::Client;
+//!
+//! let client = KitsuClient::new(Client::new()).cache(HttpCache::memory(500));
+//!
+//! let anime = client.get_anime(1).expect("Error getting anime");
+//! // Served from the cache, or revalidated if it is no longer fresh.
+//! let anime = client.get_anime(1).expect("Error getting anime");
+//! # }
+//! #
+//! # #[cfg(not(feature = "reqwest"))]
+//! # fn main() { }
+//! ```
+//!
+//! [`Auth`]: ../auth/struct.Auth.html
+//! [`CacheStore`]: trait.CacheStore.html
+//! [`FileCacheStore`]: struct.FileCacheStore.html
+//! [`HttpCache`]: struct.HttpCache.html
+//! [`HttpCache::private`]: struct.HttpCache.html#method.private
+//! [`KitsuClient`]: ../client/struct.KitsuClient.html
+//! [`MemoryCacheStore`]: struct.MemoryCacheStore.html
+
+mod store;
+
+pub use self::store::{CacheStore, FileCacheStore, MemoryCacheStore};
+
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::transport::HttpResponse;
+
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+use crate::transport::{HttpRequest, Method};
+
+/// A response stored in a [`CacheStore`].
+///
+/// [`CacheStore`]: trait.CacheStore.html
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CachedResponse {
+    /// The raw body of the response.
+    pub body: Vec<u8>,
+    /// The entity tag of the response, sent to revalidate it.
+    pub etag: Option<String>,
+    /// When the response stops being fresh, in seconds since the Unix epoch.
+    pub expires_at: u64,
+    /// The headers received, as pairs of names and values.
+    pub headers: Vec<(String, String)>,
+    /// The directives that the response was stored with, updated by the
+    /// responses to revalidating it.
+    #[serde(default)]
+    pub policy: CachePolicy,
+    /// The HTTP status code of the response.
+    pub status: u16,
+}
+
+impl CachedResponse {
+    /// Creates an entry for a response, if its `Cache-Control` header allows
+    /// it to be stored and it is either fresh for some time or can be
+    /// revalidated.
+    pub fn from_response(response: &HttpResponse) -> Option<Self> {
+        let policy = CachePolicy::parse(response);
+        let etag = response.header("ETag").map(str::to_owned);
+
+        if policy.no_store || (policy.max_age == 0 && etag.is_none()) {
+            return None;
+        }
+
+        Some(CachedResponse {
+            body: response.body.clone(),
+            etag,
+            expires_at: now().saturating_add(policy.max_age),
+            headers: response.headers.clone(),
+            policy,
+            status: response.status,
+        })
+    }
+
+    /// Whether the response can be used without revalidating it.
+    pub fn is_fresh(&self) -> bool {
+        now() < self.expires_at
+    }
+
+    /// Converts the entry back into the response it was created from.
+    pub fn into_response(self) -> HttpResponse {
+        HttpResponse {
+            body: self.body,
+            headers: self.headers,
+            status: self.status,
+        }
+    }
+}
+
+/// A cache of responses to `GET` requests, sharing its store between its
+/// clones.
+#[derive(Clone, Debug)]
+pub struct HttpCache {
+    private: bool,
+    store: Arc<dyn CacheStore>,
+}
+
+impl HttpCache {
+    /// Creates a cache keeping responses in a store.
+    pub fn new<S: CacheStore + 'static>(store: S) -> Self {
+        HttpCache {
+            private: false,
+            store: Arc::new(store),
+        }
+    }
+
+    /// Creates a cache keeping up to `capacity` of the most recently used
+    /// responses in memory.
+    pub fn memory(capacity: usize) -> Self {
+        HttpCache::new(MemoryCacheStore::new(capacity))
+    }
+
+    /// Sets whether to also cache responses meant only for the user they were
+    /// sent to: those to authenticated requests, and those marked `private`
+    /// or varying by the `Authorization` header.
+    ///
+    /// These are not cached by default. Only enable this for a cache used by
+    /// clients authenticated as the same user, since a response stored for
+    /// one user would be returned to every client using the cache.
+    pub fn private(mut self, private: bool) -> Self {
+        self.private = private;
+
+        self
+    }
+
+    /// Retrieves an immutable reference to the cache's store.
+    pub fn store(&self) -> &dyn CacheStore {
+        &*self.store
+    }
+
+    /// Looks up the stored response for a request, adding the headers to
+    /// revalidate it to the request if it is stale.
+    ///
+    /// Authenticated requests are only looked up in a private cache.
+    #[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+    pub(crate) fn lookup(&self, request: &mut HttpRequest, authorized: bool)
+        -> Lookup {
+        if request.method != Method::Get || (authorized && !self.private) {
+            return Lookup::Miss(None);
+        }
+
+        let entry = match self.store.get(&request.url) {
+            Ok(Some(entry)) => entry,
+            Ok(None) | Err(_) => return Lookup::Miss(None),
+        };
+
+        if entry.is_fresh() {
+            return Lookup::Fresh(entry.into_response());
+        }
+
+        if let Some(ref etag) = entry.etag {
+            request.headers.push(("If-None-Match".to_owned(), etag.clone()));
+        }
+
+        Lookup::Miss(Some(entry))
+    }
+
+    /// Updates the store with the response to a request, returning the
+    /// stored response instead if the API responded that it is unchanged.
+    #[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+    pub(crate) fn update(
+        &self,
+        method: Method,
+        url: &str,
+        authorized: bool,
+        stale: Option<CachedResponse>,
+        response: HttpResponse,
+    ) -> HttpResponse {
+        if method != Method::Get {
+            if response.is_success() {
+                let _ = self.store.remove(url);
+            }
+
+            return response;
+        }
+
+        match (response.status, stale) {
+            (304, Some(mut entry)) => {
+                if let Some(etag) = response.header("ETag") {
+                    entry.etag = Some(etag.to_owned());
+                }
+
+                entry.policy.revalidate(&response);
+                entry.expires_at = now().saturating_add(entry.policy.max_age);
+
+                if self.stores(authorized, &entry.policy) {
+                    let _ = self.store.put(url, &entry);
+                } else {
+                    let _ = self.store.remove(url);
+                }
+
+                entry.into_response()
+            },
+            (200, _) => {
+                let policy = CachePolicy::parse(&response);
+
+                if self.stores(authorized, &policy) {
+                    if let Some(entry) = CachedResponse::from_response(&response) {
+                        let _ = self.store.put(url, &entry);
+                    }
+                }
+
+                response
+            },
+            _ => response,
+        }
+    }
+
+    /// Whether a response to a request can be stored, given whether the
+    /// request was authenticated.
+    #[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+    fn stores(&self, authorized: bool, policy: &CachePolicy) -> bool {
+        !policy.no_store && (self.private || !(authorized || policy.private))
+    }
+}
+
+/// The outcome of looking up a request in an [`HttpCache`].
+///
+/// [`HttpCache`]: struct.HttpCache.html
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+pub(crate) enum Lookup {
+    /// A fresh response to return without sending the request.
+    Fresh(HttpResponse),
+    /// The request must be sent, revalidating the stale response if there
+    /// is one.
+    Miss(Option<CachedResponse>),
+}
+
+/// The directives of a response's `Cache-Control` and `Vary` headers that
+/// the cache honors.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CachePolicy {
+    /// How many seconds the response stays fresh for after it is received or
+    /// revalidated.
+    pub max_age: u64,
+    /// Whether the response must not be stored.
+    pub no_store: bool,
+    /// Whether the response is only meant for the user it was sent to.
+    pub private: bool,
+}
+
+impl CachePolicy {
+    fn parse(response: &HttpResponse) -> Self {
+        let header = response.header("Cache-Control").unwrap_or_default();
+        let mut policy = CachePolicy {
+            max_age: freshness(header).unwrap_or(0),
+            no_store: false,
+            private: false,
+        };
+
+        for directive in header.split(',') {
+            let directive = directive.trim().to_ascii_lowercase();
+
+            if directive == "no-store" {
+                policy.no_store = true;
+            } else if directive == "private" || directive.starts_with("private=") {
+                policy.private = true;
+            }
+        }
+
+        // A response varying by request headers other than the ones a client
+        // sends every time can not be told apart by its URL.
+        let vary = response.header("Vary").unwrap_or_default();
+
+        for name in vary.split(',') {
+            let name = name.trim();
+
+            if name == "*" {
+                policy.no_store = true;
+            } else if name.eq_ignore_ascii_case("Authorization") {
+                policy.private = true;
+            }
+        }
+
+        policy
+    }
+
+    /// Updates the policy of a stored response with a `304 Not Modified`
+    /// response to revalidating it, which may omit any of the directives.
+    ///
+    /// The freshness is only replaced if the response has a `max-age` or
+    /// `no-cache` directive, and a response stays private or unstorable once
+    /// it is.
+    #[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+    fn revalidate(&mut self, response: &HttpResponse) {
+        let header = response.header("Cache-Control").unwrap_or_default();
+        let revalidated = CachePolicy::parse(response);
+
+        if let Some(max_age) = freshness(header) {
+            self.max_age = max_age;
+        }
+
+        self.no_store |= revalidated.no_store;
+        self.private |= revalidated.private;
+    }
+}
+
+/// How many seconds a `Cache-Control` header keeps a response fresh for, if
+/// it has a directive deciding that.
+///
+/// A `no-cache` directive takes precedence over `max-age`.
+fn freshness(header: &str) -> Option<u64> {
+    let mut max_age = None;
+
+    for directive in header.split(',') {
+        let directive = directive.trim().to_ascii_lowercase();
+
+        if directive == "no-cache" {
+            return Some(0);
+        } else if let Some(seconds) = directive.strip_prefix("max-age=") {
+            max_age = Some(seconds.trim_matches('"').parse().unwrap_or(0));
+        }
+    }
+
+    max_age
+}
+
+/// The current time in seconds since the Unix epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs())
+        .unwrap_or_default()
+}
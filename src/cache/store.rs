@@ -0,0 +1,221 @@
+//! Storage for the responses of an [`HttpCache`].
+//!
+//! [`HttpCache`]: ../struct.HttpCache.html
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use super::CachedResponse;
+use crate::file;
+
+/// A place that the responses of an [`HttpCache`] are kept in, keyed by the
+/// URLs of their requests.
+///
+/// [`HttpCache`]: struct.HttpCache.html
+pub trait CacheStore: Debug + Send + Sync {
+    /// Loads the response stored for a URL, if there is one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the response could not be read.
+    fn get(&self, url: &str) -> io::Result<Option<CachedResponse>>;
+
+    /// Stores a response for a URL, replacing the stored one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the response could not be written.
+    fn put(&self, url: &str, response: &CachedResponse) -> io::Result<()>;
+
+    /// Removes the response stored for a URL, if there is one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the response could not be removed.
+    fn remove(&self, url: &str) -> io::Result<()>;
+}
+
+/// A store keeping a number of the most recently used responses in memory,
+/// evicting the least recently used response when it is full.
+#[derive(Debug)]
+pub struct MemoryCacheStore {
+    capacity: usize,
+    entries: Mutex<Lru>,
+}
+
+#[derive(Debug, Default)]
+struct Lru {
+    entries: HashMap<String, (u64, CachedResponse)>,
+    recency: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+impl Lru {
+    /// Marks the entry for a URL as the most recently used one.
+    fn touch(&mut self, url: &str) {
+        self.tick += 1;
+
+        if let Some(entry) = self.entries.get_mut(url) {
+            self.recency.remove(&entry.0);
+            self.recency.insert(self.tick, url.to_owned());
+            entry.0 = self.tick;
+        }
+    }
+}
+
+impl MemoryCacheStore {
+    /// Creates an empty store holding up to `capacity` responses.
+    pub fn new(capacity: usize) -> Self {
+        MemoryCacheStore {
+            capacity,
+            entries: Mutex::new(Lru::default()),
+        }
+    }
+
+    /// The number of responses in the store.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Whether the store holds no responses.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Lru> {
+        self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl CacheStore for MemoryCacheStore {
+    fn get(&self, url: &str) -> io::Result<Option<CachedResponse>> {
+        let mut lru = self.lock();
+        lru.touch(url);
+
+        Ok(lru.entries.get(url).map(|(_, response)| response.clone()))
+    }
+
+    fn put(&self, url: &str, response: &CachedResponse) -> io::Result<()> {
+        if self.capacity == 0 {
+            return Ok(());
+        }
+
+        let mut lru = self.lock();
+        lru.entries.insert(url.to_owned(), (0, response.clone()));
+        lru.touch(url);
+
+        while lru.entries.len() > self.capacity {
+            let oldest = match lru.recency.keys().next() {
+                Some(&tick) => tick,
+                None => break,
+            };
+
+            if let Some(url) = lru.recency.remove(&oldest) {
+                lru.entries.remove(&url);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn remove(&self, url: &str) -> io::Result<()> {
+        let mut lru = self.lock();
+
+        if let Some((tick, _)) = lru.entries.remove(url) {
+            lru.recency.remove(&tick);
+        }
+
+        Ok(())
+    }
+}
+
+/// A store keeping each response in a JSON file in a directory.
+///
+/// Files are named after a hash of the URL, and are re-read on every lookup,
+/// so that responses stored by other processes are picked up. Responses are
+/// saved by writing to a temporary file and renaming it over the response's
+/// file, so that a partially written response is never loaded.
+#[derive(Clone, Debug)]
+pub struct FileCacheStore {
+    directory: PathBuf,
+}
+
+/// The contents of a file of a [`FileCacheStore`], which includes the URL so
+/// that responses for URLs with the same hash are told apart.
+#[derive(Deserialize, Serialize)]
+struct FileEntry {
+    response: CachedResponse,
+    url: String,
+}
+
+impl FileCacheStore {
+    /// Creates a store for the directory at a path, which does not need to
+    /// exist yet.
+    pub fn new<P: Into<PathBuf>>(directory: P) -> Self {
+        FileCacheStore {
+            directory: directory.into(),
+        }
+    }
+
+    /// The path of the store's directory.
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.directory
+    }
+
+    fn file(&self, url: &str) -> PathBuf {
+        self.directory.join(format!("{:016x}.json", fnv1a(url.as_bytes())))
+    }
+}
+
+impl CacheStore for FileCacheStore {
+    fn get(&self, url: &str) -> io::Result<Option<CachedResponse>> {
+        let file = match File::open(self.file(url)) {
+            Ok(file) => file,
+            Err(ref why) if why.kind() == ErrorKind::NotFound => {
+                return Ok(None);
+            },
+            Err(why) => return Err(why),
+        };
+
+        let entry: FileEntry = serde_json::from_reader(file)
+            .map_err(|why| io::Error::new(ErrorKind::InvalidData, why))?;
+
+        if entry.url == url {
+            Ok(Some(entry.response))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn put(&self, url: &str, response: &CachedResponse) -> io::Result<()> {
+        let json = serde_json::to_vec(&FileEntry {
+            response: response.clone(),
+            url: url.to_owned(),
+        }).map_err(|why| io::Error::new(ErrorKind::InvalidData, why))?;
+
+        let mut options = OpenOptions::new();
+        options.create_new(true).write(true);
+
+        fs::create_dir_all(&self.directory)?;
+        file::replace(&self.file(url), &options, &json)
+    }
+
+    fn remove(&self, url: &str) -> io::Result<()> {
+        match fs::remove_file(self.file(url)) {
+            Err(ref why) if why.kind() == ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+}
+
+/// Hashes bytes with the 64-bit FNV-1a function, which unlike the standard
+/// library's hasher is stable across processes and versions.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
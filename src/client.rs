@@ -0,0 +1,636 @@
+//! A configurable wrapper around an HTTP client.
+//!
+//! The requester traits are implemented directly on the supported HTTP
+//! clients, in which case requests are always made to [`API_URL`]. When the
+//! base URL, default headers, or user agent need to be changed - for example
+//! to point the library at a staging mirror, a caching proxy, or a local mock
+//! server - wrap the client in a [`KitsuClient`] instead.
+//!
+//! # Examples
+//!
+//! Send requests for a `reqwest` Client to a local mirror of the API:
+//!
+//! ```rust,no_run
+//! extern crate kitsu;
+//! # #[cfg(feature = "reqwest")]
+//! extern crate reqwest;
+//!
+//! # #[cfg(feature = "reqwest")]
+//! # fn main() {
+//! #
+//! use kitsu::{KitsuClient, KitsuReqwestRequester};
+//! use reqwest::Client;
+//!
+//! let client = KitsuClient::new(Client::new())
+//!     .base_url("http://localhost:8080/api/edge")
+//!     .user_agent("my-app/0.1")
+//!     .header("X-Request-Source", "docs");
+//!
+//! let anime = client.get_anime(1).expect("Error getting anime");
+//! # }
+//! #
+//! # #[cfg(not(feature = "reqwest"))]
+//! # fn main() { }
+//! ```
+//!
+//! [`API_URL`]: ../constant.API_URL.html
+//! [`KitsuClient`]: struct.KitsuClient.html
+
+use std::sync::Arc;
+use crate::auth::{Auth, Token};
+use crate::cache::HttpCache;
+use crate::middleware::Middleware;
+use crate::rate_limit::RateLimiter;
+use crate::retry::RetryPolicy;
+use crate::{Result, API_URL};
+
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+use crate::endpoint::Endpoint;
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+use crate::transport::HttpRequest;
+
+#[cfg(feature = "reqwest_async")]
+use tokio::sync::oneshot;
+#[cfg(feature = "reqwest_async")]
+use crate::transport::AsyncHttpTransport;
+#[cfg(feature = "reqwest")]
+use std::sync::mpsc;
+#[cfg(feature = "reqwest")]
+use crate::transport::HttpTransport;
+#[cfg(any(feature = "reqwest", feature = "reqwest_async"))]
+use std::time::Duration;
+#[cfg(any(feature = "reqwest", feature = "reqwest_async"))]
+use crate::cache::Lookup;
+#[cfg(any(feature = "reqwest", feature = "reqwest_async"))]
+use crate::middleware::Chain;
+#[cfg(any(feature = "reqwest", feature = "reqwest_async"))]
+use crate::transport::HttpResponse;
+#[cfg(any(feature = "reqwest", feature = "reqwest_async"))]
+use crate::auth::Renewal;
+#[cfg(any(feature = "reqwest", feature = "reqwest_async"))]
+use crate::endpoint;
+#[cfg(any(feature = "reqwest", feature = "reqwest_async"))]
+use crate::trace::{self, RequestSpan};
+#[cfg(any(feature = "reqwest", feature = "reqwest_async"))]
+use serde::de::DeserializeOwned;
+
+/// A wrapper around an HTTP client holding the configuration that every
+/// request made through it uses.
+///
+/// The bridges' requester traits are implemented for a `KitsuClient` wrapping
+/// each of the supported HTTP clients, as well as any client implementing
+/// [`HttpTransport`], [`AsyncHttpTransport`], or [`FuturesHttpTransport`].
+///
+/// [`AsyncHttpTransport`]: ../transport/trait.AsyncHttpTransport.html
+/// [`FuturesHttpTransport`]: ../transport/trait.FuturesHttpTransport.html
+/// [`HttpTransport`]: ../transport/trait.HttpTransport.html
+#[derive(Clone, Debug)]
+pub struct KitsuClient<C> {
+    pub(crate) client: C,
+    pub(crate) auth: Option<Arc<Auth>>,
+    pub(crate) base_url: String,
+    pub(crate) cache: Option<HttpCache>,
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) middleware: Vec<Arc<dyn Middleware>>,
+    pub(crate) rate_limit: Option<RateLimiter>,
+    pub(crate) retry: Option<RetryPolicy>,
+    pub(crate) user_agent: Option<String>,
+}
+
+impl<C> KitsuClient<C> {
+    /// Wraps an HTTP client, using [`API_URL`] as the base URL with no
+    /// additional headers or user agent.
+    ///
+    /// [`API_URL`]: ../constant.API_URL.html
+    pub fn new(client: C) -> Self {
+        KitsuClient {
+            client,
+            auth: None,
+            base_url: API_URL.to_owned(),
+            cache: None,
+            headers: Vec::new(),
+            middleware: Vec::new(),
+            rate_limit: None,
+            retry: None,
+            user_agent: None,
+        }
+    }
+
+    /// Authenticates every request with a token obtained through the
+    /// [`Auth`].
+    ///
+    /// [`Auth`]: ../auth/struct.Auth.html
+    pub fn auth(mut self, auth: Auth) -> Self {
+        self.auth = Some(Arc::new(auth));
+
+        self
+    }
+
+    /// Sets the base URL that request paths are appended to.
+    ///
+    /// A trailing slash is removed, so both `http://localhost/api/edge` and
+    /// `http://localhost/api/edge/` are accepted.
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_owned();
+
+        self
+    }
+
+    /// Caches responses to `GET` requests in an [`HttpCache`], which may be
+    /// shared with other clients.
+    ///
+    /// Responses are not cached by default. Authenticated requests bypass a
+    /// cache unless it is [private].
+    ///
+    /// [private]: ../cache/struct.HttpCache.html#method.private
+    /// [`HttpCache`]: ../cache/struct.HttpCache.html
+    pub fn cache(mut self, cache: HttpCache) -> Self {
+        self.cache = Some(cache);
+
+        self
+    }
+
+    /// Adds a header to send with every request.
+    ///
+    /// Headers are sent in the order they were added, and adding a header
+    /// with the same name multiple times will send it multiple times.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_owned(), value.to_owned()));
+
+        self
+    }
+
+    /// Adds a [`Middleware`] to run around every request.
+    ///
+    /// Middleware are run in the order they were added before a request is
+    /// sent, and in the reverse order after its response is received.
+    ///
+    /// [`Middleware`]: ../middleware/trait.Middleware.html
+    pub fn middleware<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+        self.middleware.push(Arc::new(middleware));
+
+        self
+    }
+
+    /// Limits the rate that requests are sent at with a [`RateLimiter`],
+    /// which may be shared with other clients.
+    ///
+    /// Requests are not limited by default.
+    ///
+    /// [`RateLimiter`]: ../rate_limit/struct.RateLimiter.html
+    pub fn rate_limit(mut self, limiter: RateLimiter) -> Self {
+        self.rate_limit = Some(limiter);
+
+        self
+    }
+
+    /// Retries requests that fail for transient reasons according to a
+    /// [`RetryPolicy`].
+    ///
+    /// Requests are not retried by default.
+    ///
+    /// [`RetryPolicy`]: ../retry/struct.RetryPolicy.html
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+
+        self
+    }
+
+    /// Sets the `User-Agent` header to send with every request.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_owned());
+
+        self
+    }
+
+    /// Loads the token that requests are authenticated with, if one has been
+    /// obtained.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TokenStore`] if the token could not be loaded from
+    /// the [`Auth`]'s store.
+    ///
+    /// [`Auth`]: ../auth/struct.Auth.html
+    /// [`Error::TokenStore`]: ../enum.Error.html#variant.TokenStore
+    pub fn auth_token(&self) -> Result<Option<Token>> {
+        match self.auth {
+            Some(ref auth) => auth.current_token(),
+            None => Ok(None),
+        }
+    }
+
+    /// Retrieves an immutable reference to the wrapped HTTP client.
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    /// Consumes the wrapper, returning the wrapped HTTP client.
+    pub fn into_inner(self) -> C {
+        self.client
+    }
+
+    /// Creates a wrapper around another HTTP client, sharing this wrapper's
+    /// configuration and authentication.
+    #[cfg(feature = "reqwest")]
+    pub(crate) fn with_client<D>(&self, client: D) -> KitsuClient<D> {
+        KitsuClient {
+            client,
+            auth: self.auth.clone(),
+            base_url: self.base_url.clone(),
+            cache: self.cache.clone(),
+            headers: self.headers.clone(),
+            middleware: self.middleware.clone(),
+            rate_limit: self.rate_limit.clone(),
+            retry: self.retry.clone(),
+            user_agent: self.user_agent.clone(),
+        }
+    }
+
+    /// Creates the full URL to a path relative to the base URL, unless the
+    /// path is already an absolute URL.
+    #[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+    pub(crate) fn url(&self, path: &str) -> String {
+        if path.starts_with("https://") || path.starts_with("http://") {
+            return path.to_owned();
+        }
+
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Creates the request for an endpoint, applying the default headers and
+    /// user agent.
+    #[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+    pub(crate) fn request<T>(&self, endpoint: Endpoint<T>) -> HttpRequest {
+        let mut request = HttpRequest::new(
+            endpoint.method,
+            self.url(&endpoint.path),
+        );
+        request.body = endpoint.body;
+
+        if let Some(ref user_agent) = self.user_agent {
+            request.headers.push((
+                "User-Agent".to_owned(),
+                user_agent.clone(),
+            ));
+        }
+
+        request.headers.extend(self.headers.iter().cloned());
+        request.headers.extend(endpoint.headers);
+
+        request
+    }
+
+    /// Whether a request is authenticated, either by the client's [`Auth`]
+    /// or by an `Authorization` header of its own.
+    ///
+    /// [`Auth`]: ../auth/struct.Auth.html
+    #[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+    pub(crate) fn is_authorized(&self, request: &HttpRequest) -> bool {
+        self.auth.is_some() || request.header("Authorization").is_some()
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl<C: HttpTransport> KitsuClient<C> {
+    /// Sends the request for an endpoint and decodes its response.
+    pub(crate) fn execute<T: DeserializeOwned>(&self, endpoint: Endpoint<T>)
+        -> Result<T> {
+        let request = self.request(endpoint);
+        let span = RequestSpan::new(&request, &self.base_url);
+
+        span.in_scope(|| {
+            let response = self.send_through(request);
+            span.record(&response);
+
+            span.decode(response?)
+        })
+    }
+
+    /// Sends a request through the client's [`Middleware`].
+    ///
+    /// [`Middleware`]: ../middleware/trait.Middleware.html
+    fn send_through(&self, mut request: HttpRequest) -> Result<HttpResponse> {
+        if self.middleware.is_empty() {
+            return self.send_cached(request);
+        }
+
+        let mut chain = Chain::new(&self.middleware);
+        let outcome = match chain.before(&mut request) {
+            Ok(Some(response)) => Ok(response),
+            Ok(None) => self.send_cached(request.clone()),
+            Err(why) => Err(why),
+        };
+
+        chain.after(&request, outcome)
+    }
+
+    /// Sends a request, or returns a fresh response from the client's
+    /// [`HttpCache`] instead.
+    ///
+    /// [`HttpCache`]: ../cache/struct.HttpCache.html
+    fn send_cached(&self, mut request: HttpRequest) -> Result<HttpResponse> {
+        let cache = match self.cache {
+            Some(ref cache) => cache,
+            None => return self.send(request),
+        };
+        let authorized = self.is_authorized(&request);
+        let stale = match cache.lookup(&mut request, authorized) {
+            Lookup::Fresh(response) => return Ok(response),
+            Lookup::Miss(stale) => stale,
+        };
+        let (method, url) = (request.method, request.url.clone());
+        let response = self.send(request)?;
+
+        Ok(cache.update(method, &url, authorized, stale, response))
+    }
+
+    /// Sends a request, authenticating it if the client has an [`Auth`].
+    ///
+    /// A token is obtained first if there is no usable one, and the request
+    /// is sent again with a new token if the API rejects the current one.
+    ///
+    /// [`Auth`]: ../auth/struct.Auth.html
+    fn send(&self, mut request: HttpRequest) -> Result<HttpResponse> {
+        let auth = match self.auth {
+            Some(ref auth) => auth,
+            None => return self.send_retrying(request),
+        };
+
+        self.renew(auth, None)?;
+        auth.authorize(&mut request)?;
+        let response = self.send_retrying(request.clone())?;
+
+        if response.status != 401 || !self.renew(auth, Some(&request))? {
+            return Ok(response);
+        }
+
+        auth.authorize(&mut request)?;
+
+        self.send_retrying(request)
+    }
+
+    /// Renews the token of an [`Auth`] unless it is usable, waiting for a
+    /// renewal by another request to finish instead of starting another.
+    ///
+    /// Returns whether there is a token to send the request with, which is
+    /// not the case if there were no grants to renew it with.
+    ///
+    /// [`Auth`]: ../auth/struct.Auth.html
+    fn renew(&self, auth: &Arc<Auth>, rejected: Option<&HttpRequest>)
+        -> Result<bool> {
+        loop {
+            let (sender, receiver) = mpsc::channel();
+            let renewal = Auth::renewal(auth, rejected, move || {
+                let _ = sender.send(());
+            })?;
+
+            match renewal {
+                Renewal::Usable => return Ok(true),
+                Renewal::Pending => {
+                    let _ = receiver.recv();
+                },
+                Renewal::Grants(_, ref grants) if grants.is_empty() => {
+                    return Ok(false);
+                },
+                Renewal::Grants(_guard, grants) => {
+                    self.authenticate(auth, grants)?;
+
+                    return Ok(true);
+                },
+            }
+        }
+    }
+
+    /// Sends a request, sending it again after a delay for as long as the
+    /// client's [`RetryPolicy`] allows.
+    ///
+    /// [`RetryPolicy`]: ../retry/struct.RetryPolicy.html
+    fn send_retrying(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let policy = match self.retry {
+            Some(ref policy) => policy,
+            None => return self.send_limited(request),
+        };
+        let mut attempt = 1;
+
+        loop {
+            let outcome = self.send_limited(request.clone());
+
+            match policy.delay(&request, attempt, &outcome) {
+                Some(delay) => {
+                    trace::retry(attempt + 1, delay);
+                    self.client.sleep(delay);
+                },
+                None => return outcome,
+            }
+
+            attempt += 1;
+        }
+    }
+
+    /// Sends a request once the client's [`RateLimiter`] allows it.
+    ///
+    /// [`RateLimiter`]: ../rate_limit/struct.RateLimiter.html
+    fn send_limited(&self, request: HttpRequest) -> Result<HttpResponse> {
+        if let Some(ref limiter) = self.rate_limit {
+            let delay = limiter.reserve();
+
+            if delay > Duration::from_secs(0) {
+                self.client.sleep(delay);
+            }
+        }
+
+        self.client.send(request)
+    }
+
+    /// Requests a token with each grant in turn until one succeeds, storing
+    /// it in the [`Auth`].
+    ///
+    /// The error of the last grant is returned if every grant fails.
+    ///
+    /// [`Auth`]: ../auth/struct.Auth.html
+    fn authenticate(&self, auth: &Auth, grants: Vec<Endpoint<Token>>)
+        -> Result<()> {
+        let mut failure = None;
+
+        for grant in grants {
+            let response = self.send_retrying(self.request(grant));
+
+            match response.and_then(endpoint::decode) {
+                Ok(token) => return auth.update(token),
+                Err(why) => failure = Some(why),
+            }
+        }
+
+        failure.map_or(Ok(()), Err)
+    }
+}
+
+#[cfg(feature = "reqwest_async")]
+impl<C: AsyncHttpTransport> KitsuClient<C> {
+    /// Sends the request for an endpoint and decodes its response.
+    pub(crate) async fn execute_async<T: DeserializeOwned>(
+        &self,
+        endpoint: Endpoint<T>,
+    ) -> Result<T> {
+        let request = self.request(endpoint);
+        let span = RequestSpan::new(&request, &self.base_url);
+
+        span.instrument(async {
+            let response = self.send_through_async(request).await;
+            span.record(&response);
+
+            span.decode(response?)
+        }).await
+    }
+
+    /// Sends a request through the client's middleware.
+    async fn send_through_async(&self, mut request: HttpRequest)
+        -> Result<HttpResponse> {
+        if self.middleware.is_empty() {
+            return self.send_cached_async(request).await;
+        }
+
+        let mut chain = Chain::new(&self.middleware);
+        let outcome = match chain.before(&mut request) {
+            Ok(Some(response)) => Ok(response),
+            Ok(None) => self.send_cached_async(request.clone()).await,
+            Err(why) => Err(why),
+        };
+
+        chain.after(&request, outcome)
+    }
+
+    /// Sends a request, or returns a fresh response from the client's cache
+    /// instead.
+    async fn send_cached_async(&self, mut request: HttpRequest)
+        -> Result<HttpResponse> {
+        let cache = match self.cache {
+            Some(ref cache) => cache,
+            None => return self.send_async(request).await,
+        };
+        let authorized = self.is_authorized(&request);
+        let stale = match cache.lookup(&mut request, authorized) {
+            Lookup::Fresh(response) => return Ok(response),
+            Lookup::Miss(stale) => stale,
+        };
+        let (method, url) = (request.method, request.url.clone());
+        let response = self.send_async(request).await?;
+
+        Ok(cache.update(method, &url, authorized, stale, response))
+    }
+
+    /// Sends a request, authenticating it in the same way as the blocking
+    /// executor.
+    async fn send_async(&self, mut request: HttpRequest)
+        -> Result<HttpResponse> {
+        let auth = match self.auth {
+            Some(ref auth) => auth,
+            None => return self.send_retrying_async(request).await,
+        };
+
+        self.renew_async(auth, None).await?;
+        auth.authorize(&mut request)?;
+        let response = self.send_retrying_async(request.clone()).await?;
+
+        if response.status != 401
+            || !self.renew_async(auth, Some(&request)).await? {
+            return Ok(response);
+        }
+
+        auth.authorize(&mut request)?;
+
+        self.send_retrying_async(request).await
+    }
+
+    /// Renews the token of an [`Auth`] in the same way as the blocking
+    /// executor.
+    ///
+    /// [`Auth`]: ../auth/struct.Auth.html
+    async fn renew_async(
+        &self,
+        auth: &Arc<Auth>,
+        rejected: Option<&HttpRequest>,
+    ) -> Result<bool> {
+        loop {
+            let (sender, receiver) = oneshot::channel();
+            let renewal = Auth::renewal(auth, rejected, move || {
+                let _ = sender.send(());
+            })?;
+
+            match renewal {
+                Renewal::Usable => return Ok(true),
+                Renewal::Pending => {
+                    let _ = receiver.await;
+                },
+                Renewal::Grants(_, ref grants) if grants.is_empty() => {
+                    return Ok(false);
+                },
+                Renewal::Grants(_guard, grants) => {
+                    self.authenticate_async(auth, grants).await?;
+
+                    return Ok(true);
+                },
+            }
+        }
+    }
+
+    /// Sends a request, retrying it in the same way as the blocking
+    /// executor.
+    async fn send_retrying_async(&self, request: HttpRequest)
+        -> Result<HttpResponse> {
+        let policy = match self.retry {
+            Some(ref policy) => policy,
+            None => return self.send_limited_async(request).await,
+        };
+        let mut attempt = 1;
+
+        loop {
+            let outcome = self.send_limited_async(request.clone()).await;
+
+            match policy.delay(&request, attempt, &outcome) {
+                Some(delay) => {
+                    trace::retry(attempt + 1, delay);
+                    self.client.sleep(delay).await;
+                },
+                None => return outcome,
+            }
+
+            attempt += 1;
+        }
+    }
+
+    /// Sends a request once the client's rate limiter allows it.
+    async fn send_limited_async(&self, request: HttpRequest)
+        -> Result<HttpResponse> {
+        if let Some(ref limiter) = self.rate_limit {
+            let delay = limiter.reserve();
+
+            if delay > Duration::from_secs(0) {
+                self.client.sleep(delay).await;
+            }
+        }
+
+        self.client.send(request).await
+    }
+
+    async fn authenticate_async(
+        &self,
+        auth: &Auth,
+        grants: Vec<Endpoint<Token>>,
+    ) -> Result<()> {
+        let mut failure = None;
+
+        for grant in grants {
+            let request = self.request(grant);
+            let response = self.send_retrying_async(request).await;
+
+            match response.and_then(endpoint::decode) {
+                Ok(token) => return auth.update(token),
+                Err(why) => failure = Some(why),
+            }
+        }
+
+        failure.map_or(Ok(()), Err)
+    }
+}
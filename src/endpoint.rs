@@ -0,0 +1,622 @@
+//! Definitions of the API's endpoints, shared by every bridge.
+//!
+//! Each bridge turns an [`Endpoint`] into a request through
+//! [`KitsuClient::request`], sends it with its HTTP client, and passes the
+//! response to [`decode`]. The requester traits' methods are generated from
+//! the table in `requester_methods!`, so adding a resource only requires
+//! adding its endpoints and their entries here.
+//!
+//! [`Endpoint`]: struct.Endpoint.html
+//! [`KitsuClient::request`]: ../client/struct.KitsuClient.html#method.request
+//! [`decode`]: fn.decode.html
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::json;
+use std::marker::PhantomData;
+use crate::auth::{Token, TokenRequest};
+use crate::builder::{
+    AnimeSearch,
+    CharacterSearch,
+    LibraryEntryBuilder,
+    MangaSearch,
+    Search,
+    UserSearch,
+};
+use crate::model::{
+    Anime,
+    Character,
+    LibraryEntry,
+    LibraryMedia,
+    Manga,
+    Producer,
+    Relationship,
+    Resource,
+    Response,
+    User,
+};
+use crate::transport::{HttpResponse, Method};
+use crate::{Error, Result};
+
+/// Passes the documentation, signature, and endpoint of every method shared
+/// by the requester traits to a bridge's macro, which declares or implements
+/// them for its trait.
+///
+/// The bridge's macro is invoked with `$mode` followed by one entry per
+/// method, of the form:
+///
+/// ```text
+/// /// Documentation.
+/// fn name[Generics](argument: Type, ...) -> Output where [Bounds]
+///     => endpoint;
+/// ```
+///
+/// The endpoint expression may only refer to the method's arguments, and
+/// evaluates to an [`IntoEndpoint`]. Types are written with their full paths,
+/// as they are resolved in the bridge's module.
+///
+/// [`IntoEndpoint`]: trait.IntoEndpoint.html
+macro_rules! requester_methods {
+    ($bridge:ident, $mode:ident) => {
+        $bridge! {
+            $mode
+
+            /// Gets an anime using its id.
+            ///
+            /// # Errors
+            ///
+            /// Returns [`Error::Decode`] if there was an error parsing the
+            /// response body.
+            ///
+            /// Returns [`Error::InvalidRequest`] if the request could not be
+            /// built or was rejected by the API.
+            ///
+            /// Returns [`Error::NotFound`] if the requested resource does not
+            /// exist.
+            ///
+            /// Returns [`Error::RateLimited`] if too many requests have been
+            /// made.
+            ///
+            /// Returns [`Error::Server`] if the API failed to handle the
+            /// request.
+            ///
+            /// Returns [`Error::Transport`] if the request could not be sent or
+            /// the response could not be received.
+            ///
+            /// Returns [`Error::Unauthorized`] if the authorization token was
+            /// invalid.
+            ///
+            /// [`Error::Decode`]: ../../enum.Error.html#variant.Decode
+            /// [`Error::InvalidRequest`]: ../../enum.Error.html#variant.InvalidRequest
+            /// [`Error::NotFound`]: ../../enum.Error.html#variant.NotFound
+            /// [`Error::RateLimited`]: ../../enum.Error.html#variant.RateLimited
+            /// [`Error::Server`]: ../../enum.Error.html#variant.Server
+            /// [`Error::Transport`]: ../../enum.Error.html#variant.Transport
+            /// [`Error::Unauthorized`]: ../../enum.Error.html#variant.Unauthorized
+            fn get_anime[](id: u64)
+                -> $crate::model::Response<$crate::model::Anime>
+                where []
+                => $crate::endpoint::anime(id);
+
+            /// Gets a character using its id.
+            ///
+            /// # Errors
+            ///
+            /// Returns the same errors as [`get_anime`].
+            ///
+            /// [`get_anime`]: #tymethod.get_anime
+            fn get_character[](id: u64)
+                -> $crate::model::Response<$crate::model::Character>
+                where []
+                => $crate::endpoint::character(id);
+
+            /// Gets a manga using its id.
+            ///
+            /// # Errors
+            ///
+            /// Returns the same errors as [`get_anime`].
+            ///
+            /// [`get_anime`]: #tymethod.get_anime
+            fn get_manga[](id: u64)
+                -> $crate::model::Response<$crate::model::Manga>
+                where []
+                => $crate::endpoint::manga(id);
+
+            /// Gets a producer using its id.
+            ///
+            /// # Errors
+            ///
+            /// Returns the same errors as [`get_anime`].
+            ///
+            /// [`get_anime`]: #tymethod.get_anime
+            fn get_producer[](id: u64)
+                -> $crate::model::Response<$crate::model::Producer>
+                where []
+                => $crate::endpoint::producer(id);
+
+            /// Gets a user using their id.
+            ///
+            /// # Errors
+            ///
+            /// Returns the same errors as [`get_anime`].
+            ///
+            /// [`get_anime`]: #tymethod.get_anime
+            fn get_user[](id: u64)
+                -> $crate::model::Response<$crate::model::User>
+                where []
+                => $crate::endpoint::user(id);
+
+            /// Gets a resource of any type using its id, with the request's
+            /// query built by the passed [`Search`] builder.
+            ///
+            /// This is used to include related resources or otherwise change
+            /// the query of requests for a single resource.
+            ///
+            /// # Errors
+            ///
+            /// Returns the same errors as [`get_anime`].
+            ///
+            /// [`Search`]: ../../builder/struct.Search.html
+            /// [`get_anime`]: #tymethod.get_anime
+            fn get_resource[T, F](id: u64, f: F)
+                -> $crate::model::Response<T>
+                where [
+                    T: $crate::model::Resource + 'static,
+                    F: FnOnce($crate::builder::Search) -> $crate::builder::Search,
+                ]
+                => $crate::endpoint::resource(
+                    id,
+                    f($crate::builder::Search::default()),
+                );
+
+            /// Searches for resources of any type using the passed [`Search`]
+            /// builder.
+            ///
+            /// This is used to search for resources that do not have their
+            /// own search method, or for [`Partial`] resources when requesting
+            /// a sparse fieldset.
+            ///
+            /// # Errors
+            ///
+            /// Returns the same errors as [`get_anime`].
+            ///
+            /// [`Partial`]: ../../model/struct.Partial.html
+            /// [`Search`]: ../../builder/struct.Search.html
+            /// [`get_anime`]: #tymethod.get_anime
+            fn search_resources[T, F](f: F)
+                -> $crate::model::Response<Vec<T>>
+                where [
+                    T: $crate::model::Resource + 'static,
+                    F: FnOnce($crate::builder::Search) -> $crate::builder::Search,
+                ]
+                => $crate::endpoint::search_resources(
+                    f($crate::builder::Search::default()),
+                );
+
+            /// Gets the related resources of a relationship, following its
+            /// `related` link.
+            ///
+            /// Use a `Vec` of the related model for to-many relationships, and
+            /// the model itself for to-one relationships.
+            ///
+            /// # Errors
+            ///
+            /// Returns [`Error::Decode`] if the related resources could not be
+            /// deserialized as `T`.
+            ///
+            /// Otherwise, returns the same errors as [`get_anime`].
+            ///
+            /// [`Error::Decode`]: ../../enum.Error.html#variant.Decode
+            /// [`get_anime`]: #tymethod.get_anime
+            fn fetch_related[T](relationship: &$crate::model::Relationship)
+                -> $crate::model::Response<T>
+                where [T: ::serde::de::DeserializeOwned + 'static]
+                => $crate::endpoint::related(relationship);
+
+            /// Searches for an anime using the passed [`AnimeSearch`] builder.
+            ///
+            /// # Errors
+            ///
+            /// Returns the same errors as [`get_anime`].
+            ///
+            /// [`AnimeSearch`]: ../../builder/struct.AnimeSearch.html
+            /// [`get_anime`]: #tymethod.get_anime
+            fn search_anime[F](f: F)
+                -> $crate::model::Response<Vec<$crate::model::Anime>>
+                where [
+                    F: FnOnce($crate::builder::AnimeSearch)
+                        -> $crate::builder::AnimeSearch,
+                ]
+                => $crate::endpoint::search_anime(
+                    f($crate::builder::AnimeSearch::default()),
+                );
+
+            /// Searches for a character using the passed [`CharacterSearch`]
+            /// builder.
+            ///
+            /// # Errors
+            ///
+            /// Returns the same errors as [`get_anime`].
+            ///
+            /// [`CharacterSearch`]: ../../builder/struct.CharacterSearch.html
+            /// [`get_anime`]: #tymethod.get_anime
+            fn search_characters[F](f: F)
+                -> $crate::model::Response<Vec<$crate::model::Character>>
+                where [
+                    F: FnOnce($crate::builder::CharacterSearch)
+                        -> $crate::builder::CharacterSearch,
+                ]
+                => $crate::endpoint::search_characters(
+                    f($crate::builder::CharacterSearch::default()),
+                );
+
+            /// Searches for a manga using the passed [`MangaSearch`] builder.
+            ///
+            /// # Errors
+            ///
+            /// Returns the same errors as [`get_anime`].
+            ///
+            /// [`MangaSearch`]: ../../builder/struct.MangaSearch.html
+            /// [`get_anime`]: #tymethod.get_anime
+            fn search_manga[F](f: F)
+                -> $crate::model::Response<Vec<$crate::model::Manga>>
+                where [
+                    F: FnOnce($crate::builder::MangaSearch)
+                        -> $crate::builder::MangaSearch,
+                ]
+                => $crate::endpoint::search_manga(
+                    f($crate::builder::MangaSearch::default()),
+                );
+
+            /// Searches for a user using the passed [`UserSearch`] builder.
+            ///
+            /// # Errors
+            ///
+            /// Returns the same errors as [`get_anime`].
+            ///
+            /// [`UserSearch`]: ../../builder/struct.UserSearch.html
+            /// [`get_anime`]: #tymethod.get_anime
+            fn search_users[F](f: F)
+                -> $crate::model::Response<Vec<$crate::model::User>>
+                where [
+                    F: FnOnce($crate::builder::UserSearch)
+                        -> $crate::builder::UserSearch,
+                ]
+                => $crate::endpoint::search_users(
+                    f($crate::builder::UserSearch::default()),
+                );
+
+            /// Gets the entries in a user's library, using the user's id and
+            /// the passed [`Search`] builder.
+            ///
+            /// Private entries are only included if the request is
+            /// authenticated as the user.
+            ///
+            /// # Errors
+            ///
+            /// Returns the same errors as [`get_anime`].
+            ///
+            /// [`Search`]: ../../builder/struct.Search.html
+            /// [`get_anime`]: #tymethod.get_anime
+            fn get_library_entries[F](user_id: u64, f: F)
+                -> $crate::model::Response<Vec<$crate::model::LibraryEntry>>
+                where [
+                    F: FnOnce($crate::builder::Search) -> $crate::builder::Search,
+                ]
+                => $crate::endpoint::library_entries(
+                    user_id,
+                    f($crate::builder::Search::default()),
+                );
+
+            /// Adds an anime or manga to a user's library, setting the
+            /// attributes of the new entry with the passed
+            /// [`LibraryEntryBuilder`].
+            ///
+            /// The request must be authenticated as the user; refer to the
+            /// [`auth`] module.
+            ///
+            /// # Errors
+            ///
+            /// Returns [`Error::InvalidRequest`] if the media is already in the
+            /// user's library, or an attribute is invalid.
+            ///
+            /// Returns [`Error::Unauthorized`] if the request is not
+            /// authenticated as the user.
+            ///
+            /// Otherwise, returns the same errors as [`get_anime`].
+            ///
+            /// [`Error::InvalidRequest`]: ../../enum.Error.html#variant.InvalidRequest
+            /// [`Error::Unauthorized`]: ../../enum.Error.html#variant.Unauthorized
+            /// [`LibraryEntryBuilder`]: ../../builder/struct.LibraryEntryBuilder.html
+            /// [`auth`]: ../../auth/index.html
+            /// [`get_anime`]: #tymethod.get_anime
+            fn create_library_entry[F](
+                user_id: u64,
+                media: $crate::model::LibraryMedia,
+                f: F,
+            ) -> $crate::model::Response<$crate::model::LibraryEntry>
+                where [
+                    F: FnOnce($crate::builder::LibraryEntryBuilder)
+                        -> $crate::builder::LibraryEntryBuilder,
+                ]
+                => $crate::endpoint::create_library_entry(
+                    user_id,
+                    media,
+                    &f($crate::builder::LibraryEntryBuilder::default()),
+                );
+
+            /// Updates a library entry using its id, changing the attributes
+            /// set with the passed [`LibraryEntryBuilder`].
+            ///
+            /// The request must be authenticated as the entry's user.
+            ///
+            /// # Errors
+            ///
+            /// Returns the same errors as [`create_library_entry`].
+            ///
+            /// [`LibraryEntryBuilder`]: ../../builder/struct.LibraryEntryBuilder.html
+            /// [`create_library_entry`]: #tymethod.create_library_entry
+            fn update_library_entry[F](id: u64, f: F)
+                -> $crate::model::Response<$crate::model::LibraryEntry>
+                where [
+                    F: FnOnce($crate::builder::LibraryEntryBuilder)
+                        -> $crate::builder::LibraryEntryBuilder,
+                ]
+                => $crate::endpoint::update_library_entry(
+                    id,
+                    &f($crate::builder::LibraryEntryBuilder::default()),
+                );
+
+            /// Deletes a library entry using its id.
+            ///
+            /// The request must be authenticated as the entry's user.
+            ///
+            /// # Errors
+            ///
+            /// Returns the same errors as [`create_library_entry`].
+            ///
+            /// [`create_library_entry`]: #tymethod.create_library_entry
+            fn delete_library_entry[](id: u64) -> ()
+                where []
+                => $crate::endpoint::delete_library_entry(id);
+        }
+    };
+}
+
+pub(crate) use requester_methods;
+
+/// The media type of JSON:API request bodies.
+const JSON_API: &str = "application/vnd.api+json";
+
+/// A request to the API, relative to the base URL, whose successful response
+/// deserializes to `T`.
+#[derive(Debug)]
+pub(crate) struct Endpoint<T> {
+    /// The body to send, if any.
+    pub body: Option<Vec<u8>>,
+    /// Headers specific to the endpoint, such as the type of the body.
+    pub headers: Vec<(String, String)>,
+    /// The method of the request.
+    pub method: Method,
+    /// The path of the request, including its query string.
+    pub path: String,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Endpoint<T> {
+    fn get(path: String) -> Self {
+        Endpoint {
+            body: None,
+            headers: Vec::new(),
+            method: Method::Get,
+            path,
+            marker: PhantomData,
+        }
+    }
+
+    /// Creates an endpoint sending a body serialized as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`] if the body could not be serialized.
+    ///
+    /// [`Error::InvalidRequest`]: ../enum.Error.html#variant.InvalidRequest
+    fn json<B: Serialize>(
+        method: Method,
+        path: String,
+        content_type: &str,
+        body: &B,
+    ) -> Result<Self> {
+        let body = serde_json::to_vec(body).map_err(invalid_body)?;
+
+        Ok(Endpoint {
+            body: Some(body),
+            headers: vec![("Content-Type".to_owned(), content_type.to_owned())],
+            method,
+            path,
+            marker: PhantomData,
+        })
+    }
+
+    fn search(path: &str, search: Search) -> Self {
+        let query = search.0;
+        let query = query.trim_start_matches('&');
+
+        if query.is_empty() {
+            Endpoint::get(path.to_owned())
+        } else {
+            Endpoint::get(format!("{}?{}", path, query))
+        }
+    }
+}
+
+/// The error for a request body that could not be serialized.
+fn invalid_body(why: serde_json::Error) -> Error {
+    Error::InvalidRequest {
+        response: None,
+        source: Some(Box::new(why)),
+    }
+}
+
+/// An endpoint, or the result of building one that could fail, so that the
+/// requester methods can be generated for both kinds.
+pub(crate) trait IntoEndpoint<T> {
+    fn into_endpoint(self) -> Result<Endpoint<T>>;
+}
+
+impl<T> IntoEndpoint<T> for Endpoint<T> {
+    fn into_endpoint(self) -> Result<Endpoint<T>> {
+        Ok(self)
+    }
+}
+
+impl<T> IntoEndpoint<T> for Result<Endpoint<T>> {
+    fn into_endpoint(self) -> Result<Endpoint<T>> {
+        self
+    }
+}
+
+pub(crate) fn anime(id: u64) -> Endpoint<Response<Anime>> {
+    Endpoint::get(format!("/anime/{}", id))
+}
+
+pub(crate) fn character(id: u64) -> Endpoint<Response<Character>> {
+    Endpoint::get(format!("/characters/{}", id))
+}
+
+pub(crate) fn manga(id: u64) -> Endpoint<Response<Manga>> {
+    Endpoint::get(format!("/manga/{}", id))
+}
+
+pub(crate) fn producer(id: u64) -> Endpoint<Response<Producer>> {
+    Endpoint::get(format!("/producers/{}", id))
+}
+
+pub(crate) fn user(id: u64) -> Endpoint<Response<User>> {
+    Endpoint::get(format!("/users/{}", id))
+}
+
+pub(crate) fn resource<T: Resource>(id: u64, search: Search)
+    -> Endpoint<Response<T>> {
+    Endpoint::search(&format!("{}/{}", T::PATH, id), search)
+}
+
+pub(crate) fn search_resources<T: Resource>(search: Search)
+    -> Endpoint<Response<Vec<T>>> {
+    Endpoint::search(T::PATH, search)
+}
+
+/// Requests a page of a collection, using a path or an absolute `next` link.
+pub(crate) fn page<T>(path: String) -> Endpoint<Response<Vec<T>>> {
+    Endpoint::get(path)
+}
+
+/// Requests the related resources of a relationship, using its absolute
+/// `related` link.
+pub(crate) fn related<T>(relationship: &Relationship) -> Endpoint<T> {
+    Endpoint::get(relationship.links.related.clone())
+}
+
+pub(crate) fn search_anime(search: AnimeSearch)
+    -> Endpoint<Response<Vec<Anime>>> {
+    Endpoint::search("/anime", search.into())
+}
+
+pub(crate) fn search_characters(search: CharacterSearch)
+    -> Endpoint<Response<Vec<Character>>> {
+    Endpoint::search("/characters", search.into())
+}
+
+pub(crate) fn search_manga(search: MangaSearch)
+    -> Endpoint<Response<Vec<Manga>>> {
+    Endpoint::search("/manga", search.into())
+}
+
+pub(crate) fn search_users(search: UserSearch)
+    -> Endpoint<Response<Vec<User>>> {
+    Endpoint::search("/users", search.into())
+}
+
+pub(crate) fn library_entries(user_id: u64, search: Search)
+    -> Endpoint<Response<Vec<LibraryEntry>>> {
+    let search = Search(format!("&filter[userId]={}{}", user_id, search.0));
+
+    Endpoint::search("/library-entries", search)
+}
+
+pub(crate) fn create_library_entry(
+    user_id: u64,
+    media: LibraryMedia,
+    attributes: &LibraryEntryBuilder,
+) -> Result<Endpoint<Response<LibraryEntry>>> {
+    let (media_type, media_id) = match media {
+        LibraryMedia::Anime(id) => ("anime", id),
+        LibraryMedia::Manga(id) => ("manga", id),
+    };
+    let attributes = serde_json::to_value(attributes).map_err(invalid_body)?;
+    let body = json!({
+        "data": {
+            "attributes": attributes,
+            "relationships": {
+                "media": {
+                    "data": { "id": media_id.to_string(), "type": media_type },
+                },
+                "user": {
+                    "data": { "id": user_id.to_string(), "type": "users" },
+                },
+            },
+            "type": "libraryEntries",
+        },
+    });
+
+    Endpoint::json(Method::Post, "/library-entries".to_owned(), JSON_API, &body)
+}
+
+pub(crate) fn update_library_entry(id: u64, attributes: &LibraryEntryBuilder)
+    -> Result<Endpoint<Response<LibraryEntry>>> {
+    let attributes = serde_json::to_value(attributes).map_err(invalid_body)?;
+    let body = json!({
+        "data": {
+            "attributes": attributes,
+            "id": id.to_string(),
+            "type": "libraryEntries",
+        },
+    });
+
+    Endpoint::json(
+        Method::Patch,
+        format!("/library-entries/{}", id),
+        JSON_API,
+        &body,
+    )
+}
+
+pub(crate) fn delete_library_entry(id: u64) -> Endpoint<()> {
+    Endpoint {
+        method: Method::Delete,
+        ..Endpoint::get(format!("/library-entries/{}", id))
+    }
+}
+
+/// Requests a token from the OAuth2 token endpoint at an absolute URL.
+pub(crate) fn token(url: &str, request: &TokenRequest)
+    -> Result<Endpoint<Token>> {
+    Endpoint::json(Method::Post, url.to_owned(), "application/json", request)
+}
+
+/// Checks the status code of a response, deserializing the body if the
+/// request was successful.
+///
+/// An empty body, such as that of a `204 No Content` response, is
+/// deserialized as `null`.
+pub(crate) fn decode<T: DeserializeOwned>(response: HttpResponse) -> Result<T> {
+    if !response.is_success() {
+        return Err(Error::from_response(response));
+    }
+
+    if response.body.is_empty() {
+        return serde_json::from_slice(b"null").map_err(From::from);
+    }
+
+    serde_json::from_slice(&response.body).map_err(From::from)
+}
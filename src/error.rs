@@ -1,16 +1,17 @@
-use serde_json::Error as JsonError;
+use serde_json::{self, Error as JsonError};
 use std::error::Error as StdError;
 use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::io::Error as IoError;
 use std::result::Result as StdResult;
+use std::time::Duration;
+use crate::model::ApiError;
 
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+use crate::transport::HttpResponse;
 #[cfg(feature = "hyper")]
 use hyper::error::{Error as HyperError, UriError};
 #[cfg(feature = "reqwest")]
-use reqwest::{
-    Error as ReqwestError,
-    Response as ReqwestResponse,
-    UrlError as ReqwestUrlError,
-};
+use reqwest::{Error as ReqwestError, UrlError as ReqwestUrlError};
 
 /// A result type to compose a successful value and the library's [`Error`]
 /// type.
@@ -18,97 +19,482 @@ use reqwest::{
 /// [`Error`]: enum.Error.html
 pub type Result<T> = StdResult<T, Error>;
 
-/// An error type to compose a singular error enum between various dependencies'
-/// errors.
+/// An unsuccessful response returned by the API.
+#[derive(Clone, Debug)]
+pub struct ErrorResponse {
+    /// The raw body of the response.
+    pub body: Vec<u8>,
+    /// The errors described by the response, if its body was a JSON:API error
+    /// document.
+    pub errors: Vec<ApiError>,
+    /// The HTTP status code of the response.
+    pub status: u16,
+}
+
+impl ErrorResponse {
+    /// Creates an error response from its status code and raw body, parsing
+    /// the errors out of the body if it is a JSON:API error document or an
+    /// OAuth2 error response.
+    pub fn new(status: u16, body: Vec<u8>) -> Self {
+        let errors = serde_json::from_slice::<ErrorDocument>(&body)
+            .map(|document| document.errors)
+            .or_else(|_| {
+                serde_json::from_slice::<OAuthError>(&body)
+                    .map(|error| vec![error.into()])
+            })
+            .unwrap_or_default();
+
+        ErrorResponse {
+            body,
+            errors,
+            status,
+        }
+    }
+}
+
+impl Display for ErrorResponse {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "Kitsu responded with status {}", self.status)?;
+
+        for error in &self.errors {
+            match (error.title.as_ref(), error.detail.as_ref()) {
+                (Some(title), Some(detail)) => {
+                    write!(f, "; {}: {}", title, detail)?;
+                },
+                (Some(summary), None) | (None, Some(summary)) => {
+                    write!(f, "; {}", summary)?;
+                },
+                (None, None) => {},
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An error type classifying why a request failed, independent of the HTTP
+/// client that was used to make it.
+///
+/// Errors from the underlying HTTP client or deserializer are available
+/// through [`source`].
+///
+/// [`source`]: https://doc.rust-lang.org/std/error/trait.Error.html#method.source
 #[derive(Debug)]
 pub enum Error {
-    /// An error from the `hyper` crate.
-    #[cfg(feature = "hyper")]
-    Hyper(HyperError),
-    /// An error from the `serde_json` crate.
+    /// An error indicating that the response body could not be deserialized.
+    Decode(JsonError),
+    /// An error indicating that the request could not be built, or that the
+    /// API rejected it as invalid.
+    ///
+    /// This is used for any unsuccessful status code that is not covered by
+    /// another variant, such as 400 or 422.
+    InvalidRequest {
+        /// The response, if the request was sent.
+        response: Option<ErrorResponse>,
+        /// The underlying error, if the request could not be built.
+        source: Option<Box<dyn StdError + Send + Sync>>,
+    },
+    /// An error indicating that the requested resource does not exist
+    /// (status code 404).
+    NotFound(ErrorResponse),
+    /// An error indicating that too many requests have been made (status code
+    /// 429).
+    RateLimited {
+        /// The response.
+        response: ErrorResponse,
+        /// How long the API asked to wait before retrying, if it specified a
+        /// number of seconds in the `Retry-After` header.
+        retry_after: Option<Duration>,
+    },
+    /// An error indicating that the API failed to handle the request (status
+    /// code 5xx).
+    Server(ErrorResponse),
+    /// An error indicating that the authentication token could not be loaded
+    /// from or saved to its [`TokenStore`].
     ///
-    /// A potential reason for this is when there is an error deserializing a
-    /// JSON response body.
-    Json(JsonError),
-    /// An error from the `reqwest` crate when it is enabled.
-    #[cfg(feature = "reqwest")]
-    Reqwest(ReqwestError),
-    /// An error indicating a bad request when using `reqwest`.
-    #[cfg(feature = "reqwest")]
-    ReqwestBad(Box<ReqwestResponse>),
-    /// An error indicating an invalid request when using `reqwest`.
-    #[cfg(feature = "reqwest")]
-    ReqwestInvalid(Box<ReqwestResponse>),
-    /// An error indicating a parsing issue when using `reqwest`.
-    #[cfg(feature = "reqwest")]
-    ReqwestParse(ReqwestUrlError),
-    /// An error indicating an unathorized request when using `reqwest`.
-    #[cfg(feature = "reqwest")]
-    ReqwestUnauthorized(Box<ReqwestResponse>),
-    /// An error when building a request's URI from the `hyper` crate when it is
-    /// enabled.
-    #[cfg(feature = "hyper")]
-    Uri(UriError),
+    /// [`TokenStore`]: auth/trait.TokenStore.html
+    TokenStore(IoError),
+    /// An error indicating that the request could not be sent or the response
+    /// could not be received, containing the HTTP client's error.
+    Transport(Box<dyn StdError + Send + Sync>),
+    /// An error indicating that the request was not authorized (status code
+    /// 401 or 403).
+    Unauthorized(ErrorResponse),
+}
+
+impl Error {
+    /// Whether the request may succeed if it is made again.
+    ///
+    /// This is the case for rate limits, transport errors, and server errors
+    /// other than `501 Not Implemented`.
+    pub fn is_retryable(&self) -> bool {
+        match *self {
+            Error::RateLimited { .. } | Error::Transport(_) => true,
+            Error::Server(ref response) => response.status != 501,
+            _ => false,
+        }
+    }
+
+    /// The unsuccessful response returned by the API, if there was one.
+    pub fn response(&self) -> Option<&ErrorResponse> {
+        match *self {
+            Error::InvalidRequest { ref response, .. } => response.as_ref(),
+            Error::NotFound(ref response)
+                | Error::RateLimited { ref response, .. }
+                | Error::Server(ref response)
+                | Error::Unauthorized(ref response) => Some(response),
+            Error::Decode(_)
+                | Error::TokenStore(_)
+                | Error::Transport(_) => None,
+        }
+    }
+
+    /// The HTTP status code of the unsuccessful response, if there was one.
+    #[inline]
+    pub fn status(&self) -> Option<u16> {
+        self.response().map(|response| response.status)
+    }
+
+    /// Creates an error from a non-successful response, classifying it by its
+    /// status code.
+    #[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+    pub(crate) fn from_response(response: HttpResponse) -> Self {
+        let retry_after = response.header("Retry-After")
+            .and_then(parse_retry_after);
+        let response = ErrorResponse::new(response.status, response.body);
+
+        match response.status {
+            401 | 403 => Error::Unauthorized(response),
+            404 => Error::NotFound(response),
+            429 => Error::RateLimited {
+                response,
+                retry_after,
+            },
+            500..=599 => Error::Server(response),
+            _ => Error::InvalidRequest {
+                response: Some(response),
+                source: None,
+            },
+        }
+    }
 }
 
 #[cfg(feature = "hyper")]
 impl From<HyperError> for Error {
     fn from(err: HyperError) -> Self {
-        Error::Hyper(err)
+        Error::Transport(Box::new(err))
     }
 }
 
 impl From<JsonError> for Error {
     fn from(err: JsonError) -> Self {
-        Error::Json(err)
+        Error::Decode(err)
     }
 }
 
 #[cfg(feature = "reqwest")]
 impl From<ReqwestError> for Error {
     fn from(err: ReqwestError) -> Self {
-        Error::Reqwest(err)
+        Error::Transport(Box::new(err))
     }
 }
 
 #[cfg(feature = "reqwest")]
 impl From<ReqwestUrlError> for Error {
     fn from(err: ReqwestUrlError) -> Self {
-        Error::ReqwestParse(err)
+        Error::InvalidRequest {
+            response: None,
+            source: Some(Box::new(err)),
+        }
     }
 }
 
 #[cfg(feature = "hyper")]
 impl From<UriError> for Error {
     fn from(err: UriError) -> Error {
-        Error::Uri(err)
+        Error::InvalidRequest {
+            response: None,
+            source: Some(Box::new(err)),
+        }
     }
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        f.write_str(self.description())
+        match *self {
+            Error::Decode(ref inner) => {
+                write!(f, "Error decoding response body: {}", inner)
+            },
+            Error::InvalidRequest { response: Some(ref response), .. } => {
+                write!(f, "Invalid request: {}", response)
+            },
+            Error::InvalidRequest { source: Some(ref source), .. } => {
+                write!(f, "Invalid request: {}", source)
+            },
+            Error::InvalidRequest { .. } => f.write_str("Invalid request"),
+            Error::NotFound(ref response) => {
+                write!(f, "Resource not found: {}", response)
+            },
+            Error::RateLimited { ref response, retry_after: Some(delay) } => {
+                write!(
+                    f,
+                    "Rate limited, retry after {}s: {}",
+                    delay.as_secs(),
+                    response,
+                )
+            },
+            Error::RateLimited { ref response, .. } => {
+                write!(f, "Rate limited: {}", response)
+            },
+            Error::Server(ref response) => {
+                write!(f, "Server error: {}", response)
+            },
+            Error::TokenStore(ref inner) => {
+                write!(f, "Error accessing token store: {}", inner)
+            },
+            Error::Transport(ref inner) => {
+                write!(f, "Error sending request: {}", inner)
+            },
+            Error::Unauthorized(ref response) => {
+                write!(f, "Request unauthorized: {}", response)
+            },
+        }
     }
 }
 
 impl StdError for Error {
-    fn description(&self) -> &str {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
         match *self {
-            #[cfg(feature = "hyper")]
-            Error::Hyper(ref inner) => inner.description(),
-            Error::Json(ref inner) => inner.description(),
-            #[cfg(feature = "reqwest")]
-            Error::Reqwest(ref inner) => inner.description(),
-            #[cfg(feature = "reqwest")]
-            Error::ReqwestBad(_) => "Request bad",
-            #[cfg(feature = "reqwest")]
-            Error::ReqwestInvalid(_) => "Request invalid",
-            #[cfg(feature = "reqwest")]
-            Error::ReqwestParse(ref inner) => inner.description(),
-            #[cfg(feature = "reqwest")]
-            Error::ReqwestUnauthorized(_) => "Request auth bad",
-            #[cfg(feature = "hyper")]
-            Error::Uri(ref inner) => inner.description(),
+            Error::Decode(ref inner) => Some(inner),
+            Error::TokenStore(ref inner) => Some(inner),
+            Error::InvalidRequest { source: Some(ref inner), .. }
+                | Error::Transport(ref inner) => Some(&**inner),
+            _ => None,
+        }
+    }
+}
+
+/// A JSON:API document containing the errors that caused a request to fail.
+#[derive(Deserialize)]
+struct ErrorDocument {
+    errors: Vec<ApiError>,
+}
+
+/// An error returned by the OAuth2 token endpoint, which does not follow the
+/// JSON:API specification.
+#[derive(Deserialize)]
+struct OAuthError {
+    error: String,
+    error_description: Option<String>,
+}
+
+impl From<OAuthError> for ApiError {
+    fn from(error: OAuthError) -> Self {
+        ApiError {
+            code: Some(error.error.clone()),
+            detail: error.error_description,
+            title: Some(error.error),
+            ..ApiError::default()
         }
     }
 }
+
+/// Parses the value of a `Retry-After` header.
+///
+/// Only the number of seconds form is supported; HTTP dates are ignored.
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+pub(crate) fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.trim().parse().ok().map(Duration::from_secs)
+}
+
+#[cfg(all(
+    test,
+    any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"),
+))]
+mod tests {
+    use super::*;
+
+    fn response(status: u16, headers: &[(&str, &str)], body: &[u8])
+        -> HttpResponse {
+        HttpResponse {
+            body: body.to_vec(),
+            headers: headers.iter()
+                .map(|&(name, value)| (name.to_owned(), value.to_owned()))
+                .collect(),
+            status,
+        }
+    }
+
+    fn classify(status: u16) -> Error {
+        Error::from_response(response(status, &[], b""))
+    }
+
+    #[test]
+    fn test_status_classification() {
+        assert!(matches!(classify(401), Error::Unauthorized(_)));
+        assert!(matches!(classify(403), Error::Unauthorized(_)));
+        assert!(matches!(classify(404), Error::NotFound(_)));
+        assert!(matches!(classify(429), Error::RateLimited { .. }));
+        assert!(matches!(classify(500), Error::Server(_)));
+        assert!(matches!(classify(503), Error::Server(_)));
+        assert!(matches!(classify(599), Error::Server(_)));
+
+        for status in &[400, 405, 409, 422] {
+            match classify(*status) {
+                Error::InvalidRequest { response: Some(response), source: None } => {
+                    assert_eq!(response.status, *status);
+                },
+                other => panic!("{} classified as {:?}", status, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_status_is_kept() {
+        for status in &[401, 404, 422, 429, 502] {
+            assert_eq!(classify(*status).status(), Some(*status));
+        }
+    }
+
+    #[test]
+    fn test_api_errors() {
+        let body = br#"{
+            "errors": [
+                {
+                    "title": "Record not found",
+                    "detail": "The record identified by 0 could not be found.",
+                    "code": "404",
+                    "status": "404"
+                },
+                {
+                    "title": "Invalid field",
+                    "source": { "pointer": "/data/attributes/progress" }
+                }
+            ]
+        }"#;
+        let response = ErrorResponse::new(404, body.to_vec());
+
+        assert_eq!(response.errors.len(), 2);
+        assert_eq!(response.errors[0].code.as_deref(), Some("404"));
+        assert_eq!(response.errors[0].status.as_deref(), Some("404"));
+        assert_eq!(
+            response.errors[1].source.as_ref().and_then(|s| s.pointer.as_deref()),
+            Some("/data/attributes/progress"),
+        );
+        assert_eq!(
+            response.to_string(),
+            "Kitsu responded with status 404; Record not found: The record \
+             identified by 0 could not be found.; Invalid field",
+        );
+    }
+
+    #[test]
+    fn test_oauth_errors() {
+        let body = br#"{
+            "error": "invalid_grant",
+            "error_description": "The provided authorization grant is invalid."
+        }"#;
+        let response = ErrorResponse::new(400, body.to_vec());
+
+        assert_eq!(response.errors.len(), 1);
+        assert_eq!(response.errors[0].code.as_deref(), Some("invalid_grant"));
+        assert_eq!(response.errors[0].title.as_deref(), Some("invalid_grant"));
+        assert_eq!(
+            response.errors[0].detail.as_deref(),
+            Some("The provided authorization grant is invalid."),
+        );
+
+        let terse = ErrorResponse::new(401, br#"{"error": "invalid_token"}"#.to_vec());
+        assert_eq!(terse.errors[0].detail, None);
+        assert_eq!(terse.to_string(), "Kitsu responded with status 401; invalid_token");
+    }
+
+    #[test]
+    fn test_unparseable_errors_keep_body() {
+        let bodies: &[&[u8]] = &[
+            b"",
+            b"<html><body>502 Bad Gateway</body></html>",
+            br#"{"errors": "not a list"}"#,
+            br#"{"errors": [{"title": 5}]}"#,
+            br#"{"error": null}"#,
+            br#"{"errors": [{"title": "Cut off"#,
+        ];
+
+        for body in bodies {
+            let response = ErrorResponse::new(502, body.to_vec());
+
+            assert!(response.errors.is_empty(), "parsed {:?}", response.errors);
+            assert_eq!(response.body, *body);
+            assert_eq!(response.to_string(), "Kitsu responded with status 502");
+        }
+    }
+
+    #[test]
+    fn test_classified_errors_are_parsed() {
+        let body = br#"{"errors": [{"title": "Too many requests"}]}"#;
+
+        match Error::from_response(response(429, &[], body)) {
+            Error::RateLimited { response, .. } => {
+                assert_eq!(response.errors[0].title.as_deref(), Some("Too many requests"));
+            },
+            other => panic!("classified as {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_retry_after() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after(" 0 "), Some(Duration::from_secs(0)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+        assert_eq!(parse_retry_after("-1"), None);
+        assert_eq!(parse_retry_after("1.5"), None);
+        assert_eq!(parse_retry_after(""), None);
+
+        let limited = response(429, &[("retry-after", "30")], b"");
+        match Error::from_response(limited) {
+            Error::RateLimited { retry_after, .. } => {
+                assert_eq!(retry_after, Some(Duration::from_secs(30)));
+            },
+            other => panic!("classified as {:?}", other),
+        }
+
+        let dated = response(429, &[("Retry-After", "Wed, 21 Oct 2015 07:28:00 GMT")], b"");
+        match Error::from_response(dated) {
+            Error::RateLimited { retry_after, .. } => assert_eq!(retry_after, None),
+            other => panic!("classified as {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(classify(429).is_retryable());
+        assert!(classify(500).is_retryable());
+        assert!(classify(503).is_retryable());
+        assert!(!classify(501).is_retryable());
+        assert!(!classify(401).is_retryable());
+        assert!(!classify(404).is_retryable());
+        assert!(!classify(422).is_retryable());
+        assert!(Error::Transport("connection reset".into()).is_retryable());
+
+        let decode = serde_json::from_slice::<u8>(b"x").unwrap_err();
+        assert!(!Error::from(decode).is_retryable());
+    }
+
+    #[test]
+    fn test_errors_without_response() {
+        let transport = Error::Transport("connection reset".into());
+        assert!(transport.response().is_none());
+        assert_eq!(transport.status(), None);
+        assert_eq!(
+            transport.source().map(ToString::to_string).as_deref(),
+            Some("connection reset"),
+        );
+
+        let invalid = Error::InvalidRequest {
+            response: None,
+            source: Some("bad url".into()),
+        };
+        assert_eq!(invalid.status(), None);
+        assert_eq!(invalid.to_string(), "Invalid request: bad url");
+    }
+}
@@ -0,0 +1,44 @@
+//! Replacing files atomically, for the stores keeping their contents in
+//! files.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The number of temporary files created by this process, so that every
+/// write uses a file of its own.
+static TEMPORARY_FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// Replaces the contents of a file by writing them to a temporary file next
+/// to it and renaming that over it, so that partially written contents are
+/// never read.
+///
+/// The temporary file is created with the options, which must create a new
+/// file, and is removed if the contents could not be written or it could not
+/// be renamed.
+pub(crate) fn replace(path: &Path, options: &OpenOptions, contents: &[u8])
+    -> io::Result<()> {
+    let temporary = temporary(path);
+    let mut file = options.open(&temporary)?;
+
+    let replaced = file.write_all(contents)
+        .and_then(|()| file.sync_all())
+        .and_then(|()| fs::rename(&temporary, path));
+
+    if replaced.is_err() {
+        let _ = fs::remove_file(&temporary);
+    }
+
+    replaced
+}
+
+/// A path next to a file that no other write, in this process or another,
+/// uses.
+fn temporary(path: &Path) -> PathBuf {
+    let count = TEMPORARY_FILES.fetch_add(1, Ordering::Relaxed);
+    let mut temporary = path.to_path_buf().into_os_string();
+    temporary.push(format!(".{}.{}.tmp", std::process::id(), count));
+
+    PathBuf::from(temporary)
+}
@@ -1,16 +1,27 @@
 //! # kitsu.rs
 //!
 //! An unofficial Rust library acting as a wrapper around the [Kitsu] API,
-//! offering implementations for both asynchronous hyper (v0.11) and synchronous
-//! reqwest (v0.8).
+//! offering implementations for asynchronous hyper (v0.11), asynchronous
+//! reqwest (v0.12) on `std` futures, and synchronous reqwest (v0.9).
 //!
-//! **note:** The library supports retrieval from the API, but does not currently
-//! support authenticated requests.
+//! Requests can be authenticated with an OAuth2 token, which is obtained and
+//! refreshed automatically; refer to the [`auth`] module. Requests failing
+//! for transient reasons can be retried; refer to the [`retry`] module. The
+//! rate of requests can be limited; refer to the [`rate_limit`] module.
+//! Responses can be cached and revalidated; refer to the [`cache`] module.
+//! Hooks can be run around every request; refer to the [`middleware`]
+//! module.
 //!
 //! ### Compile features
 //!
+//! - **async-support**: Compiles with asynchronous `reqwest` support, for use
+//!   with `std` futures and `async`/`await`
 //! - **hyper-support**: Compiles with `hyper` support
 //! - **reqwest-support**: Compliles with `reqwest` support (*default*)
+//! - **testing**: Compiles the [`testing`] module, providing a mock transport
+//!   for testing without making requests to the API
+//! - **tracing**: Instruments every request with a `tracing` span, recording
+//!   the endpoint, response status and size, decoding time, and retries
 //!
 //! ### Installation
 //!
@@ -66,7 +77,7 @@
 //! let input_trimmed = input.trim();
 //!
 //! // Search for the anime.
-//! let anime = client.search_anime(|f| f.filter("text", input_trimmed))
+//! let anime = client.search_anime(|f| f.text(input_trimmed))
 //!     .expect("Error searching for anime");
 //!
 //! // Print out the response of the request.
@@ -93,6 +104,12 @@
 //! ISC. View the full license [here][license file].
 //!
 //! [Kitsu]: https://kitsu.io
+//! [`auth`]: auth/index.html
+//! [`cache`]: cache/index.html
+//! [`middleware`]: middleware/index.html
+//! [`rate_limit`]: rate_limit/index.html
+//! [`retry`]: retry/index.html
+//! [`testing`]: testing/index.html
 //! [examples]: https://github.com/zeyla/kitsu.rs/blob/master/examples
 //! [license file]: https://github.com/zeyla/kitsu.rs/blob/master/README.md
 #![deny(missing_docs)]
@@ -105,23 +122,53 @@ extern crate serde_json;
 
 #[cfg(feature = "hyper")]
 extern crate futures;
+#[cfg(feature = "reqwest_async")]
+extern crate futures_core;
 #[cfg(feature = "hyper")]
 extern crate hyper;
 #[cfg(feature = "reqwest")]
 extern crate reqwest;
+#[cfg(feature = "reqwest_async")]
+extern crate reqwest_async;
+#[cfg(feature = "reqwest_async")]
+extern crate tokio;
+#[cfg(feature = "hyper")]
+extern crate tokio_timer;
+#[cfg(feature = "tracing")]
+extern crate tracing;
 
+pub mod auth;
 pub mod bridge;
 pub mod builder;
+pub mod cache;
+pub mod client;
+pub mod middleware;
 pub mod model;
+pub mod rate_limit;
+pub mod retry;
+pub mod transport;
+
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+pub mod pagination;
+#[cfg(feature = "testing")]
+pub mod testing;
 
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+mod endpoint;
 mod error;
+mod file;
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+mod trace;
 
-pub use error::{Error, Result};
+pub use client::KitsuClient;
+pub use error::{Error, ErrorResponse, Result};
 
+#[cfg(feature = "reqwest_async")]
+pub use bridge::reqwest_async::KitsuRequester as KitsuAsyncRequester;
 #[cfg(feature = "hyper")]
 pub use bridge::hyper::KitsuRequester as KitsuHyperRequester;
 #[cfg(feature = "reqwest")]
 pub use bridge::reqwest::KitsuRequester as KitsuReqwestRequester;
 
 /// Kitsu API Url
-pub const API_URL: &'static str = "https://kitsu.io/api/edge";
+pub const API_URL: &str = "https://kitsu.io/api/edge";
@@ -0,0 +1,198 @@
+//! Hooks run around every request made through a [`KitsuClient`].
+//!
+//! A [`Middleware`] can change a request before it is sent, answer it
+//! without sending it, and inspect or change the response that is received.
+//! Middleware added to a client form a chain: before a request is sent, each
+//! middleware's [`before`] hook is run in the order they were added; once the
+//! response is received, each [`after`] hook is run in the reverse order.
+//!
+//! If a [`before`] hook returns a response, the request is not sent and the
+//! hooks of the middleware after it are skipped, while the [`after`] hooks
+//! of it and the middleware before it are still run with that response. If
+//! the request fails, or a later middleware's hook returns an error,
+//! [`on_error`] is run instead of [`after`].
+//!
+//! Every bridge runs the middleware once per call of a requester method, and
+//! once per page of the pagination helpers. They run first, with the rest of
+//! the client's configuration applied between them and the transport in this
+//! order: the cache, authentication, retries, and the rate limiter. So the
+//! [`before`] hooks see requests without the `Authorization` header, the
+//! [`after`] hooks see responses served from the cache, and only the last
+//! attempt of a retried request is seen.
+//!
+//! # Examples
+//!
+//! Add a header to every request, and log how long each one took:
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "reqwest")]
+//! # extern crate reqwest;
+//! #
+//! # #[cfg(feature = "reqwest")]
+//! # fn main() {
+//! #
+//! use kitsu::middleware::Middleware;
+//! use kitsu::transport::{HttpRequest, HttpResponse};
+//! use kitsu::{KitsuClient, KitsuReqwestRequester, Result};
+//! use reqwest::Client;
+//! use std::time::Duration;
+//!
+//! #[derive(Debug)]
+//! struct Tracing;
+//!
+//! impl Middleware for Tracing {
+//!     fn before(&self, request: &mut HttpRequest)
+//!         -> Result<Option<HttpResponse>> {
+//!         request.headers.push(("X-Trace-Id".to_owned(), "abc".to_owned()));
+//!
+//!         Ok(None)
+//!     }
+//!
+//!     fn after(
+//!         &self,
+//!         request: &HttpRequest,
+//!         response: &mut HttpResponse,
+//!         elapsed: Duration,
+//!     ) -> Result<()> {
+//!         println!("{} {}: {} in {:?}", request.method, request.url, response.status, elapsed);
+//!
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let client = KitsuClient::new(Client::new()).middleware(Tracing);
+//!
+//! let anime = client.get_anime(1).expect("Error getting anime");
+//! # }
+//! #
+//! # #[cfg(not(feature = "reqwest"))]
+//! # fn main() { }
+//! ```
+//!
+//! [`KitsuClient`]: ../client/struct.KitsuClient.html
+//! [`Middleware`]: trait.Middleware.html
+//! [`after`]: trait.Middleware.html#method.after
+//! [`before`]: trait.Middleware.html#method.before
+//! [`on_error`]: trait.Middleware.html#method.on_error
+
+use std::fmt::Debug;
+use std::time::Duration;
+use crate::transport::{HttpRequest, HttpResponse};
+use crate::{Error, Result};
+
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+use std::sync::Arc;
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+use std::time::Instant;
+
+/// Hooks run before a request is sent and after its response is received.
+///
+/// Every hook does nothing by default.
+pub trait Middleware: Debug + Send + Sync {
+    /// Runs before a request is sent, and may change it.
+    ///
+    /// Returning a response answers the request with it instead of sending
+    /// it.
+    ///
+    /// # Errors
+    ///
+    /// Returning an error fails the request with it, without sending it.
+    fn before(&self, request: &mut HttpRequest)
+        -> Result<Option<HttpResponse>> {
+        let _ = request;
+
+        Ok(None)
+    }
+
+    /// Runs after the response to a request is received, and may change it.
+    ///
+    /// The response has not been checked for an unsuccessful status code
+    /// yet. The elapsed time is measured from when the first middleware's
+    /// [`before`] hook was run.
+    ///
+    /// # Errors
+    ///
+    /// Returning an error fails the request with it.
+    ///
+    /// [`before`]: #method.before
+    fn after(
+        &self,
+        request: &HttpRequest,
+        response: &mut HttpResponse,
+        elapsed: Duration,
+    ) -> Result<()> {
+        let _ = (request, response, elapsed);
+
+        Ok(())
+    }
+
+    /// Runs instead of [`after`] when the request failed without a
+    /// response, including when a hook of a middleware added after this one
+    /// returned an error.
+    ///
+    /// [`after`]: #method.after
+    fn on_error(&self, request: &HttpRequest, error: &Error) {
+        let _ = (request, error);
+    }
+}
+
+/// The state of a request passing through a client's middleware.
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+pub(crate) struct Chain {
+    entered: usize,
+    middleware: Vec<Arc<dyn Middleware>>,
+    started: Instant,
+}
+
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+impl Chain {
+    pub(crate) fn new(middleware: &[Arc<dyn Middleware>]) -> Self {
+        Chain {
+            entered: 0,
+            middleware: middleware.to_vec(),
+            started: Instant::now(),
+        }
+    }
+
+    /// Runs the `before` hooks, stopping at the first one that returns a
+    /// response or an error.
+    pub(crate) fn before(&mut self, request: &mut HttpRequest)
+        -> Result<Option<HttpResponse>> {
+        self.started = Instant::now();
+
+        for middleware in &self.middleware {
+            let response = middleware.before(request)?;
+            self.entered += 1;
+
+            if response.is_some() {
+                return Ok(response);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Runs the `after` or `on_error` hooks of the middleware whose `before`
+    /// hooks succeeded, in reverse order.
+    pub(crate) fn after(
+        &self,
+        request: &HttpRequest,
+        outcome: Result<HttpResponse>,
+    ) -> Result<HttpResponse> {
+        let elapsed = self.started.elapsed();
+
+        self.middleware[..self.entered]
+            .iter()
+            .rev()
+            .fold(outcome, |outcome, middleware| match outcome {
+                Ok(mut response) => middleware
+                    .after(request, &mut response, elapsed)
+                    .map(|()| response),
+                Err(why) => {
+                    middleware.on_error(request, &why);
+
+                    Err(why)
+                },
+            })
+    }
+}
@@ -1,8 +1,108 @@
 //! Models in struct form, parsed out from JSON in response bodies.
 
-use serde_json;
+use serde::de::{Deserialize, DeserializeOwned};
+use serde_json::{Map, Value};
 use std::collections::HashMap;
-use ::Result;
+use std::marker::PhantomData;
+use crate::Result;
+
+/// An error returned by the API when a request was rejected, as described by
+/// the [JSON:API specification].
+///
+/// [JSON:API specification]: http://jsonapi.org/format/#error-objects
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ApiError {
+    /// An application-specific error code.
+    ///
+    /// # Examples
+    ///
+    /// `404`
+    pub code: Option<String>,
+    /// A human-readable explanation specific to this occurrence of the
+    /// problem.
+    ///
+    /// # Examples
+    ///
+    /// `The record identified by 0 could not be found.`
+    pub detail: Option<String>,
+    /// A reference to the part of the request that caused the error.
+    pub source: Option<ApiErrorSource>,
+    /// The HTTP status code applicable to the problem.
+    ///
+    /// # Examples
+    ///
+    /// `404`
+    pub status: Option<String>,
+    /// A short, human-readable summary of the problem.
+    ///
+    /// # Examples
+    ///
+    /// `Record not found`
+    pub title: Option<String>,
+}
+
+/// A reference to the part of a request that caused an [`ApiError`].
+///
+/// [`ApiError`]: struct.ApiError.html
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ApiErrorSource {
+    /// The name of the query parameter that caused the error.
+    ///
+    /// # Examples
+    ///
+    /// `filter`
+    pub parameter: Option<String>,
+    /// A JSON Pointer to the value in the request document that caused the
+    /// error.
+    ///
+    /// # Examples
+    ///
+    /// `/data/attributes/title`
+    pub pointer: Option<String>,
+}
+
+/// Information about a genre.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Genre {
+    /// Information about the genre.
+    pub attributes: GenreAttributes,
+    /// The id of the genre.
+    pub id: String,
+    /// The type of item this is. Should always be [`Type::Genre`].
+    ///
+    /// [`Type::Genre`]: enum.Type.html#variant.Genre
+    #[serde(rename="type")]
+    pub kind: Type,
+    /// Links related to the genre.
+    #[serde(default)]
+    pub links: HashMap<String, String>,
+}
+
+/// Information about a [`Genre`].
+///
+/// [`Genre`]: struct.Genre.html
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenreAttributes {
+    /// When the genre was created.
+    pub created_at: String,
+    /// A description of the genre.
+    pub description: Option<String>,
+    /// The name of the genre.
+    ///
+    /// # Examples
+    ///
+    /// `Comedy`
+    pub name: String,
+    /// The genre's slug.
+    ///
+    /// # Examples
+    ///
+    /// `comedy`
+    pub slug: String,
+    /// When the genre was last updated.
+    pub updated_at: String,
+}
 
 /// Information about a character.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -18,6 +118,9 @@ pub struct Character {
     pub kind: Type,
     /// Links related to the anime.
     pub links: HashMap<String, String>,
+    /// List of the character's relationships.
+    #[serde(default)]
+    pub relationships: CharacterRelationships,
 }
 
 /// Information about a [`Character`].
@@ -42,6 +145,21 @@ pub struct CharacterAttributes {
     pub updated_at: String,
 }
 
+/// Relationships for a [`Character`].
+///
+/// Relationships that were not returned, such as when requesting a sparse
+/// fieldset without them, are `None`.
+///
+/// [`Character`]: struct.Character.html
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterRelationships {
+    /// Castings of the character.
+    pub castings: Option<Relationship>,
+    /// The anime or manga that the character primarily appears in.
+    pub primary_media: Option<Relationship>,
+}
+
 /// Information about a producer
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -57,6 +175,9 @@ pub struct Producer {
     pub kind: Type,
     /// Links to the related anime-productions.
     pub links: HashMap<String, String>,
+    /// List of the producer's relationships.
+    #[serde(default)]
+    pub relationships: ProducerRelationships,
 }
 
 /// Information about a [`Producer`].
@@ -75,6 +196,19 @@ pub struct ProducerAttributes {
     pub updated_at: String,
 }
 
+/// Relationships for a [`Producer`].
+///
+/// Relationships that were not returned, such as when requesting a sparse
+/// fieldset without them, are `None`.
+///
+/// [`Producer`]: struct.Producer.html
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProducerRelationships {
+    /// The producer's productions of anime.
+    pub anime_productions: Option<Relationship>,
+}
+
 /// Information about an anime.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Anime {
@@ -90,6 +224,7 @@ pub struct Anime {
     /// Links related to the anime.
     pub links: HashMap<String, String>,
     /// List of the anime's relationships.
+    #[serde(default)]
     pub relationships: AnimeRelationships,
 }
 
@@ -299,6 +434,117 @@ impl AnimeAttributes {
     }
 }
 
+/// An entry in a [`User`]'s library, tracking their progress through an
+/// [`Anime`] or [`Manga`].
+///
+/// [`Anime`]: struct.Anime.html
+/// [`Manga`]: struct.Manga.html
+/// [`User`]: struct.User.html
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LibraryEntry {
+    /// Information about the library entry.
+    pub attributes: LibraryEntryAttributes,
+    /// The id of the library entry.
+    pub id: String,
+    /// The type of item this is. Should always be [`Type::LibraryEntry`].
+    ///
+    /// [`Type::LibraryEntry`]: enum.Type.html#variant.LibraryEntry
+    #[serde(rename="type")]
+    pub kind: Type,
+    /// Links related to the library entry.
+    #[serde(default)]
+    pub links: HashMap<String, String>,
+    /// List of the library entry's relationships.
+    #[serde(default)]
+    pub relationships: LibraryEntryRelationships,
+}
+
+/// Information about a [`LibraryEntry`].
+///
+/// [`LibraryEntry`]: struct.LibraryEntry.html
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all="camelCase")]
+pub struct LibraryEntryAttributes {
+    /// When the library entry was created.
+    ///
+    /// # Examples
+    ///
+    /// `2017-08-08T14:34:28.367Z`
+    pub created_at: String,
+    /// When the user finished the media, if they have.
+    pub finished_at: Option<String>,
+    /// The user's notes about the media.
+    pub notes: Option<String>,
+    /// Whether the library entry is only visible to the user.
+    pub private: bool,
+    /// The number of episodes or chapters the user has consumed.
+    ///
+    /// # Examples
+    ///
+    /// `13`
+    pub progress: u32,
+    /// When the user last made progress.
+    pub progressed_at: Option<String>,
+    /// The user's rating of the media, out of twenty, in steps of one.
+    ///
+    /// A rating of `20` is displayed as 10/10 or 5 stars, depending on the
+    /// user's rating system.
+    ///
+    /// # Examples
+    ///
+    /// `14`
+    pub rating_twenty: Option<u8>,
+    /// The number of times the user has rewatched or reread the media.
+    pub reconsume_count: u32,
+    /// Whether the user is currently rewatching or rereading the media.
+    pub reconsuming: bool,
+    /// When the user started the media, if they have.
+    pub started_at: Option<String>,
+    /// The status of the library entry.
+    pub status: LibraryEntryStatus,
+    /// When the library entry was last updated.
+    ///
+    /// # Examples
+    ///
+    /// `2017-08-08T14:34:28.367Z`
+    pub updated_at: String,
+    /// The number of volumes of the manga the user owns.
+    pub volumes_owned: u32,
+}
+
+/// Relationships for a [`LibraryEntry`].
+///
+/// Library entries returned by older versions of the API may not have
+/// relationships, in which case every relationship is `None`.
+///
+/// [`LibraryEntry`]: struct.LibraryEntry.html
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LibraryEntryRelationships {
+    /// The anime that the entry tracks, if it tracks an anime.
+    pub anime: Option<Relationship>,
+    /// The manga that the entry tracks, if it tracks a manga.
+    pub manga: Option<Relationship>,
+    /// The anime or manga that the entry tracks.
+    pub media: Option<Relationship>,
+    /// The user whose library the entry is in.
+    pub user: Option<Relationship>,
+}
+
+/// The media that a new [`LibraryEntry`] tracks.
+///
+/// [`LibraryEntry`]: struct.LibraryEntry.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LibraryMedia {
+    /// An [`Anime`], using its id.
+    ///
+    /// [`Anime`]: struct.Anime.html
+    Anime(u64),
+    /// A [`Manga`], using its id.
+    ///
+    /// [`Manga`]: struct.Manga.html
+    Manga(u64),
+}
+
 /// Links related to the media item.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Links {
@@ -310,35 +556,381 @@ pub struct Links {
 }
 
 /// A relationship for a media item.
+///
+/// The related resources can be requested by passing the relationship to a
+/// requester's `fetch_related` method, or resolved from the resources
+/// included in a response with [`resolve`].
+///
+/// [`resolve`]: #method.resolve
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Relationship {
+    /// The identifiers of the related resources.
+    ///
+    /// This is only present if the related resources were included in the
+    /// response, or if the API always returns them for the relationship.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<RelationshipData>,
     /// Links for one set of the media item's related links.
     pub links: Links,
 }
 
+impl Relationship {
+    /// The identifier of the related resource of a to-one relationship.
+    ///
+    /// Returns `None` if the relationship is to-many, or the identifier was
+    /// not returned.
+    pub fn identifier(&self) -> Option<&ResourceIdentifier> {
+        match self.data {
+            Some(RelationshipData::One(ref identifier)) => Some(identifier),
+            _ => None,
+        }
+    }
+
+    /// The identifiers of the related resources, which is empty if they were
+    /// not returned.
+    pub fn identifiers(&self) -> &[ResourceIdentifier] {
+        match self.data {
+            Some(RelationshipData::Many(ref identifiers)) => identifiers,
+            Some(RelationshipData::One(ref identifier)) => {
+                std::slice::from_ref(identifier)
+            },
+            None => &[],
+        }
+    }
+
+    /// Resolves the related resources of type `T` from the resources
+    /// included in a response.
+    ///
+    /// The related resources must have been included in the response by
+    /// passing the relationship's name to [`Search::include`]. Related
+    /// resources of a different type, or that were not included, are
+    /// skipped.
+    ///
+    /// # Examples
+    ///
+    /// Get an anime along with its genres:
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "reqwest")]
+    /// # extern crate reqwest;
+    /// #
+    /// # #[cfg(feature = "reqwest")]
+    /// # fn main() {
+    /// #
+    /// use kitsu::model::{Anime, Genre};
+    /// use kitsu::KitsuReqwestRequester;
+    /// use reqwest::Client;
+    ///
+    /// let client = Client::new();
+    ///
+    /// let response = client.get_resource::<Anime, _>(1, |f| f.include("genres"))
+    ///     .expect("Error getting anime");
+    /// let genres: Vec<Genre> = response.data.relationships.genres.as_ref()
+    ///     .expect("No genres relationship")
+    ///     .resolve(&response)
+    ///     .expect("Error decoding genres");
+    ///
+    /// for genre in genres {
+    ///     println!("{}", genre.attributes.name);
+    /// }
+    /// # }
+    /// #
+    /// # #[cfg(not(feature = "reqwest"))]
+    /// # fn main() { }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Decode`] if an included resource could not be
+    /// deserialized as `T`.
+    ///
+    /// [`Error::Decode`]: ../enum.Error.html#variant.Decode
+    /// [`Search::include`]: ../builder/struct.Search.html#method.include
+    pub fn resolve<T: Resource, U>(&self, response: &Response<U>)
+        -> Result<Vec<T>> {
+        let mut resources = Vec::new();
+
+        for identifier in self.identifiers() {
+            if identifier.kind != T::TYPE {
+                continue;
+            }
+
+            if let Some(resource) = response.included.get(&identifier.id)? {
+                resources.push(resource);
+            }
+        }
+
+        Ok(resources)
+    }
+}
+
+/// The identifiers of the resources in a [`Relationship`].
+///
+/// [`Relationship`]: struct.Relationship.html
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RelationshipData {
+    /// The identifiers of the resources in a to-many relationship.
+    Many(Vec<ResourceIdentifier>),
+    /// The identifier of the resource in a to-one relationship.
+    One(ResourceIdentifier),
+}
+
+/// Identifies a resource by its type and id.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct ResourceIdentifier {
+    /// The id of the resource.
+    pub id: String,
+    /// The type of the resource, such as `anime` or `genres`.
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+/// A model of a type of resource from the API.
+pub trait Resource: DeserializeOwned {
+    /// The type of the resource in JSON:API documents.
+    const TYPE: &'static str;
+
+    /// The path of the collection of the resources, relative to the API's
+    /// base URL.
+    const PATH: &'static str;
+
+    /// The id of the resource.
+    fn id(&self) -> &str;
+}
+
+impl Resource for Anime {
+    const TYPE: &'static str = "anime";
+    const PATH: &'static str = "/anime";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Resource for Character {
+    const TYPE: &'static str = "characters";
+    const PATH: &'static str = "/characters";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Resource for Genre {
+    const TYPE: &'static str = "genres";
+    const PATH: &'static str = "/genres";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Resource for LibraryEntry {
+    const TYPE: &'static str = "libraryEntries";
+    const PATH: &'static str = "/library-entries";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Resource for Manga {
+    const TYPE: &'static str = "manga";
+    const PATH: &'static str = "/manga";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Resource for Producer {
+    const TYPE: &'static str = "producers";
+    const PATH: &'static str = "/producers";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Resource for User {
+    const TYPE: &'static str = "users";
+    const PATH: &'static str = "/users";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+/// A resource of type `T` that may be missing some of its attributes or
+/// relationships, such as those requested with a sparse fieldset through
+/// [`Search::fields`].
+///
+/// Attributes are named as they are in the API, such as `canonicalTitle`.
+///
+/// # Examples
+///
+/// Search for anime, only requesting their titles:
+///
+/// ```rust,no_run
+/// # #[cfg(feature = "reqwest")]
+/// # extern crate reqwest;
+/// #
+/// # #[cfg(feature = "reqwest")]
+/// # fn main() {
+/// #
+/// use kitsu::model::{Anime, Partial};
+/// use kitsu::KitsuReqwestRequester;
+/// use reqwest::Client;
+///
+/// let client = Client::new();
+///
+/// let anime = client.search_resources::<Partial<Anime>, _>(|f| f
+///     .filter("text", "bebop")
+///     .fields("anime", &["canonicalTitle"]))
+///     .expect("Error searching for anime");
+///
+/// for anime in anime.data {
+///     let title: Option<String> = anime.attribute("canonicalTitle")
+///         .expect("Error decoding title");
+///
+///     println!("{}: {:?}", anime.id, title);
+/// }
+/// # }
+/// #
+/// # #[cfg(not(feature = "reqwest"))]
+/// # fn main() { }
+/// ```
+///
+/// [`Search::fields`]: ../builder/struct.Search.html#method.fields
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Partial<T> {
+    /// The attributes of the resource that were returned.
+    #[serde(default)]
+    pub attributes: Map<String, Value>,
+    /// The id of the resource.
+    pub id: String,
+    /// The type of the resource.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Links related to the resource.
+    #[serde(default)]
+    pub links: HashMap<String, String>,
+    /// The relationships of the resource that were returned, by name.
+    #[serde(default)]
+    pub relationships: HashMap<String, Relationship>,
+    #[serde(skip)]
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Partial<T> {
+    /// Deserializes an attribute, returning `None` if it was not returned or
+    /// is `null`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Decode`] if the attribute could not be deserialized
+    /// as `V`.
+    ///
+    /// [`Error::Decode`]: ../enum.Error.html#variant.Decode
+    pub fn attribute<V: DeserializeOwned>(&self, name: &str) -> Result<Option<V>> {
+        match self.attributes.get(name) {
+            Some(value) => Option::<V>::deserialize(value).map_err(From::from),
+            None => Ok(None),
+        }
+    }
+
+    /// Whether an attribute was returned.
+    #[inline]
+    pub fn has_attribute(&self, name: &str) -> bool {
+        self.attributes.contains_key(name)
+    }
+}
+
+impl<T: Resource> Partial<T> {
+    /// Deserializes the resource into its full model.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Decode`] if attributes required by the model are
+    /// missing.
+    ///
+    /// [`Error::Decode`]: ../enum.Error.html#variant.Decode
+    pub fn into_full(self) -> Result<T> {
+        let value = serde_json::to_value(self)?;
+
+        T::deserialize(value).map_err(From::from)
+    }
+}
+
+impl<T: Resource> Resource for Partial<T> {
+    const TYPE: &'static str = T::TYPE;
+    const PATH: &'static str = T::PATH;
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
 /// Relationships for an [`Anime`].
 ///
+/// Relationships that were not returned, such as when requesting a sparse
+/// fieldset without them, are `None`.
+///
 /// [`Anime`]: struct.Anime.html
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct AnimeRelationships {
     /// Productions for the anime
     #[serde(rename="animeProductions")]
-    pub anime_productions: Relationship,
+    pub anime_productions: Option<Relationship>,
     /// Castings for the anime.
-    pub castings: Relationship,
+    pub castings: Option<Relationship>,
     /// The anime's episodes.
-    pub episodes: Relationship,
+    pub episodes: Option<Relationship>,
     /// The anime's genres.
-    pub genres: Relationship,
+    pub genres: Option<Relationship>,
     /// The anime's installments.
-    pub installments: Relationship,
+    pub installments: Option<Relationship>,
     /// The anime's mappings.
-    pub mappings: Relationship,
+    pub mappings: Option<Relationship>,
     /// The anime's reviews.
-    pub reviews: Relationship,
+    pub reviews: Option<Relationship>,
     /// The anime's streaming links.
     #[serde(rename="streamingLinks")]
-    pub streaming_links: Relationship,
+    pub streaming_links: Option<Relationship>,
+}
+
+/// Relationships for a [`Manga`].
+///
+/// Relationships that were not returned, such as when requesting a sparse
+/// fieldset without them, are `None`.
+///
+/// [`Manga`]: struct.Manga.html
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MangaRelationships {
+    /// Castings for the manga.
+    pub castings: Option<Relationship>,
+    /// The manga's categories.
+    pub categories: Option<Relationship>,
+    /// The manga's chapters.
+    pub chapters: Option<Relationship>,
+    /// The manga's genres.
+    pub genres: Option<Relationship>,
+    /// The manga's installments.
+    pub installments: Option<Relationship>,
+    /// The characters appearing in the manga.
+    pub manga_characters: Option<Relationship>,
+    /// The staff who worked on the manga.
+    pub manga_staff: Option<Relationship>,
+    /// The manga's mappings.
+    pub mappings: Option<Relationship>,
+    /// Anime and manga related to the manga, such as sequels and
+    /// adaptations.
+    pub media_relationships: Option<Relationship>,
+    /// The manga's reviews.
+    pub reviews: Option<Relationship>,
 }
 
 /// Information about the cover image for a media item.
@@ -359,7 +951,7 @@ impl CoverImage {
     /// This places priority on the [`original`] field.
     ///
     /// [`original`]: #structfield.original
-    pub fn largest<'a>(&'a self) -> Option<&'a String> {
+    pub fn largest(&self) -> Option<&String> {
         self.original.as_ref().or(self.large.as_ref()).or(self.small.as_ref())
     }
 }
@@ -386,7 +978,7 @@ impl Image {
     /// This places priority on the [`original`] field.
     ///
     /// [`original`]: #structfield.original
-    pub fn largest<'a>(&'a self) -> Option<&'a String> {
+    pub fn largest(&self) -> Option<&String> {
         self.original.as_ref()
             .or(self.large.as_ref())
             .or(self.medium.as_ref())
@@ -409,6 +1001,9 @@ pub struct Manga {
     pub kind: Type,
     /// Links related to the manga.
     pub links: HashMap<String, String>,
+    /// List of the manga's relationships.
+    #[serde(default)]
+    pub relationships: MangaRelationships,
 }
 
 impl Manga {
@@ -649,9 +1244,145 @@ pub struct MangaTitles {
 pub struct Response<T> {
     /// The full data from a response.
     pub data: T,
+    /// Resources related to the data, included by passing the names of
+    /// their relationships to [`Search::include`].
+    ///
+    /// [`Search::include`]: ../builder/struct.Search.html#method.include
+    #[serde(default, skip_serializing_if = "Included::is_empty")]
+    pub included: Included,
     /// Links relevant to the search.
     #[serde(default)]
     pub links: HashMap<String, String>,
+    /// Information about the response that is not part of its data, such as
+    /// the total number of results of a search.
+    #[serde(default, skip_serializing_if = "Meta::is_empty")]
+    pub meta: Meta,
+}
+
+impl<T> Response<T> {
+    /// The total number of results of a search, across every page.
+    ///
+    /// Refer to [`Meta::count`].
+    ///
+    /// [`Meta::count`]: struct.Meta.html#structfield.count
+    #[inline]
+    pub fn total_count(&self) -> Option<u64> {
+        self.meta.count
+    }
+
+    /// The total number of pages of a search when requesting `limit` results
+    /// per page.
+    ///
+    /// Returns `None` if the response has no total count, or if `limit` is
+    /// 0.
+    ///
+    /// # Examples
+    ///
+    /// Print the progress of a search through its pages:
+    ///
+    /// ```rust,no_run
+    /// # use kitsu::model::{Anime, Response};
+    /// #
+    /// # fn print(page: Response<Vec<Anime>>) {
+    /// if let Some(pages) = page.total_pages(20) {
+    ///     println!("Received page 1 of {}", pages);
+    /// }
+    /// # }
+    /// ```
+    pub fn total_pages(&self, limit: u64) -> Option<u64> {
+        let count = self.meta.count?;
+
+        if limit == 0 {
+            return None;
+        }
+
+        Some(count.div_ceil(limit))
+    }
+}
+
+/// The resources included in a [`Response`], of any type.
+///
+/// Resources are deserialized into their models when they are retrieved,
+/// so resources of types without a model are kept without failing the
+/// response.
+///
+/// [`Response`]: struct.Response.html
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Included(Vec<Value>);
+
+impl Included {
+    /// Retrieves the included resource of type `T` with an id.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Decode`] if the resource could not be deserialized
+    /// as `T`.
+    ///
+    /// [`Error::Decode`]: ../enum.Error.html#variant.Decode
+    pub fn get<T: Resource>(&self, id: &str) -> Result<Option<T>> {
+        self.0.iter()
+            .find(|resource| is_resource(resource, T::TYPE, Some(id)))
+            .map(|resource| T::deserialize(resource).map_err(From::from))
+            .transpose()
+    }
+
+    /// Retrieves every included resource of type `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Decode`] if a resource could not be deserialized as
+    /// `T`.
+    ///
+    /// [`Error::Decode`]: ../enum.Error.html#variant.Decode
+    pub fn all<T: Resource>(&self) -> Result<Vec<T>> {
+        self.0.iter()
+            .filter(|resource| is_resource(resource, T::TYPE, None))
+            .map(|resource| T::deserialize(resource).map_err(From::from))
+            .collect()
+    }
+
+    /// The identifiers of every included resource.
+    pub fn identifiers(&self) -> Vec<ResourceIdentifier> {
+        self.0.iter()
+            .filter_map(|resource| ResourceIdentifier::deserialize(resource).ok())
+            .collect()
+    }
+
+    /// Whether no resources were included.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The number of included resources.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Information about a [`Response`] that is not part of its data.
+///
+/// [`Response`]: struct.Response.html
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Meta {
+    /// The total number of results of a search, across every page.
+    ///
+    /// This is only present on responses with a collection of resources.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+    /// Any other information, keyed by name.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Meta {
+    /// Whether the response had no meta information.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.count.is_none() && self.extra.is_empty()
+    }
 }
 
 /// Information about a user.
@@ -669,6 +1400,7 @@ pub struct User {
     /// Links related to the user.
     pub links: HashMap<String, String>,
     /// List of the user's relationships.
+    #[serde(default)]
     pub relationships: UserRelationships,
 }
 
@@ -851,32 +1583,35 @@ impl UserAttributes {
 
 /// Relationships for a [`User`].
 ///
+/// Relationships that were not returned, such as when requesting a sparse
+/// fieldset without them, are `None`.
+///
 /// [`User`]: struct.User.html
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all="camelCase")]
 pub struct UserRelationships {
     /// Links to users the user blocks.
-    pub blocks: Relationship,
+    pub blocks: Option<Relationship>,
     /// Links to the user's favorite media entries.
-    pub favorites: Relationship,
+    pub favorites: Option<Relationship>,
     /// Links to users following the user.
-    pub followers: Relationship,
+    pub followers: Option<Relationship>,
     /// Links to users the user follows.
-    pub following: Relationship,
+    pub following: Option<Relationship>,
     /// Links to the user's library entries.
-    pub library_entries: Relationship,
+    pub library_entries: Option<Relationship>,
     /// Links to profiles linked to the user.
     pub linked_profiles: Option<Relationship>,
     /// Links to the user's media.
     pub media_follows: Option<Relationship>,
     /// Links to the user's pinned post on their profile.
-    pub pinned_post: Relationship,
+    pub pinned_post: Option<Relationship>,
     /// Links to the user's reviews.
-    pub reviews: Relationship,
+    pub reviews: Option<Relationship>,
     /// Links to the user's roles.
-    pub user_roles: Relationship,
+    pub user_roles: Option<Relationship>,
     /// Links to the user's waifu or husbando.
-    pub waifu: Relationship,
+    pub waifu: Option<Relationship>,
 }
 
 /// The age rating of the [`Anime`].
@@ -919,13 +1654,30 @@ impl AgeRating {
     /// assert_eq!(AgeRating::PG.name().unwrap(), "PG");
     /// ```
     pub fn name(&self) -> Result<String> {
-        let mut name = serde_json::to_string(self)?;
-
-        // Serde wraps the encoded string in quotation marks, so remove those.
-        let _ = name.remove(0);
-        let _ = name.pop();
+        Ok(self.as_str().to_owned())
+    }
 
-        Ok(name)
+    /// The name of the age rating, as the API spells it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kitsu::model::AgeRating;
+    ///
+    /// assert_eq!(AgeRating::R17Plus.as_str(), "R17+");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            AgeRating::G => "G",
+            AgeRating::PG => "PG",
+            AgeRating::PG13 => "PG-13",
+            AgeRating::R => "R",
+            AgeRating::R17 => "R17",
+            AgeRating::R17Plus => "R17+",
+            AgeRating::R18 => "R18",
+            AgeRating::R18Plus => "R18+",
+            AgeRating::TvY7 => "TV-Y7",
+        }
     }
 }
 
@@ -942,6 +1694,11 @@ pub enum AiringStatus {
 impl AiringStatus {
     /// The name of the airing status.
     pub fn name(&self) -> &str {
+        self.as_str()
+    }
+
+    /// The name of the airing status, as the API spells it.
+    pub fn as_str(&self) -> &'static str {
         match *self {
             AiringStatus::Airing => "airing",
             AiringStatus::Finished => "finished",
@@ -949,6 +1706,55 @@ impl AiringStatus {
     }
 }
 
+/// The season in which an [`Anime`] started airing.
+///
+/// [`Anime`]: struct.Anime.html
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all="lowercase")]
+pub enum Season {
+    /// Indicator that the anime started airing in the fall.
+    Fall,
+    /// Indicator that the anime started airing in the spring.
+    Spring,
+    /// Indicator that the anime started airing in the summer.
+    Summer,
+    /// Indicator that the anime started airing in the winter.
+    Winter,
+}
+
+impl Season {
+    /// The name of the season.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kitsu::model::Season;
+    ///
+    /// assert_eq!(Season::Winter.name(), "winter");
+    /// ```
+    pub fn name(&self) -> &str {
+        self.as_str()
+    }
+
+    /// The name of the season, as the API spells it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kitsu::model::Season;
+    ///
+    /// assert_eq!(Season::Fall.as_str(), "fall");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Season::Fall => "fall",
+            Season::Spring => "spring",
+            Season::Summer => "summer",
+            Season::Winter => "winter",
+        }
+    }
+}
+
 /// The status of [`Anime`] and [`Manga`]
 ///
 /// [`Anime`]: struct.Anime.html
@@ -1008,12 +1814,79 @@ impl AnimeType {
     ///
     /// [`Anime`]: struct.Anime.html
     pub fn name(&self) -> Result<String> {
-        let mut name = serde_json::to_string(self)?;
+        Ok(self.as_str().to_owned())
+    }
+
+    /// The name of the anime type, as the API spells it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kitsu::model::AnimeType;
+    ///
+    /// assert_eq!(AnimeType::ONA.as_str(), "ONA");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            AnimeType::Movie => "movie",
+            AnimeType::Music => "music",
+            AnimeType::ONA => "ONA",
+            AnimeType::OVA => "OVA",
+            AnimeType::Special => "special",
+            AnimeType::TV => "TV",
+        }
+    }
+}
+
+/// The status of a [`LibraryEntry`].
+///
+/// [`LibraryEntry`]: struct.LibraryEntry.html
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all="snake_case")]
+pub enum LibraryEntryStatus {
+    /// Indicator that the user has finished the media.
+    Completed,
+    /// Indicator that the user is watching or reading the media.
+    Current,
+    /// Indicator that the user has stopped watching or reading the media.
+    Dropped,
+    /// Indicator that the user has paused watching or reading the media.
+    OnHold,
+    /// Indicator that the user plans to watch or read the media.
+    Planned,
+}
 
-        let _ = name.remove(0);
-        let _ = name.pop();
+impl LibraryEntryStatus {
+    /// The name of the library entry status.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kitsu::model::LibraryEntryStatus;
+    ///
+    /// assert_eq!(LibraryEntryStatus::OnHold.name(), "on_hold");
+    /// ```
+    pub fn name(&self) -> &str {
+        self.as_str()
+    }
 
-        Ok(name)
+    /// The name of the library entry status, as the API spells it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kitsu::model::LibraryEntryStatus;
+    ///
+    /// assert_eq!(LibraryEntryStatus::Planned.as_str(), "planned");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            LibraryEntryStatus::Completed => "completed",
+            LibraryEntryStatus::Current => "current",
+            LibraryEntryStatus::Dropped => "dropped",
+            LibraryEntryStatus::OnHold => "on_hold",
+            LibraryEntryStatus::Planned => "planned",
+        }
     }
 }
 
@@ -1050,12 +1923,28 @@ impl MangaType {
     /// assert_eq!(MangaType::Novel.name().unwrap(), "novel");
     /// ```
     pub fn name(&self) -> Result<String> {
-        let mut name = serde_json::to_string(self)?;
-
-        let _ = name.remove(0);
-        let _ = name.pop();
+        Ok(self.as_str().to_owned())
+    }
 
-        Ok(name)
+    /// The name of the manga type, as the API spells it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kitsu::model::MangaType;
+    ///
+    /// assert_eq!(MangaType::OEL.as_str(), "oel");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            MangaType::Doujin => "doujin",
+            MangaType::Manga => "manga",
+            MangaType::Manhua => "manhua",
+            MangaType::Manhwa => "manhwa",
+            MangaType::Novel => "novel",
+            MangaType::OEL => "oel",
+            MangaType::Oneshot => "oneshot",
+        }
     }
 }
 
@@ -1074,6 +1963,16 @@ pub enum Type {
     Character,
     /// Indicator that the result is a drama.
     Drama,
+    /// Indicator that the result is a [`Genre`].
+    ///
+    /// [`Genre`]: struct.Genre.html
+    #[serde(rename = "genres")]
+    Genre,
+    /// Indicator that the result is a [`LibraryEntry`].
+    ///
+    /// [`LibraryEntry`]: struct.LibraryEntry.html
+    #[serde(rename = "libraryEntries")]
+    LibraryEntry,
     /// Indicator that the result is a [`Manga`].
     ///
     /// [`Manga`]: struct.Manga.html
@@ -1100,12 +1999,29 @@ impl Type {
     /// assert_eq!(Type::Anime.name().unwrap(), "anime");
     /// ```
     pub fn name(&self) -> Result<String> {
-        let mut name = serde_json::to_string(self)?;
-
-        let _ = name.remove(0);
-        let _ = name.pop();
+        Ok(self.as_str().to_owned())
+    }
 
-        Ok(name)
+    /// The name of the type, as the API spells it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kitsu::model::Type;
+    ///
+    /// assert_eq!(Type::LibraryEntry.as_str(), "libraryEntries");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Type::Anime => "anime",
+            Type::Character => "characters",
+            Type::Drama => "drama",
+            Type::Genre => "genres",
+            Type::LibraryEntry => "libraryEntries",
+            Type::Manga => "manga",
+            Type::Producer => "producers",
+            Type::Users => "users",
+        }
     }
 }
 
@@ -1131,12 +2047,35 @@ impl WaifuOrHusbando {
     /// assert_eq!(WaifuOrHusbando::Husbando.name().unwrap(), "Husbando");
     /// ```
     pub fn name(&self) -> Result<String> {
-        let mut name = serde_json::to_string(self)?;
+        Ok(self.as_str().to_owned())
+    }
+
+    /// The name of the waifu or husbando indicator, as the API spells it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kitsu::model::WaifuOrHusbando;
+    ///
+    /// assert_eq!(WaifuOrHusbando::Waifu.as_str(), "Waifu");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            WaifuOrHusbando::Husbando => "Husbando",
+            WaifuOrHusbando::Waifu => "Waifu",
+        }
+    }
+}
 
-        let _ = name.remove(0);
-        let _ = name.pop();
+/// Whether a resource object has a type and, if given, an id.
+fn is_resource(resource: &Value, kind: &str, id: Option<&str>) -> bool {
+    if resource["type"] != kind {
+        return false;
+    }
 
-        Ok(name)
+    match id {
+        Some(id) => resource["id"] == id,
+        None => true,
     }
 }
 
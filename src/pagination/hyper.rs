@@ -0,0 +1,210 @@
+//! Streams over every page of a search for the `hyper` bridge, built on
+//! `futures` 0.1.
+//!
+//! These behave the same as the `std` streams of the asynchronous `reqwest`
+//! bridge, but yield errors through the stream's error type.
+
+use futures::{Async, Future, Poll, Stream};
+use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use super::{Cursor, Slot};
+use crate::model::Response;
+use crate::{Error, Result};
+
+type PageFuture<T> = Box<dyn Future<Item = Response<Vec<T>>, Error = Error>>;
+type Fetch<T> = Box<dyn Fn(String) -> PageFuture<T>>;
+type PageSlot<T> = Slot<PageFuture<T>, Result<Response<Vec<T>>>>;
+
+/// A lazy stream over the pages of a search, requesting pages as the stream
+/// is polled.
+///
+/// The stream ends after the last page, which has no `next` link, or after
+/// the first error, dropping any pages still in flight.
+pub struct PageStream<T> {
+    cursor: Cursor,
+    fetch: Fetch<T>,
+    queue: VecDeque<PageSlot<T>>,
+}
+
+impl<T> PageStream<T> {
+    pub(crate) fn new<F>(path: String, fetch: F) -> Self
+        where F: Fn(String) -> PageFuture<T> + 'static {
+        PageStream {
+            cursor: Cursor::new(path),
+            fetch: Box::new(fetch),
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Sets the number of results to request per page, overriding a limit
+    /// set with [`Search::limit`].
+    ///
+    /// The API allows at most 20 results per page, so larger sizes are
+    /// lowered to 20. This has no effect once the first page has been
+    /// requested.
+    ///
+    /// [`Search::limit`]: ../../builder/struct.Search.html#method.limit
+    pub fn page_size(mut self, size: u64) -> Self {
+        self.cursor.page_size(size);
+
+        self
+    }
+
+    /// Sets the number of pages to request ahead of the page being waited
+    /// on, so that up to `pages + 1` requests are in flight at once.
+    ///
+    /// Pages are then requested by offset rather than by following `next`
+    /// links, and are still yielded in order. This has no effect once the
+    /// first page has been requested.
+    pub fn prefetch(mut self, pages: usize) -> Self {
+        self.cursor.prefetch(pages);
+
+        self
+    }
+
+    /// Streams the results of every page rather than the pages.
+    pub fn items(self) -> ItemStream<T> {
+        ItemStream {
+            buffer: VecDeque::new(),
+            pages: self,
+            remaining: None,
+        }
+    }
+}
+
+impl<T> Stream for PageStream<T> {
+    type Item = Response<Vec<T>>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        while self.queue.len() < self.cursor.max_in_flight() {
+            match self.cursor.next_path() {
+                Some(path) => {
+                    self.queue.push_back(Slot::Pending((self.fetch)(path)));
+                },
+                None => break,
+            }
+        }
+
+        for slot in &mut self.queue {
+            let page = match *slot {
+                Slot::Pending(ref mut future) => match future.poll() {
+                    Ok(Async::Ready(page)) => Ok(page),
+                    Ok(Async::NotReady) => continue,
+                    Err(why) => Err(why),
+                },
+                Slot::Ready(_) => continue,
+            };
+
+            *slot = Slot::Ready(page);
+        }
+
+        let page = match self.queue.pop_front() {
+            Some(Slot::Ready(page)) => page,
+            Some(pending) => {
+                self.queue.push_front(pending);
+
+                return Ok(Async::NotReady);
+            },
+            None => return Ok(Async::Ready(None)),
+        };
+
+        match page {
+            Ok(ref page) => self.cursor.advance(page),
+            Err(_) => self.cursor.finish(),
+        }
+
+        if self.cursor.is_finished() {
+            self.queue.clear();
+        }
+
+        page.map(|page| Async::Ready(Some(page)))
+    }
+}
+
+impl<T> Debug for PageStream<T> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("PageStream")
+            .field("cursor", &self.cursor)
+            .field("in_flight", &self.queue.len())
+            .finish()
+    }
+}
+
+/// A lazy stream over the results of every page of a search.
+///
+/// The stream ends after the last result of the last page, after the
+/// maximum number of results, or after the first error.
+pub struct ItemStream<T> {
+    buffer: VecDeque<T>,
+    pages: PageStream<T>,
+    remaining: Option<u64>,
+}
+
+impl<T> ItemStream<T> {
+    /// Sets the number of results to request per page.
+    ///
+    /// Refer to [`PageStream::page_size`].
+    ///
+    /// [`PageStream::page_size`]: struct.PageStream.html#method.page_size
+    pub fn page_size(mut self, size: u64) -> Self {
+        self.pages = self.pages.page_size(size);
+
+        self
+    }
+
+    /// Sets the number of pages to request ahead of the current one.
+    ///
+    /// Refer to [`PageStream::prefetch`].
+    ///
+    /// [`PageStream::prefetch`]: struct.PageStream.html#method.prefetch
+    pub fn prefetch(mut self, pages: usize) -> Self {
+        self.pages = self.pages.prefetch(pages);
+
+        self
+    }
+
+    /// Sets the maximum number of results to stream.
+    ///
+    /// No more pages are requested once the maximum is reached.
+    pub fn max_items(mut self, max: u64) -> Self {
+        self.remaining = Some(max);
+
+        self
+    }
+}
+
+impl<T> Stream for ItemStream<T> {
+    type Item = T;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        if self.remaining == Some(0) {
+            return Ok(Async::Ready(None));
+        }
+
+        while self.buffer.is_empty() {
+            match self.pages.poll()? {
+                Async::Ready(Some(page)) => self.buffer.extend(page.data),
+                Async::Ready(None) => return Ok(Async::Ready(None)),
+                Async::NotReady => return Ok(Async::NotReady),
+            }
+        }
+
+        if let Some(ref mut remaining) = self.remaining {
+            *remaining -= 1;
+        }
+
+        Ok(Async::Ready(self.buffer.pop_front()))
+    }
+}
+
+impl<T> Debug for ItemStream<T> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("ItemStream")
+            .field("buffered", &self.buffer.len())
+            .field("pages", &self.pages)
+            .field("remaining", &self.remaining)
+            .finish()
+    }
+}
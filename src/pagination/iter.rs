@@ -0,0 +1,145 @@
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::marker::PhantomData;
+use super::Cursor;
+use crate::client::KitsuClient;
+use crate::endpoint;
+use crate::model::Response;
+use crate::transport::HttpTransport;
+use crate::Result;
+
+/// A lazy iterator over the pages of a search, requesting each page when it
+/// is reached.
+///
+/// Iteration stops after the last page, which has no `next` link, or after
+/// the first error.
+pub struct Pages<'a, T> {
+    client: KitsuClient<&'a dyn HttpTransport>,
+    cursor: Cursor,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> Pages<'a, T> {
+    pub(crate) fn new(client: KitsuClient<&'a dyn HttpTransport>, path: String)
+        -> Self {
+        Pages {
+            client,
+            cursor: Cursor::new(path),
+            marker: PhantomData,
+        }
+    }
+
+    /// Sets the number of results to request per page, overriding a limit
+    /// set with [`Search::limit`].
+    ///
+    /// The API allows at most 20 results per page, so larger sizes are
+    /// lowered to 20. This has no effect once the first page has been
+    /// requested.
+    ///
+    /// [`Search::limit`]: ../builder/struct.Search.html#method.limit
+    pub fn page_size(mut self, size: u64) -> Self {
+        self.cursor.page_size(size);
+
+        self
+    }
+
+    /// Iterates over the results of every page rather than the pages.
+    pub fn items(self) -> Items<'a, T> {
+        Items {
+            buffer: VecDeque::new(),
+            pages: self,
+            remaining: None,
+        }
+    }
+}
+
+impl<'a, T: DeserializeOwned> Iterator for Pages<'a, T> {
+    type Item = Result<Response<Vec<T>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let path = self.cursor.next_path()?;
+        let page = self.client.execute(endpoint::page(path));
+
+        match page {
+            Ok(ref page) => self.cursor.advance(page),
+            Err(_) => self.cursor.finish(),
+        }
+
+        Some(page)
+    }
+}
+
+impl<'a, T> Debug for Pages<'a, T> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("Pages")
+            .field("cursor", &self.cursor)
+            .finish()
+    }
+}
+
+/// A lazy iterator over the results of every page of a search, requesting
+/// each page when its first result is reached.
+///
+/// Iteration stops after the last result of the last page, after the
+/// maximum number of results, or after the first error.
+pub struct Items<'a, T> {
+    buffer: VecDeque<T>,
+    pages: Pages<'a, T>,
+    remaining: Option<u64>,
+}
+
+impl<'a, T> Items<'a, T> {
+    /// Sets the number of results to request per page.
+    ///
+    /// Refer to [`Pages::page_size`].
+    ///
+    /// [`Pages::page_size`]: struct.Pages.html#method.page_size
+    pub fn page_size(mut self, size: u64) -> Self {
+        self.pages = self.pages.page_size(size);
+
+        self
+    }
+
+    /// Sets the maximum number of results to iterate over.
+    ///
+    /// No more pages are requested once the maximum is reached.
+    pub fn max_items(mut self, max: u64) -> Self {
+        self.remaining = Some(max);
+
+        self
+    }
+}
+
+impl<'a, T: DeserializeOwned> Iterator for Items<'a, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+
+        while self.buffer.is_empty() {
+            match self.pages.next()? {
+                Ok(page) => self.buffer.extend(page.data),
+                Err(why) => return Some(Err(why)),
+            }
+        }
+
+        if let Some(ref mut remaining) = self.remaining {
+            *remaining -= 1;
+        }
+
+        self.buffer.pop_front().map(Ok)
+    }
+}
+
+impl<'a, T> Debug for Items<'a, T> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("Items")
+            .field("buffered", &self.buffer.len())
+            .field("pages", &self.pages)
+            .field("remaining", &self.remaining)
+            .finish()
+    }
+}
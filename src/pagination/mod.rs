@@ -0,0 +1,259 @@
+//! Iterators and streams over every page of a search, following the `next`
+//! link of each page.
+//!
+//! The blocking `reqwest` bridge returns the iterators [`Pages`] and
+//! [`Items`], the asynchronous `reqwest` bridge returns the `std` streams
+//! [`PageStream`] and [`ItemStream`], and the `hyper` bridge returns the
+//! `futures` streams in the [`hyper`] module.
+//!
+//! Streams request one page at a time by default. With a prefetch set, they
+//! instead request pages by offset, keeping the following pages in flight
+//! while the current one is consumed.
+//!
+//! # Examples
+//!
+//! Print the title of every anime airing in the winter of 2018, requesting
+//! them 20 at a time:
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "reqwest")]
+//! # extern crate reqwest;
+//! #
+//! # #[cfg(feature = "reqwest")]
+//! # fn main() {
+//! #
+//! use kitsu::builder::AnimeSearch;
+//! use kitsu::model::{Anime, Season};
+//! use kitsu::KitsuReqwestRequester;
+//! use reqwest::Client;
+//!
+//! let client = Client::new();
+//! let search = AnimeSearch::default()
+//!     .season(Season::Winter)
+//!     .season_year(2018);
+//!
+//! let anime = client.search_all::<Anime, _>(search).page_size(20);
+//!
+//! for anime in anime {
+//!     let anime = anime.expect("Error getting anime");
+//!
+//!     println!("{}", anime.attributes.canonical_title);
+//! }
+//! # }
+//! #
+//! # #[cfg(not(feature = "reqwest"))]
+//! # fn main() { }
+//! ```
+//!
+//! [`ItemStream`]: struct.ItemStream.html
+//! [`Items`]: struct.Items.html
+//! [`PageStream`]: struct.PageStream.html
+//! [`Pages`]: struct.Pages.html
+//! [`hyper`]: hyper/index.html
+
+#[cfg(feature = "reqwest")]
+mod iter;
+#[cfg(feature = "reqwest_async")]
+mod stream;
+
+#[cfg(feature = "hyper")]
+pub mod hyper;
+
+#[cfg(feature = "reqwest")]
+pub use self::iter::{Items, Pages};
+#[cfg(feature = "reqwest_async")]
+pub use self::stream::{ItemStream, PageStream};
+
+use crate::model::Response;
+
+/// The number of results per page that the API responds with when a search
+/// sets no limit.
+const DEFAULT_PAGE_SIZE: u64 = 10;
+
+/// The most results per page that the API responds with, whatever the limit
+/// requested.
+const MAX_PAGE_SIZE: u64 = 20;
+
+/// Tracks which page of a search to request next.
+///
+/// Without a prefetch, the path of the next page is the `next` link of the
+/// current one. With a prefetch, paths are instead created from the offset
+/// of each page, so that they are known before the previous pages arrive.
+#[derive(Clone, Debug)]
+pub(crate) struct Cursor {
+    finished: bool,
+    next: Option<String>,
+    offsets: Option<Offsets>,
+    prefetch: usize,
+    started: bool,
+}
+
+#[derive(Clone, Debug)]
+struct Offsets {
+    limit: u64,
+    offset: u64,
+    path: String,
+}
+
+impl Cursor {
+    pub fn new(path: String) -> Self {
+        Cursor {
+            finished: false,
+            next: Some(path),
+            offsets: None,
+            prefetch: 0,
+            started: false,
+        }
+    }
+
+    /// Sets the number of results per page, if no page has been requested.
+    ///
+    /// Sizes above the API's maximum are lowered to it.
+    pub fn page_size(&mut self, size: u64) {
+        if self.started {
+            return;
+        }
+
+        if let Some(ref mut path) = self.next {
+            *path = with_page(path, Some(size.min(MAX_PAGE_SIZE)), None);
+        }
+    }
+
+    /// Sets the number of pages to request ahead of the current one, if no
+    /// page has been requested.
+    #[cfg(any(feature = "hyper", feature = "reqwest_async"))]
+    pub fn prefetch(&mut self, pages: usize) {
+        if !self.started {
+            self.prefetch = pages;
+        }
+    }
+
+    /// The maximum number of pages to have requested at once.
+    #[cfg(any(feature = "hyper", feature = "reqwest_async"))]
+    pub fn max_in_flight(&self) -> usize {
+        self.prefetch + 1
+    }
+
+    #[cfg(any(feature = "hyper", feature = "reqwest_async"))]
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Takes the path of the next page to request, if it is known.
+    pub fn next_path(&mut self) -> Option<String> {
+        if self.finished {
+            return None;
+        }
+
+        if !self.started {
+            self.started = true;
+
+            if self.prefetch > 0 {
+                let path = self.next.take()?;
+
+                self.offsets = Some(Offsets {
+                    limit: page_param(&path, "limit")
+                        .filter(|&limit| limit > 0)
+                        .map_or(DEFAULT_PAGE_SIZE, |limit| limit.min(MAX_PAGE_SIZE)),
+                    offset: page_param(&path, "offset").unwrap_or(0),
+                    path,
+                });
+            }
+        }
+
+        match self.offsets {
+            Some(ref mut offsets) => {
+                let path = with_page(
+                    &offsets.path,
+                    Some(offsets.limit),
+                    Some(offsets.offset),
+                );
+                offsets.offset += offsets.limit;
+
+                Some(path)
+            },
+            None => self.next.take(),
+        }
+    }
+
+    /// Records a received page, finding the next page from it.
+    ///
+    /// When requesting by offset, the search is finished once a page has no
+    /// `next` link. The offsets step by a limit of at most the API's maximum,
+    /// so that a page cut short by the API is not taken as the last one.
+    pub fn advance<T>(&mut self, page: &Response<Vec<T>>) {
+        let next = page.links.get("next");
+
+        if self.offsets.is_none() {
+            self.next = next.cloned();
+        } else if next.is_none() {
+            self.finished = true;
+        }
+    }
+
+    /// Stops requesting pages, such as after an error.
+    pub fn finish(&mut self) {
+        self.finished = true;
+        self.next = None;
+    }
+}
+
+/// A page that has been requested by a stream, in the order of the search.
+#[cfg(any(feature = "hyper", feature = "reqwest_async"))]
+pub(crate) enum Slot<F, T> {
+    Pending(F),
+    Ready(T),
+}
+
+/// Replaces the page limit and offset of a path's query, keeping its other
+/// parameters.
+fn with_page(path: &str, limit: Option<u64>, offset: Option<u64>) -> String {
+    let (base, query) = match path.find('?') {
+        Some(idx) => (&path[..idx], &path[idx + 1..]),
+        None => (path, ""),
+    };
+
+    let mut params = query.split('&')
+        .filter(|param| !param.is_empty())
+        .filter(|param| {
+            let key = param.split('=').next().unwrap_or("");
+            let replaced = limit.is_some() && is_page_key(key, "limit")
+                || offset.is_some() && is_page_key(key, "offset");
+
+            !replaced
+        })
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+
+    if let Some(limit) = limit {
+        params.push(format!("page[limit]={}", limit));
+    }
+
+    if let Some(offset) = offset {
+        params.push(format!("page[offset]={}", offset));
+    }
+
+    format!("{}?{}", base, params.join("&"))
+}
+
+/// Finds the value of a page parameter in a path's query.
+fn page_param(path: &str, name: &str) -> Option<u64> {
+    let query = &path[path.find('?')? + 1..];
+
+    query.split('&')
+        .filter_map(|param| {
+            let mut parts = param.splitn(2, '=');
+
+            Some((parts.next()?, parts.next()?))
+        })
+        .filter(|&(key, _)| is_page_key(key, name))
+        .filter_map(|(_, value)| value.parse().ok())
+        .next_back()
+}
+
+/// Whether a query key is the page parameter with the name, with or without
+/// its brackets percent-encoded.
+fn is_page_key(key: &str, name: &str) -> bool {
+    key == format!("page[{}]", name)
+        || key.eq_ignore_ascii_case(&format!("page%5B{}%5D", name))
+}
@@ -0,0 +1,215 @@
+use futures_core::Stream;
+use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use super::{Cursor, Slot};
+use crate::model::Response;
+use crate::transport::BoxFuture;
+use crate::Result;
+
+type PageFuture<'a, T> = BoxFuture<'a, Result<Response<Vec<T>>>>;
+type Fetch<'a, T> = Box<dyn Fn(String) -> PageFuture<'a, T> + Send + Sync + 'a>;
+type PageSlot<'a, T> = Slot<PageFuture<'a, T>, Result<Response<Vec<T>>>>;
+
+/// A lazy stream over the pages of a search, requesting pages as the stream
+/// is polled.
+///
+/// The stream ends after the last page, which has no `next` link, or after
+/// the first error, dropping any pages still in flight.
+pub struct PageStream<'a, T> {
+    cursor: Cursor,
+    fetch: Fetch<'a, T>,
+    queue: VecDeque<PageSlot<'a, T>>,
+}
+
+impl<'a, T> PageStream<'a, T> {
+    pub(crate) fn new<F>(path: String, fetch: F) -> Self
+        where F: Fn(String) -> PageFuture<'a, T> + Send + Sync + 'a {
+        PageStream {
+            cursor: Cursor::new(path),
+            fetch: Box::new(fetch),
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Sets the number of results to request per page, overriding a limit
+    /// set with [`Search::limit`].
+    ///
+    /// The API allows at most 20 results per page, so larger sizes are
+    /// lowered to 20. This has no effect once the first page has been
+    /// requested.
+    ///
+    /// [`Search::limit`]: ../builder/struct.Search.html#method.limit
+    pub fn page_size(mut self, size: u64) -> Self {
+        self.cursor.page_size(size);
+
+        self
+    }
+
+    /// Sets the number of pages to request ahead of the page being waited
+    /// on, so that up to `pages + 1` requests are in flight at once.
+    ///
+    /// Pages are then requested by offset rather than by following `next`
+    /// links, and are still yielded in order. This has no effect once the
+    /// first page has been requested.
+    pub fn prefetch(mut self, pages: usize) -> Self {
+        self.cursor.prefetch(pages);
+
+        self
+    }
+
+    /// Streams the results of every page rather than the pages.
+    pub fn items(self) -> ItemStream<'a, T> {
+        ItemStream {
+            buffer: VecDeque::new(),
+            pages: self,
+            remaining: None,
+        }
+    }
+}
+
+impl<'a, T> Stream for PageStream<'a, T> {
+    type Item = Result<Response<Vec<T>>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>)
+        -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        while this.queue.len() < this.cursor.max_in_flight() {
+            match this.cursor.next_path() {
+                Some(path) => {
+                    this.queue.push_back(Slot::Pending((this.fetch)(path)));
+                },
+                None => break,
+            }
+        }
+
+        for slot in &mut this.queue {
+            if let Slot::Pending(ref mut future) = *slot {
+                if let Poll::Ready(page) = future.as_mut().poll(cx) {
+                    *slot = Slot::Ready(page);
+                }
+            }
+        }
+
+        let page = match this.queue.pop_front() {
+            Some(Slot::Ready(page)) => page,
+            Some(pending) => {
+                this.queue.push_front(pending);
+
+                return Poll::Pending;
+            },
+            None => return Poll::Ready(None),
+        };
+
+        match page {
+            Ok(ref page) => this.cursor.advance(page),
+            Err(_) => this.cursor.finish(),
+        }
+
+        if this.cursor.is_finished() {
+            this.queue.clear();
+        }
+
+        Poll::Ready(Some(page))
+    }
+}
+
+// The stream never projects a pin to its fields; the futures in flight are
+// boxed and pinned separately.
+impl<'a, T> Unpin for PageStream<'a, T> {}
+
+impl<'a, T> Debug for PageStream<'a, T> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("PageStream")
+            .field("cursor", &self.cursor)
+            .field("in_flight", &self.queue.len())
+            .finish()
+    }
+}
+
+/// A lazy stream over the results of every page of a search.
+///
+/// The stream ends after the last result of the last page, after the
+/// maximum number of results, or after the first error.
+pub struct ItemStream<'a, T> {
+    buffer: VecDeque<T>,
+    pages: PageStream<'a, T>,
+    remaining: Option<u64>,
+}
+
+impl<'a, T> ItemStream<'a, T> {
+    /// Sets the number of results to request per page.
+    ///
+    /// Refer to [`PageStream::page_size`].
+    ///
+    /// [`PageStream::page_size`]: struct.PageStream.html#method.page_size
+    pub fn page_size(mut self, size: u64) -> Self {
+        self.pages = self.pages.page_size(size);
+
+        self
+    }
+
+    /// Sets the number of pages to request ahead of the current one.
+    ///
+    /// Refer to [`PageStream::prefetch`].
+    ///
+    /// [`PageStream::prefetch`]: struct.PageStream.html#method.prefetch
+    pub fn prefetch(mut self, pages: usize) -> Self {
+        self.pages = self.pages.prefetch(pages);
+
+        self
+    }
+
+    /// Sets the maximum number of results to stream.
+    ///
+    /// No more pages are requested once the maximum is reached.
+    pub fn max_items(mut self, max: u64) -> Self {
+        self.remaining = Some(max);
+
+        self
+    }
+}
+
+impl<'a, T> Stream for ItemStream<'a, T> {
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>)
+        -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.remaining == Some(0) {
+            return Poll::Ready(None);
+        }
+
+        while this.buffer.is_empty() {
+            match Pin::new(&mut this.pages).poll_next(cx) {
+                Poll::Ready(Some(Ok(page))) => this.buffer.extend(page.data),
+                Poll::Ready(Some(Err(why))) => {
+                    return Poll::Ready(Some(Err(why)));
+                },
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        if let Some(ref mut remaining) = this.remaining {
+            *remaining -= 1;
+        }
+
+        Poll::Ready(this.buffer.pop_front().map(Ok))
+    }
+}
+
+impl<'a, T> Unpin for ItemStream<'a, T> {}
+
+impl<'a, T> Debug for ItemStream<'a, T> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("ItemStream")
+            .field("buffered", &self.buffer.len())
+            .field("pages", &self.pages)
+            .field("remaining", &self.remaining)
+            .finish()
+    }
+}
@@ -0,0 +1,113 @@
+//! Limiting the rate that requests are sent at.
+//!
+//! A [`RateLimiter`] given to a [`KitsuClient`] holds back requests so that
+//! no more than a number of requests per second are sent on average, while
+//! allowing short bursts. Every attempt counts against the limit, including
+//! retries and requests for authentication tokens.
+//!
+//! The limiter is a handle to a shared budget: clones of it given to several
+//! clients limit the requests of all of them together.
+//!
+//! Clients wait with the `sleep` method of their transport, in the same way
+//! as between retries.
+//!
+//! # Examples
+//!
+//! Share a budget of 4 requests per second, with bursts of up to 10
+//! requests, between two clients:
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "reqwest")]
+//! # extern crate reqwest;
+//! #
+//! # #[cfg(feature = "reqwest")]
+//! # fn main() {
+//! #
+//! use kitsu::rate_limit::RateLimiter;
+//! use kitsu::{KitsuClient, KitsuReqwestRequester};
+//! use reqwest::Client;
+//!
+//! let limiter = RateLimiter::new(4.0, 10);
+//! let anime = KitsuClient::new(Client::new()).rate_limit(limiter.clone());
+//! let users = KitsuClient::new(Client::new()).rate_limit(limiter);
+//!
+//! for id in 1..=20 {
+//!     let _ = anime.get_anime(id);
+//!     let _ = users.get_user(id);
+//! }
+//! # }
+//! #
+//! # #[cfg(not(feature = "reqwest"))]
+//! # fn main() { }
+//! ```
+//!
+//! [`KitsuClient`]: ../client/struct.KitsuClient.html
+//! [`RateLimiter`]: struct.RateLimiter.html
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A token bucket limiting the rate of requests, shared between its clones.
+///
+/// The bucket holds up to `burst` tokens and starts full. Each request takes
+/// a token, and tokens are added back at the configured rate. A request
+/// arriving at an empty bucket reserves the next token and waits until it is
+/// added, so waiting requests are sent in the order they arrived.
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    bucket: Arc<Mutex<Bucket>>,
+    burst: f64,
+    per_second: f64,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    /// Creates a limiter allowing an average of `per_second` requests per
+    /// second, and up to `burst` requests at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `per_second` is not a positive number, or if `burst` is 0.
+    pub fn new(per_second: f64, burst: u32) -> Self {
+        assert!(per_second > 0.0, "rate limit must be positive");
+        assert!(burst > 0, "rate limit burst must be at least 1");
+
+        RateLimiter {
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: f64::from(burst),
+                updated: Instant::now(),
+            })),
+            burst: f64::from(burst),
+            per_second,
+        }
+    }
+
+    /// Takes a token for a request, returning how long to wait before
+    /// sending it.
+    ///
+    /// Clients wait on this themselves. It can be used to count requests
+    /// made some other way against the same budget.
+    pub fn reserve(&self) -> Duration {
+        let mut bucket = self.bucket
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+
+        bucket.tokens = (bucket.tokens + elapsed * self.per_second)
+            .min(self.burst);
+        bucket.updated = now;
+        bucket.tokens -= 1.0;
+
+        if bucket.tokens >= 0.0 {
+            Duration::from_secs(0)
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.per_second)
+        }
+    }
+}
@@ -0,0 +1,211 @@
+//! Retrying requests that fail for transient reasons.
+//!
+//! A [`RetryPolicy`] given to a [`KitsuClient`] sends a request again when it
+//! could not be sent, or when the API responds with status code 429 or a 5xx
+//! other than `501 Not Implemented`. Between attempts the client waits for
+//! an exponentially increasing delay, or for as long as the API asked in the
+//! response's `Retry-After` header.
+//!
+//! Only `GET` requests are retried by default, since sending a request that
+//! creates or updates a resource twice may apply it twice.
+//!
+//! Clients wait with the `sleep` method of their transport. The blocking
+//! bridge sleeps the current thread, the asynchronous bridge waits on a
+//! `tokio` timer, and the `hyper` bridge waits on the timer of the event
+//! loop driving the Client.
+//!
+//! # Examples
+//!
+//! Try a request up to 5 times, waiting at least a second between attempts:
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "reqwest")]
+//! # extern crate reqwest;
+//! #
+//! # #[cfg(feature = "reqwest")]
+//! # fn main() {
+//! #
+//! use kitsu::retry::RetryPolicy;
+//! use kitsu::{KitsuClient, KitsuReqwestRequester};
+//! use reqwest::Client;
+//! use std::time::Duration;
+//!
+//! let policy = RetryPolicy::new()
+//!     .max_attempts(5)
+//!     .base_delay(Duration::from_secs(1));
+//! let client = KitsuClient::new(Client::new()).retry(policy);
+//!
+//! let anime = client.get_anime(1).expect("Error getting anime");
+//! # }
+//! #
+//! # #[cfg(not(feature = "reqwest"))]
+//! # fn main() { }
+//! ```
+//!
+//! [`KitsuClient`]: ../client/struct.KitsuClient.html
+//! [`RetryPolicy`]: struct.RetryPolicy.html
+
+use std::time::Duration;
+use crate::transport::Method;
+
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+use std::collections::hash_map::RandomState;
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+use std::hash::{BuildHasher, Hasher};
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+use crate::error::parse_retry_after;
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+use crate::transport::{HttpRequest, HttpResponse};
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+use crate::{Error, Result};
+
+/// A policy deciding whether, and after how long, a failed request is sent
+/// again.
+///
+/// The delay before the `n`th retry is `base_delay * 2^(n - 1)`, capped at
+/// the maximum delay. With jitter enabled, a random delay between half of
+/// that and all of it is used instead, so that clients failing at the same
+/// time do not all retry at the same time.
+///
+/// A `Retry-After` header takes precedence over the computed delay. If it
+/// asks for a longer wait than the maximum delay, the request is not retried
+/// and its error is returned.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    base_delay: Duration,
+    jitter: bool,
+    max_attempts: u32,
+    max_delay: Duration,
+    methods: Vec<Method>,
+}
+
+impl RetryPolicy {
+    /// Creates a policy trying `GET` requests up to 3 times, with a base
+    /// delay of 500 milliseconds, a maximum delay of 30 seconds, and jitter.
+    pub fn new() -> Self {
+        RetryPolicy {
+            base_delay: Duration::from_millis(500),
+            jitter: true,
+            max_attempts: 3,
+            max_delay: Duration::from_secs(30),
+            methods: vec![Method::Get],
+        }
+    }
+
+    /// Sets the delay before the first retry, which is doubled for each
+    /// retry after it.
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+
+        self
+    }
+
+    /// Sets whether to randomize delays.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+
+        self
+    }
+
+    /// Sets the maximum number of times a request is sent, including the
+    /// first attempt.
+    ///
+    /// A value of 0 or 1 disables retries.
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts;
+
+        self
+    }
+
+    /// Sets the maximum delay between attempts.
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+
+        self
+    }
+
+    /// Sets the methods of the requests that may be retried, replacing the
+    /// default of only `GET`.
+    ///
+    /// Only add methods whose requests are safe to send twice.
+    pub fn methods(mut self, methods: &[Method]) -> Self {
+        self.methods = methods.to_vec();
+
+        self
+    }
+}
+
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+impl RetryPolicy {
+    /// Decides how long to wait before sending a request again, after the
+    /// given attempt (counting from 1) ended with the given outcome.
+    ///
+    /// Returns `None` if the request should not be retried.
+    pub(crate) fn delay(
+        &self,
+        request: &HttpRequest,
+        attempt: u32,
+        outcome: &Result<HttpResponse>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.methods.contains(&request.method) {
+            return None;
+        }
+
+        let retry_after = match *outcome {
+            Ok(ref response) if is_retryable_status(response.status) => {
+                response.header("Retry-After").and_then(parse_retry_after)
+            },
+            Ok(_) => return None,
+            Err(Error::Transport(_)) => None,
+            Err(_) => return None,
+        };
+
+        match retry_after {
+            Some(delay) if delay > self.max_delay => None,
+            Some(delay) => Some(delay),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let delay = self.base_delay
+            .checked_mul(factor)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay));
+
+        if !self.jitter {
+            return delay;
+        }
+
+        let half = delay / 2;
+        let spread = (delay - half).as_millis() as u64;
+
+        if spread == 0 {
+            return delay;
+        }
+
+        half + Duration::from_millis(random() % (spread + 1))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::new()
+    }
+}
+
+/// Whether a response's status code indicates a failure that may not happen
+/// again, matching [`Error::is_retryable`].
+///
+/// [`Error::is_retryable`]: ../enum.Error.html#method.is_retryable
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+fn is_retryable_status(status: u16) -> bool {
+    status == 429 || ((500..600).contains(&status) && status != 501)
+}
+
+/// Generates a random number from the randomly seeded keys of a
+/// `RandomState`, which is good enough for jitter.
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+fn random() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
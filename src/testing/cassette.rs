@@ -0,0 +1,450 @@
+//! A transport recording requests and responses to a file, replaying them
+//! afterward.
+
+use serde_json::Value;
+use std::fs::{self, File};
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use super::{lock, normalize};
+use crate::transport::{HttpRequest, HttpResponse, HttpTransport, Method};
+use crate::{Error, Result};
+
+#[cfg(feature = "hyper")]
+use futures::{future, Future};
+#[cfg(feature = "reqwest_async")]
+use crate::transport::{AsyncHttpTransport, BoxFuture};
+#[cfg(feature = "hyper")]
+use crate::transport::FuturesHttpTransport;
+
+/// The value that redacted header values and body fields are replaced with.
+const REDACTED: &str = "[REDACTED]";
+
+/// The fields of JSON bodies that are always redacted, holding the
+/// credentials sent to and the tokens received from the token endpoint.
+const REDACTED_FIELDS: &[&str] = &[
+    "access_token",
+    "client_secret",
+    "password",
+    "refresh_token",
+];
+
+/// Whether a [`Cassette`] records or replays interactions.
+///
+/// [`Cassette`]: struct.Cassette.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Mode {
+    /// Replays the cassette if its file exists, and records it otherwise.
+    Auto,
+    /// Sends every request with the wrapped transport, overwriting the file
+    /// with the interactions.
+    Record,
+    /// Serves every request from the file, never using the wrapped
+    /// transport.
+    Replay,
+}
+
+/// A transport wrapping another, recording the requests sent with it and
+/// the responses received to a JSON file, and deterministically replaying
+/// them afterward.
+///
+/// When replaying, each recorded interaction is served once, in the order
+/// they were recorded, to a request with the same method, URL, and body.
+/// Headers are not compared, and the order of query parameters does not
+/// matter. A request without a matching interaction fails with
+/// [`Error::InvalidRequest`].
+///
+/// The values of redacted headers and of redacted fields of JSON bodies are
+/// replaced before being written to the file, in both requests and
+/// responses. The `Authorization` header is always redacted, as are the
+/// `access_token`, `client_secret`, `password`, and `refresh_token` fields,
+/// so that logging in while recording does not write credentials or tokens
+/// to the file. Request bodies are compared with the same fields redacted
+/// when replaying.
+///
+/// Bodies are stored as strings, so bodies that are not valid UTF-8 are
+/// stored lossily.
+///
+/// Waits before retries and for rate limits are left to the wrapped
+/// transport.
+///
+/// # Examples
+///
+/// Record the requests made by a test the first time it is run, replaying
+/// them on every run after that:
+///
+/// ```rust,no_run
+/// # #[cfg(feature = "reqwest")]
+/// # extern crate reqwest;
+/// #
+/// # #[cfg(feature = "reqwest")]
+/// # fn main() {
+/// #
+/// use kitsu::testing::Cassette;
+/// use kitsu::{KitsuClient, KitsuReqwestRequester};
+/// use reqwest::Client;
+///
+/// let cassette = Cassette::new("tests/cassettes/anime.json", Client::new())
+///     .expect("Error loading cassette")
+///     .redact("X-Api-Key");
+/// let client = KitsuClient::new(&cassette).header("X-Api-Key", "secret");
+///
+/// let anime = client.get_anime(1).expect("Error getting anime");
+/// # }
+/// #
+/// # #[cfg(not(feature = "reqwest"))]
+/// # fn main() { }
+/// ```
+///
+/// [`Error::InvalidRequest`]: ../enum.Error.html#variant.InvalidRequest
+#[derive(Debug)]
+pub struct Cassette<T> {
+    inner: T,
+    recorder: Recorder,
+    recording: bool,
+}
+
+impl<T> Cassette<T> {
+    /// Loads the cassette at a path if it exists, replaying it, or prepares to
+    /// record it otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but could not be read or parsed.
+    #[inline]
+    pub fn new<P: Into<PathBuf>>(path: P, transport: T) -> io::Result<Self> {
+        Cassette::with_mode(path, transport, Mode::Auto)
+    }
+
+    /// Creates a cassette at a path, recording or replaying according to the
+    /// mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be read or parsed when
+    /// replaying.
+    pub fn with_mode<P: Into<PathBuf>>(path: P, transport: T, mode: Mode)
+        -> io::Result<Self> {
+        let path = path.into();
+        let recording = match mode {
+            Mode::Auto => !path.exists(),
+            Mode::Record => true,
+            Mode::Replay => false,
+        };
+        let interactions = if recording {
+            Vec::new()
+        } else {
+            load(&path)?
+        };
+
+        Ok(Cassette {
+            inner: transport,
+            recorder: Recorder {
+                path,
+                redacted: vec!["Authorization".to_owned()],
+                redacted_fields: REDACTED_FIELDS.iter()
+                    .map(|&field| field.to_owned())
+                    .collect(),
+                state: Arc::new(Mutex::new(State {
+                    used: vec![false; interactions.len()],
+                    interactions,
+                })),
+            },
+            recording,
+        })
+    }
+
+    /// Adds a header whose value is redacted when recording.
+    ///
+    /// Header names are compared ignoring ASCII case.
+    pub fn redact(mut self, name: &str) -> Self {
+        self.recorder.redacted.push(name.to_owned());
+
+        self
+    }
+
+    /// Adds a field of JSON bodies whose value is redacted when recording.
+    ///
+    /// Fields are redacted wherever they appear in the body, and their names
+    /// are compared exactly.
+    pub fn redact_field(mut self, name: &str) -> Self {
+        self.recorder.redacted_fields.push(name.to_owned());
+
+        self
+    }
+
+    /// Whether requests are being recorded rather than replayed.
+    #[inline]
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// The path of the cassette's file.
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.recorder.path
+    }
+
+    /// Consumes the cassette, returning the wrapped transport.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+/// The file, redactions, and interactions of a cassette, which are shared
+/// with the futures of bridges that must not borrow the cassette.
+#[derive(Clone, Debug)]
+struct Recorder {
+    path: PathBuf,
+    redacted: Vec<String>,
+    redacted_fields: Vec<String>,
+    state: Arc<Mutex<State>>,
+}
+
+impl Recorder {
+
+    /// Records an interaction, writing every interaction recorded so far to
+    /// the file.
+    fn record(&self, request: &HttpRequest, response: &HttpResponse)
+        -> Result<()> {
+        let mut state = lock(&self.state);
+
+        state.interactions.push(Interaction {
+            request: RecordedRequest {
+                body: request.body.as_ref().map(|body| self.redact_body(body)),
+                headers: self.redact_headers(&request.headers),
+                method: request.method,
+                url: request.url.clone(),
+            },
+            response: RecordedResponse {
+                body: self.redact_body(&response.body),
+                headers: self.redact_headers(&response.headers),
+                status: response.status,
+            },
+        });
+        state.used.push(true);
+
+        save(&self.path, &state.interactions)
+            .map_err(|why| Error::Transport(Box::new(why)))
+    }
+
+    /// Serves the first unused recorded interaction matching a request.
+    fn replay(&self, request: &HttpRequest) -> Result<HttpResponse> {
+        let url = normalize(&request.url);
+        let body = request.body.as_ref().map(|body| self.redact_body(body));
+        let mut state = lock(&self.state);
+        let State { ref interactions, ref mut used } = *state;
+
+        let position = interactions.iter()
+            .zip(used.iter())
+            .position(|(interaction, &used)| {
+                !used
+                    && interaction.request.method == request.method
+                    && normalize(&interaction.request.url) == url
+                    && interaction.request.body == body
+            });
+
+        match position {
+            Some(position) => {
+                used[position] = true;
+                let response = &interactions[position].response;
+
+                Ok(HttpResponse {
+                    body: response.body.clone().into_bytes(),
+                    headers: response.headers.clone(),
+                    status: response.status,
+                })
+            },
+            None => Err(Error::InvalidRequest {
+                response: None,
+                source: Some(format!(
+                    "No unused interaction in cassette {} for {} {}",
+                    self.path.display(),
+                    request.method,
+                    request.url,
+                ).into()),
+            }),
+        }
+    }
+
+    fn redact_headers(&self, headers: &[(String, String)])
+        -> Vec<(String, String)> {
+        headers.iter().map(|(name, value)| {
+            let redacted = self.redacted.iter()
+                .any(|redacted| redacted.eq_ignore_ascii_case(name));
+
+            if redacted {
+                (name.clone(), REDACTED.to_owned())
+            } else {
+                (name.clone(), value.clone())
+            }
+        }).collect()
+    }
+
+    /// Converts a body to a string, redacting its fields if it is JSON.
+    ///
+    /// Bodies are only re-serialized if a field was redacted.
+    fn redact_body(&self, body: &[u8]) -> String {
+        let mut value = match serde_json::from_slice::<Value>(body) {
+            Ok(value) => value,
+            Err(_) => return string(body),
+        };
+
+        if !self.redact_value(&mut value) {
+            return string(body);
+        }
+
+        value.to_string()
+    }
+
+    /// Redacts the fields of a JSON value recursively, returning whether any
+    /// were redacted.
+    fn redact_value(&self, value: &mut Value) -> bool {
+        let mut redacted = false;
+
+        match *value {
+            Value::Array(ref mut values) => {
+                for value in values {
+                    redacted |= self.redact_value(value);
+                }
+            },
+            Value::Object(ref mut fields) => {
+                for (name, value) in fields {
+                    if self.redacted_fields.iter().any(|field| field == name) {
+                        *value = Value::String(REDACTED.to_owned());
+                        redacted = true;
+                    } else {
+                        redacted |= self.redact_value(value);
+                    }
+                }
+            },
+            _ => {},
+        }
+
+        redacted
+    }
+}
+
+impl<T: HttpTransport> HttpTransport for Cassette<T> {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        if !self.recording {
+            return self.recorder.replay(&request);
+        }
+
+        let response = self.inner.send(request.clone())?;
+        self.recorder.record(&request, &response)?;
+
+        Ok(response)
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.inner.sleep(duration)
+    }
+}
+
+#[cfg(feature = "reqwest_async")]
+impl<T: AsyncHttpTransport> AsyncHttpTransport for Cassette<T> {
+    fn send(&self, request: HttpRequest)
+        -> BoxFuture<'_, Result<HttpResponse>> {
+        Box::pin(async move {
+            if !self.recording {
+                return self.recorder.replay(&request);
+            }
+
+            let response = self.inner.send(request.clone()).await?;
+            self.recorder.record(&request, &response)?;
+
+            Ok(response)
+        })
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'_, ()> {
+        self.inner.sleep(duration)
+    }
+}
+
+#[cfg(feature = "hyper")]
+impl<T: FuturesHttpTransport> FuturesHttpTransport for Cassette<T> {
+    fn send(&self, request: HttpRequest)
+        -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+        if !self.recording {
+            return Box::new(future::result(self.recorder.replay(&request)));
+        }
+
+        let recorder = self.recorder.clone();
+
+        Box::new(self.inner.send(request.clone()).and_then(move |response| {
+            recorder.record(&request, &response)?;
+
+            Ok(response)
+        }))
+    }
+
+    fn sleep(&self, duration: Duration)
+        -> Box<dyn Future<Item = (), Error = Error>> {
+        self.inner.sleep(duration)
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    interactions: Vec<Interaction>,
+    used: Vec<bool>,
+}
+
+/// The contents of a cassette's file.
+#[derive(Deserialize)]
+struct Document {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Serialize)]
+struct DocumentRef<'a> {
+    interactions: &'a [Interaction],
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct RecordedRequest {
+    body: Option<String>,
+    headers: Vec<(String, String)>,
+    method: Method,
+    url: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct RecordedResponse {
+    body: String,
+    headers: Vec<(String, String)>,
+    status: u16,
+}
+
+fn load(path: &Path) -> io::Result<Vec<Interaction>> {
+    let file = File::open(path)?;
+    let document: Document = serde_json::from_reader(file)
+        .map_err(|why| io::Error::new(ErrorKind::InvalidData, why))?;
+
+    Ok(document.interactions)
+}
+
+fn save(path: &Path, interactions: &[Interaction]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut json = serde_json::to_vec_pretty(&DocumentRef { interactions })
+        .map_err(|why| io::Error::new(ErrorKind::InvalidData, why))?;
+    json.push(b'\n');
+
+    fs::write(path, json)
+}
+
+fn string(body: &[u8]) -> String {
+    String::from_utf8_lossy(body).into_owned()
+}
@@ -0,0 +1,191 @@
+//! An in-memory transport serving canned responses.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+use super::{lock, normalize};
+use crate::transport::{HttpRequest, HttpResponse, HttpTransport, Method};
+use crate::{Error, Result, API_URL};
+
+#[cfg(feature = "hyper")]
+use crate::transport::FuturesHttpTransport;
+#[cfg(feature = "reqwest_async")]
+use crate::transport::{AsyncHttpTransport, BoxFuture};
+#[cfg(feature = "hyper")]
+use futures::{future, Future};
+
+/// An in-memory transport serving canned responses, keyed by the method,
+/// path, and query of each request.
+///
+/// Paths are relative to the transport's base URL, which is [`API_URL`] by
+/// default, and should match the base URL of the [`KitsuClient`] wrapping
+/// the transport. Requests outside of the base URL, such as those to the
+/// OAuth2 token endpoint, are matched by their absolute URL instead. The
+/// order of query parameters does not matter when matching.
+///
+/// When multiple responses are registered for the same request, they are
+/// served in the order they were registered, with the last one being served
+/// for every request after that.
+///
+/// A request that no response was registered for fails with
+/// [`Error::InvalidRequest`].
+///
+/// Waits before retries are recorded rather than slept, so that tests of
+/// them finish immediately and can check how long would have been waited.
+///
+/// [`API_URL`]: ../constant.API_URL.html
+/// [`Error::InvalidRequest`]: ../enum.Error.html#variant.InvalidRequest
+/// [`KitsuClient`]: ../client/struct.KitsuClient.html
+#[derive(Debug)]
+pub struct MockTransport {
+    base_url: String,
+    requests: Mutex<Vec<HttpRequest>>,
+    responses: Mutex<HashMap<(Method, String), VecDeque<HttpResponse>>>,
+    sleeps: Mutex<Vec<Duration>>,
+}
+
+impl MockTransport {
+    /// Creates a transport with no responses, using [`API_URL`] as the base
+    /// URL.
+    ///
+    /// [`API_URL`]: ../constant.API_URL.html
+    pub fn new() -> Self {
+        MockTransport {
+            base_url: API_URL.to_owned(),
+            requests: Mutex::new(Vec::new()),
+            responses: Mutex::new(HashMap::new()),
+            sleeps: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Sets the base URL that registered paths are relative to.
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_owned();
+
+        self
+    }
+
+    /// Registers a response with a status code and body for a request.
+    ///
+    /// The target is the path of the request, optionally followed by its
+    /// query, such as `/anime?filter[text]=bebop`, or an absolute URL.
+    pub fn respond<B: Into<Vec<u8>>>(
+        &self,
+        method: Method,
+        target: &str,
+        status: u16,
+        body: B,
+    ) -> &Self {
+        self.respond_with(method, target, HttpResponse {
+            body: body.into(),
+            headers: vec![(
+                "Content-Type".to_owned(),
+                "application/vnd.api+json".to_owned(),
+            )],
+            status,
+        })
+    }
+
+    /// Registers a full response for a request.
+    ///
+    /// The target is the path of the request, optionally followed by its
+    /// query, such as `/anime?filter[text]=bebop`, or an absolute URL.
+    pub fn respond_with(
+        &self,
+        method: Method,
+        target: &str,
+        response: HttpResponse,
+    ) -> &Self {
+        lock(&self.responses)
+            .entry((method, normalize(target)))
+            .or_default()
+            .push_back(response);
+
+        self
+    }
+
+    /// Retrieves the requests that have been made, in the order they were
+    /// made.
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        lock(&self.requests).clone()
+    }
+
+    /// Retrieves the durations that have been waited for, in the order they
+    /// were waited for.
+    pub fn sleeps(&self) -> Vec<Duration> {
+        lock(&self.sleeps).clone()
+    }
+
+    fn serve(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let target = normalize(request.url
+            .strip_prefix(self.base_url.as_str())
+            .unwrap_or(&request.url));
+        let method = request.method;
+        let url = request.url.clone();
+
+        lock(&self.requests).push(request);
+
+        let mut responses = lock(&self.responses);
+        let queue = responses.get_mut(&(method, target));
+
+        match queue {
+            Some(queue) if queue.len() > 1 => Ok(queue.pop_front().unwrap()),
+            Some(queue) if !queue.is_empty() => Ok(queue[0].clone()),
+            _ => Err(Error::InvalidRequest {
+                response: None,
+                source: Some(format!(
+                    "No mock response registered for {} {}",
+                    method,
+                    url,
+                ).into()),
+            }),
+        }
+    }
+}
+
+impl Default for MockTransport {
+    fn default() -> Self {
+        MockTransport::new()
+    }
+}
+
+impl HttpTransport for MockTransport {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        self.serve(request)
+    }
+
+    fn sleep(&self, duration: Duration) {
+        lock(&self.sleeps).push(duration);
+    }
+}
+
+#[cfg(feature = "reqwest_async")]
+impl AsyncHttpTransport for MockTransport {
+    fn send(&self, request: HttpRequest)
+        -> BoxFuture<'_, Result<HttpResponse>> {
+        let response = self.serve(request);
+
+        Box::pin(async move { response })
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'_, ()> {
+        lock(&self.sleeps).push(duration);
+
+        Box::pin(async {})
+    }
+}
+
+#[cfg(feature = "hyper")]
+impl FuturesHttpTransport for MockTransport {
+    fn send(&self, request: HttpRequest)
+        -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+        Box::new(future::result(self.serve(request)))
+    }
+
+    fn sleep(&self, duration: Duration)
+        -> Box<dyn Future<Item = (), Error = Error>> {
+        lock(&self.sleeps).push(duration);
+
+        Box::new(future::ok(()))
+    }
+}
@@ -0,0 +1,76 @@
+//! Utilities for testing code that uses the library without making requests
+//! to the API.
+//!
+//! This module is only available when the `testing` feature is enabled.
+//!
+//! Two transports are provided: the [`MockTransport`], which serves responses
+//! registered in code, and the [`Cassette`], which records real requests and
+//! responses to a file once and replays them afterward.
+//!
+//! # Examples
+//!
+//! Serve a canned response for an anime, and check the request that was
+//! made for it:
+//!
+//! ```rust
+//! # #[cfg(feature = "reqwest")]
+//! # fn main() {
+//! #
+//! use kitsu::testing::MockTransport;
+//! use kitsu::transport::Method;
+//! use kitsu::{KitsuClient, KitsuReqwestRequester};
+//!
+//! let transport = MockTransport::new();
+//! transport.respond(
+//!     Method::Get,
+//!     "/anime/1",
+//!     200,
+//!     &include_bytes!("../../tests/fixtures/anime.json")[..],
+//! );
+//!
+//! let client = KitsuClient::new(&transport);
+//! let anime = client.get_anime(1).expect("Error getting anime");
+//!
+//! assert_eq!(anime.data.attributes.canonical_title, "Cowboy Bebop");
+//! assert_eq!(transport.requests().len(), 1);
+//! # }
+//! #
+//! # #[cfg(not(feature = "reqwest"))]
+//! # fn main() { }
+//! ```
+//!
+//! [`Cassette`]: struct.Cassette.html
+//! [`MockTransport`]: struct.MockTransport.html
+
+mod cassette;
+mod mock;
+
+pub use self::cassette::{Cassette, Mode};
+pub use self::mock::MockTransport;
+
+use std::sync::{Mutex, MutexGuard};
+
+/// Locks a mutex, recovering the data if a panicking test poisoned it.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Normalizes a request target so that the order of its query parameters
+/// does not matter.
+fn normalize(target: &str) -> String {
+    let mut parts = target.splitn(2, '?');
+    let path = parts.next().unwrap_or_default();
+    let mut params: Vec<&str> = parts.next()
+        .map(|query| {
+            query.split('&').filter(|param| !param.is_empty()).collect()
+        })
+        .unwrap_or_default();
+
+    if params.is_empty() {
+        return path.to_owned();
+    }
+
+    params.sort_unstable();
+
+    format!("{}?{}", path, params.join("&"))
+}
@@ -0,0 +1,194 @@
+//! Instrumentation of requests with `tracing` spans.
+//!
+//! When the `tracing` feature is enabled, every call of a requester method -
+//! including each page requested by the pagination helpers - runs in a
+//! `kitsu.request` span at the debug level, with the fields:
+//!
+//! - `method`, `path`, `query`, and `resource`: what was requested, with the
+//!   resource being the last path segment that is not an id
+//! - `status` and `bytes`: the status code and body size of the response
+//! - `decode_micros`: how long decoding the response body took
+//! - `attempt`: which attempt the response was received on, when retrying
+//! - `error`: why the request failed, if it did
+//!
+//! Without the feature, all of this compiles to nothing.
+
+use serde::de::DeserializeOwned;
+use crate::endpoint;
+use crate::transport::{HttpRequest, HttpResponse};
+use crate::Result;
+
+#[cfg(any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"))]
+use std::time::Duration;
+#[cfg(feature = "reqwest_async")]
+use std::future::Future;
+#[cfg(all(feature = "tracing", feature = "hyper"))]
+use futures::Poll;
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+#[cfg(all(feature = "tracing", feature = "reqwest_async"))]
+use tracing::instrument::{Instrument, Instrumented};
+#[cfg(feature = "tracing")]
+use tracing::{field, Span};
+
+/// The span of a request, which does nothing without the `tracing` feature.
+#[derive(Clone, Debug)]
+pub(crate) struct RequestSpan {
+    #[cfg(feature = "tracing")]
+    span: Span,
+}
+
+#[cfg(feature = "tracing")]
+impl RequestSpan {
+    /// Opens the span of a request to a URL relative to the base URL.
+    pub(crate) fn new(request: &HttpRequest, base_url: &str) -> Self {
+        let target = request.url
+            .strip_prefix(base_url)
+            .unwrap_or(&request.url);
+        let mut parts = target.splitn(2, '?');
+        let path = parts.next().unwrap_or_default();
+        let query = parts.next().unwrap_or_default();
+        let resource = path.rsplit('/')
+            .find(|segment| {
+                !segment.is_empty()
+                    && !segment.bytes().all(|byte| byte.is_ascii_digit())
+            })
+            .unwrap_or_default();
+
+        RequestSpan {
+            span: tracing::debug_span!(
+                "kitsu.request",
+                method = %request.method,
+                path,
+                query,
+                resource,
+                status = field::Empty,
+                bytes = field::Empty,
+                decode_micros = field::Empty,
+                attempt = 1u32,
+                error = field::Empty,
+            ),
+        }
+    }
+
+    /// Runs a function within the span.
+    pub(crate) fn in_scope<T, F: FnOnce() -> T>(&self, f: F) -> T {
+        self.span.in_scope(f)
+    }
+
+    /// Runs a future within the span.
+    #[cfg(feature = "reqwest_async")]
+    pub(crate) fn instrument<F: Future>(&self, future: F) -> Instrumented<F> {
+        future.instrument(self.span.clone())
+    }
+
+    /// Runs a `futures` 0.1 future within the span, entering it each time
+    /// the future is polled.
+    #[cfg(feature = "hyper")]
+    pub(crate) fn instrument_futures<F>(&self, future: F) -> InScope<F> {
+        InScope {
+            future,
+            span: self.clone(),
+        }
+    }
+
+    /// Records the response received, or why none was.
+    pub(crate) fn record(&self, outcome: &Result<HttpResponse>) {
+        match *outcome {
+            Ok(ref response) => {
+                self.span.record("status", response.status);
+                self.span.record("bytes", response.body.len() as u64);
+            },
+            Err(ref why) => {
+                self.span.record("error", field::display(why));
+            },
+        }
+    }
+
+    /// Decodes a response within the span, recording how long it took and
+    /// why it failed, if it did.
+    pub(crate) fn decode<T: DeserializeOwned>(&self, response: HttpResponse)
+        -> Result<T> {
+        self.in_scope(|| {
+            let started = Instant::now();
+            let decoded = endpoint::decode(response);
+            let elapsed = started.elapsed().as_micros() as u64;
+
+            self.span.record("decode_micros", elapsed);
+
+            if let Err(ref why) = decoded {
+                self.span.record("error", field::display(why));
+            }
+
+            decoded
+        })
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+impl RequestSpan {
+    pub(crate) fn new(_: &HttpRequest, _: &str) -> Self {
+        RequestSpan {}
+    }
+
+    #[cfg(any(feature = "hyper", feature = "reqwest"))]
+    pub(crate) fn in_scope<T, F: FnOnce() -> T>(&self, f: F) -> T {
+        f()
+    }
+
+    #[cfg(feature = "reqwest_async")]
+    pub(crate) fn instrument<F: Future>(&self, future: F) -> F {
+        future
+    }
+
+    #[cfg(feature = "hyper")]
+    pub(crate) fn instrument_futures<F>(&self, future: F) -> F {
+        future
+    }
+
+    pub(crate) fn record(&self, _: &Result<HttpResponse>) {}
+
+    pub(crate) fn decode<T: DeserializeOwned>(&self, response: HttpResponse)
+        -> Result<T> {
+        endpoint::decode(response)
+    }
+}
+
+/// A `futures` 0.1 future that is polled within the span of its request.
+#[cfg(all(feature = "tracing", feature = "hyper"))]
+pub(crate) struct InScope<F> {
+    future: F,
+    span: RequestSpan,
+}
+
+#[cfg(all(feature = "tracing", feature = "hyper"))]
+impl<F: futures::Future> futures::Future for InScope<F> {
+    type Item = F::Item;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let future = &mut self.future;
+
+        self.span.in_scope(|| future.poll())
+    }
+}
+
+/// Records that the request of the current span is being retried.
+#[cfg(all(
+    feature = "tracing",
+    any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"),
+))]
+pub(crate) fn retry(attempt: u32, delay: Duration) {
+    Span::current().record("attempt", attempt);
+    tracing::debug!(
+        attempt,
+        delay_ms = delay.as_millis() as u64,
+        "retrying request",
+    );
+}
+
+#[cfg(all(
+    not(feature = "tracing"),
+    any(feature = "hyper", feature = "reqwest", feature = "reqwest_async"),
+))]
+pub(crate) fn retry(_: u32, _: Duration) {}
@@ -0,0 +1,295 @@
+//! Abstractions over the HTTP clients that requests are sent with.
+//!
+//! Every endpoint is defined once in terms of an [`HttpRequest`] and decoded
+//! from an [`HttpResponse`], so any HTTP client implementing
+//! [`HttpTransport`] - or [`AsyncHttpTransport`] for asynchronous clients,
+//! and [`FuturesHttpTransport`] for clients built on `futures` 0.1 - can be
+//! wrapped in a [`KitsuClient`] to receive all of the endpoints.
+//!
+//! [`AsyncHttpTransport`]: trait.AsyncHttpTransport.html
+//! [`FuturesHttpTransport`]: trait.FuturesHttpTransport.html
+//! [`HttpRequest`]: struct.HttpRequest.html
+//! [`HttpResponse`]: struct.HttpResponse.html
+//! [`HttpTransport`]: trait.HttpTransport.html
+//! [`KitsuClient`]: ../client/struct.KitsuClient.html
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use crate::Result;
+
+#[cfg(feature = "hyper")]
+use crate::Error;
+#[cfg(feature = "reqwest_async")]
+use std::future::Future;
+#[cfg(feature = "reqwest_async")]
+use std::pin::Pin;
+
+/// A boxed future returned by an [`AsyncHttpTransport`].
+///
+/// [`AsyncHttpTransport`]: trait.AsyncHttpTransport.html
+#[cfg(feature = "reqwest_async")]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// The method of an [`HttpRequest`].
+///
+/// [`HttpRequest`]: struct.HttpRequest.html
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Method {
+    /// Indicator that the request deletes a resource.
+    Delete,
+    /// Indicator that the request retrieves a resource.
+    Get,
+    /// Indicator that the request updates a resource.
+    Patch,
+    /// Indicator that the request creates a resource.
+    Post,
+}
+
+impl Method {
+    /// The name of the method.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kitsu::transport::Method;
+    ///
+    /// assert_eq!(Method::Get.name(), "GET");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Method::Delete => "DELETE",
+            Method::Get => "GET",
+            Method::Patch => "PATCH",
+            Method::Post => "POST",
+        }
+    }
+}
+
+impl Display for Method {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(self.name())
+    }
+}
+
+/// A request to send to the API.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HttpRequest {
+    /// The body of the request, if there is one.
+    pub body: Option<Vec<u8>>,
+    /// The headers to send, in order, as pairs of names and values.
+    pub headers: Vec<(String, String)>,
+    /// The method of the request.
+    pub method: Method,
+    /// The full URL to send the request to.
+    pub url: String,
+}
+
+impl HttpRequest {
+    /// Creates a request with no headers or body.
+    pub fn new(method: Method, url: String) -> Self {
+        HttpRequest {
+            body: None,
+            headers: Vec::new(),
+            method,
+            url,
+        }
+    }
+
+    /// Retrieves the value of the first header with the given name, ignoring
+    /// ASCII case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+}
+
+/// A response received from the API.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HttpResponse {
+    /// The raw body of the response.
+    pub body: Vec<u8>,
+    /// The headers received, as pairs of names and values.
+    pub headers: Vec<(String, String)>,
+    /// The HTTP status code of the response.
+    pub status: u16,
+}
+
+impl HttpResponse {
+    /// Retrieves the value of the first header with the given name, ignoring
+    /// ASCII case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    /// Whether the status code indicates success (2xx).
+    pub fn is_success(&self) -> bool {
+        self.status >= 200 && self.status < 300
+    }
+}
+
+/// A blocking HTTP client that requests can be sent with.
+///
+/// This is implemented for the `reqwest` Client when the `reqwest-support`
+/// feature is enabled.
+pub trait HttpTransport {
+    /// Sends a request, returning the response regardless of its status code.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Transport`] if the request could not be sent or the
+    /// response could not be received.
+    ///
+    /// [`Error::Transport`]: ../enum.Error.html#variant.Transport
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse>;
+
+    /// Waits for a duration before a request is retried or allowed by a rate
+    /// limit.
+    ///
+    /// This sleeps the current thread by default.
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+impl<T: HttpTransport + ?Sized> HttpTransport for &T {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        (**self).send(request)
+    }
+
+    fn sleep(&self, duration: Duration) {
+        (**self).sleep(duration)
+    }
+}
+
+impl<T: HttpTransport + ?Sized> HttpTransport for Arc<T> {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        (**self).send(request)
+    }
+
+    fn sleep(&self, duration: Duration) {
+        (**self).sleep(duration)
+    }
+}
+
+/// An asynchronous HTTP client that requests can be sent with.
+///
+/// This is implemented for the asynchronous `reqwest` Client when the
+/// `async-support` feature is enabled.
+#[cfg(feature = "reqwest_async")]
+pub trait AsyncHttpTransport: Send + Sync {
+    /// Sends a request, resolving to the response regardless of its status
+    /// code.
+    ///
+    /// # Errors
+    ///
+    /// Resolves to [`Error::Transport`] if the request could not be sent or
+    /// the response could not be received.
+    ///
+    /// [`Error::Transport`]: ../enum.Error.html#variant.Transport
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>>;
+
+    /// Resolves after a duration, before a request is retried or allowed by
+    /// a rate limit.
+    ///
+    /// This waits on a `tokio` timer by default.
+    fn sleep(&self, duration: Duration) -> BoxFuture<'_, ()> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+#[cfg(feature = "reqwest_async")]
+impl<T: AsyncHttpTransport + ?Sized> AsyncHttpTransport for &T {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+        (**self).send(request)
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'_, ()> {
+        (**self).sleep(duration)
+    }
+}
+
+#[cfg(feature = "reqwest_async")]
+impl<T: AsyncHttpTransport + ?Sized> AsyncHttpTransport for Arc<T> {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+        (**self).send(request)
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'_, ()> {
+        (**self).sleep(duration)
+    }
+}
+
+/// An asynchronous HTTP client built on `futures` 0.1 that requests can be
+/// sent with.
+///
+/// This is implemented for the `hyper` Client when the `hyper-support`
+/// feature is enabled.
+#[cfg(feature = "hyper")]
+pub trait FuturesHttpTransport {
+    /// Sends a request, resolving to the response regardless of its status
+    /// code.
+    ///
+    /// # Errors
+    ///
+    /// Resolves to [`Error::Transport`] if the request could not be sent or
+    /// the response could not be received.
+    ///
+    /// [`Error::Transport`]: ../enum.Error.html#variant.Transport
+    fn send(&self, request: HttpRequest)
+        -> Box<dyn futures::Future<Item = HttpResponse, Error = Error>>;
+
+    /// Resolves after a duration, before a request is retried or allowed by
+    /// a rate limit.
+    ///
+    /// `futures` 0.1 has no timer of its own, so this should wait on the
+    /// timer of the event loop the transport runs on, as the `hyper` Client
+    /// does. Transports without one can use [`thread_sleep`].
+    ///
+    /// [`thread_sleep`]: fn.thread_sleep.html
+    fn sleep(&self, duration: Duration)
+        -> Box<dyn futures::Future<Item = (), Error = Error>>;
+}
+
+#[cfg(feature = "hyper")]
+impl<T: FuturesHttpTransport + ?Sized> FuturesHttpTransport for Arc<T> {
+    fn send(&self, request: HttpRequest)
+        -> Box<dyn futures::Future<Item = HttpResponse, Error = Error>> {
+        (**self).send(request)
+    }
+
+    fn sleep(&self, duration: Duration)
+        -> Box<dyn futures::Future<Item = (), Error = Error>> {
+        (**self).sleep(duration)
+    }
+}
+
+/// Resolves after a duration by sleeping a thread spawned for it, for
+/// [`FuturesHttpTransport`]s without a timer to wait on.
+///
+/// This works whichever executor the future is run on, at the cost of a
+/// thread for every wait.
+///
+/// [`FuturesHttpTransport`]: trait.FuturesHttpTransport.html
+#[cfg(feature = "hyper")]
+pub fn thread_sleep(duration: Duration)
+    -> Box<dyn futures::Future<Item = (), Error = Error>> {
+    let (sender, receiver) = futures::sync::oneshot::channel();
+
+    thread::spawn(move || {
+        thread::sleep(duration);
+        let _ = sender.send(());
+    });
+
+    Box::new(futures::Future::map_err(receiver, |why| {
+        Error::Transport(Box::new(why))
+    }))
+}
+
+fn find_header<'a>(headers: &'a [(String, String)], name: &str)
+    -> Option<&'a str> {
+    headers.iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
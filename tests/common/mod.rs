@@ -0,0 +1,196 @@
+//! Fixtures shared by the integration tests.
+//!
+//! Each test crate only uses some of them.
+#![allow(dead_code, unused_macros)]
+
+use kitsu::auth::Token;
+use kitsu::testing::MockTransport;
+use kitsu::transport::Method;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A single anime, with an ID of 1.
+pub const ANIME: &[u8] = include_bytes!("../fixtures/anime.json");
+
+/// The current time, in seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+/// A token created at a time, with a refresh token named after its access
+/// token.
+pub fn token(access_token: &str, created_at: u64) -> Token {
+    Token {
+        access_token: access_token.to_owned(),
+        created_at,
+        expires_in: 2_592_000,
+        refresh_token: Some(format!("{}-refresh", access_token)),
+        scope: Some("public".to_owned()),
+        token_type: "bearer".to_owned(),
+    }
+}
+
+/// The body of a token endpoint response with a new token.
+pub fn token_body(access_token: &str) -> Vec<u8> {
+    serde_json::to_vec(&token(access_token, now())).unwrap()
+}
+
+/// A transport responding to every request for the anime with an ID of 1.
+pub fn anime_transport() -> Arc<MockTransport> {
+    let transport = MockTransport::new();
+    transport.respond(Method::Get, "/anime/1", 200, ANIME);
+
+    Arc::new(transport)
+}
+
+/// Rounds waits to whole seconds.
+///
+/// Waits for a rate limit of one request per second are just under whole
+/// seconds, by however long the test took to run between requests.
+pub fn rounded_secs(waits: &[Duration]) -> Vec<u64> {
+    waits.iter().map(|wait| wait.as_secs_f64().round() as u64).collect()
+}
+
+/// A genre resource with an ID.
+pub fn genre(id: u64) -> Value {
+    json!({
+        "id": id.to_string(),
+        "type": "genres",
+        "attributes": {
+            "createdAt": "2013-02-20T16:00:15.623Z",
+            "updatedAt": "2017-05-31T06:38:27.734Z",
+            "name": format!("Genre {}", id),
+            "slug": format!("genre-{}", id),
+            "description": null
+        }
+    })
+}
+
+/// The body of a page of genres, linking to the path of the next page if
+/// there is one.
+pub fn page(ids: &[u64], next: Option<&str>) -> Vec<u8> {
+    let mut links = json!({});
+
+    if let Some(next) = next {
+        links["next"] = json!(format!("https://kitsu.io/api/edge{}", next));
+    }
+
+    let data = ids.iter().map(|&id| genre(id)).collect::<Vec<_>>();
+
+    serde_json::to_vec(&json!({ "data": data, "links": links })).unwrap()
+}
+
+/// A transport serving five genres two at a time, by `next` link or by
+/// offset.
+pub fn genre_pages() -> MockTransport {
+    let transport = MockTransport::new();
+    transport
+        .respond(
+            Method::Get,
+            "/genres?page[limit]=2",
+            200,
+            page(&[1, 2], Some("/genres?page[limit]=2&page[offset]=2")),
+        )
+        .respond(
+            Method::Get,
+            "/genres?page[limit]=2&page[offset]=0",
+            200,
+            page(&[1, 2], Some("/genres?page[limit]=2&page[offset]=2")),
+        )
+        .respond(
+            Method::Get,
+            "/genres?page[limit]=2&page[offset]=2",
+            200,
+            page(&[3, 4], Some("/genres?page[limit]=2&page[offset]=4")),
+        )
+        .respond(
+            Method::Get,
+            "/genres?page[limit]=2&page[offset]=4",
+            200,
+            page(&[5], None),
+        );
+
+    transport
+}
+
+/// A transport serving 25 genres, cut to the API's maximum of 20 per page
+/// however many are requested.
+pub fn capped_genre_pages() -> MockTransport {
+    let first = (1..=20).collect::<Vec<_>>();
+    let rest = (21..=25).collect::<Vec<_>>();
+    let transport = MockTransport::new();
+    transport
+        .respond(
+            Method::Get,
+            "/genres?page[limit]=20&page[offset]=0",
+            200,
+            page(&first, Some("/genres?page[limit]=20&page[offset]=20")),
+        )
+        .respond(
+            Method::Get,
+            "/genres?page[limit]=20&page[offset]=20",
+            200,
+            page(&rest, None),
+        );
+
+    transport
+}
+
+/// Defines tests once for every bridge that is enabled: as `#[test]`s in a
+/// `blocking` module, `#[tokio::test]`s in an `asynchronous` module, and
+/// `#[test]`s in a `hyper` module.
+///
+/// The items of the enclosing module, the imports listed before the tests,
+/// and the bridge's requester trait are in scope of each test, and `send!`
+/// waits for a requester method's result. The `hyper` bridge needs a
+/// transport it can clone, so tests wrap theirs in an `Arc`.
+macro_rules! bridge_tests {
+    (
+        $(use $root:ident $(::$segment:ident)*;)*
+        $($(#[$attr:meta])* fn $name:ident() $body:block)*
+    ) => {
+        #[cfg(feature = "reqwest")]
+        mod blocking {
+            #[allow(unused_imports)]
+            use super::*;
+            use kitsu::KitsuReqwestRequester;
+            $(use $root $(::$segment)*;)*
+
+            macro_rules! send {
+                ($request:expr) => { $request };
+            }
+
+            $($(#[$attr])* #[test] fn $name() $body)*
+        }
+
+        #[cfg(feature = "async-support")]
+        mod asynchronous {
+            #[allow(unused_imports)]
+            use super::*;
+            use kitsu::KitsuAsyncRequester;
+            $(use $root $(::$segment)*;)*
+
+            macro_rules! send {
+                ($request:expr) => { $request.await };
+            }
+
+            $($(#[$attr])* #[tokio::test] async fn $name() $body)*
+        }
+
+        #[cfg(feature = "hyper-support")]
+        mod hyper {
+            #[allow(unused_imports)]
+            use super::*;
+            use futures::Future;
+            use kitsu::KitsuHyperRequester;
+            $(use $root $(::$segment)*;)*
+
+            macro_rules! send {
+                ($request:expr) => { $request.wait() };
+            }
+
+            $($(#[$attr])* #[test] fn $name() $body)*
+        }
+    };
+}
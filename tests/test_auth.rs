@@ -0,0 +1,260 @@
+#![cfg(all(feature = "reqwest-support", feature = "testing"))]
+
+extern crate kitsu;
+extern crate serde_json;
+
+use kitsu::auth::{Auth, FileTokenStore, MemoryTokenStore, TokenStore, TOKEN_URL};
+use kitsu::testing::MockTransport;
+use kitsu::transport::{HttpRequest, HttpResponse, HttpTransport, Method};
+use kitsu::{Error, KitsuClient, KitsuReqwestRequester, Result};
+use std::env;
+use std::fs;
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+mod common;
+
+use common::{now, token, token_body};
+
+const USER: &[u8] = include_bytes!("fixtures/user.json");
+
+/// A transport responding with status code 401 to the requests that `reject`
+/// returns `true` for, sending the others with a mock transport.
+struct Rejecting<F> {
+    inner: MockTransport,
+    reject: F,
+}
+
+impl<F: Fn(&HttpRequest) -> bool> HttpTransport for Rejecting<F> {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        if (self.reject)(&request) {
+            return Ok(HttpResponse {
+                body: Vec::new(),
+                headers: Vec::new(),
+                status: 401,
+            });
+        }
+
+        self.inner.send(request)
+    }
+}
+
+fn grants(transport: &MockTransport) -> usize {
+    transport.requests()
+        .iter()
+        .filter(|request| request.method == Method::Post)
+        .count()
+}
+
+#[test]
+fn test_password_grant() {
+    let transport = MockTransport::new();
+    transport.respond(Method::Post, TOKEN_URL, 200, token_body("first"));
+    transport.respond(Method::Get, "/users/1", 200, USER);
+
+    let client = KitsuClient::new(&transport)
+        .auth(Auth::password("user@example.com", "hunter2"));
+    client.get_user(1).unwrap();
+    client.get_user(1).unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 3);
+
+    let grant: serde_json::Value =
+        serde_json::from_slice(requests[0].body.as_ref().unwrap()).unwrap();
+    assert_eq!(grant["grant_type"], "password");
+    assert_eq!(grant["username"], "user@example.com");
+    assert_eq!(grant["password"], "hunter2");
+    assert_eq!(requests[0].header("Authorization"), None);
+
+    assert_eq!(requests[1].header("Authorization"), Some("Bearer first"));
+    assert_eq!(requests[2].header("Authorization"), Some("Bearer first"));
+    assert_eq!(client.auth_token().unwrap().unwrap().access_token, "first");
+}
+
+#[test]
+fn test_refresh_expired_token() {
+    let transport = MockTransport::new();
+    transport.respond(Method::Post, TOKEN_URL, 200, token_body("second"));
+    transport.respond(Method::Get, "/users/1", 200, USER);
+
+    let expired = token("first", now() - 2_592_000);
+    assert!(expired.is_expired());
+
+    let client = KitsuClient::new(&transport).auth(Auth::token(expired));
+    client.get_user(1).unwrap();
+
+    let requests = transport.requests();
+    let grant: serde_json::Value =
+        serde_json::from_slice(requests[0].body.as_ref().unwrap()).unwrap();
+    assert_eq!(grant["grant_type"], "refresh_token");
+    assert_eq!(grant["refresh_token"], "first-refresh");
+    assert_eq!(requests[1].header("Authorization"), Some("Bearer second"));
+}
+
+#[test]
+fn test_refresh_on_unauthorized() {
+    let transport = MockTransport::new();
+    transport.respond(Method::Post, TOKEN_URL, 200, token_body("second"));
+    transport
+        .respond(Method::Get, "/users/1", 401, "")
+        .respond(Method::Get, "/users/1", 200, USER);
+
+    let client = KitsuClient::new(&transport)
+        .auth(Auth::token(token("first", now())));
+    client.get_user(1).unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0].header("Authorization"), Some("Bearer first"));
+    assert_eq!(requests[1].method, Method::Post);
+    assert_eq!(requests[2].header("Authorization"), Some("Bearer second"));
+}
+
+#[test]
+fn test_concurrent_rejections_refresh_once() {
+    let inner = MockTransport::new();
+    inner.respond(Method::Post, TOKEN_URL, 200, token_body("second"));
+    inner.respond(Method::Get, "/users/1", 200, USER);
+
+    // Both requests are rejected only once both have been sent with the
+    // first token, so that they renew it at the same time.
+    let barrier = Barrier::new(2);
+    let transport = Arc::new(Rejecting {
+        inner,
+        reject: move |request: &HttpRequest| {
+            if request.header("Authorization") != Some("Bearer first") {
+                return false;
+            }
+
+            barrier.wait();
+
+            true
+        },
+    });
+    let client = KitsuClient::new(Arc::clone(&transport))
+        .auth(Auth::token(token("first", now())));
+
+    let threads = (0..2)
+        .map(|_| {
+            let client = client.clone();
+
+            thread::spawn(move || client.get_user(1).map(|_| ()))
+        })
+        .collect::<Vec<_>>();
+
+    for thread in threads {
+        thread.join().unwrap().unwrap();
+    }
+
+    assert_eq!(grants(&transport.inner), 1);
+    assert_eq!(client.auth_token().unwrap().unwrap().access_token, "second");
+}
+
+#[test]
+fn test_rejection_uses_token_saved_elsewhere() {
+    let path = env::temp_dir()
+        .join(format!("kitsu-auth-{}", std::process::id()))
+        .join("rotated.json");
+    let _ = fs::remove_file(&path);
+    let store = FileTokenStore::new(&path);
+    store.save(&token("first", now())).unwrap();
+
+    // Another process renews the token after this one sent its request.
+    let other = FileTokenStore::new(&path);
+    let transport = Rejecting {
+        inner: MockTransport::new(),
+        reject: move |request: &HttpRequest| {
+            if request.header("Authorization") != Some("Bearer first") {
+                return false;
+            }
+
+            other.save(&token("second", now())).unwrap();
+
+            true
+        },
+    };
+    transport.inner.respond(Method::Get, "/users/1", 200, USER);
+
+    let client = KitsuClient::new(&transport).auth(Auth::from_store(store));
+    client.get_user(1).unwrap();
+
+    let requests = transport.inner.requests();
+    assert_eq!(grants(&transport.inner), 0);
+    assert_eq!(requests[0].header("Authorization"), Some("Bearer second"));
+}
+
+#[test]
+fn test_password_grant_after_failed_refresh() {
+    let transport = MockTransport::new();
+    transport
+        .respond(Method::Post, TOKEN_URL, 400, r#"{"error": "invalid_grant"}"#)
+        .respond(Method::Post, TOKEN_URL, 200, token_body("second"));
+    transport.respond(Method::Get, "/users/1", 200, USER);
+
+    let expired = token("first", now() - 2_592_000);
+    let auth = Auth::password("user@example.com", "hunter2")
+        .store(MemoryTokenStore::with_token(expired));
+    let client = KitsuClient::new(&transport).auth(auth);
+    client.get_user(1).unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 3);
+
+    let grants = requests[..2].iter()
+        .map(|request| {
+            serde_json::from_slice::<serde_json::Value>(
+                request.body.as_ref().unwrap(),
+            ).unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(grants[0]["grant_type"], "refresh_token");
+    assert_eq!(grants[0]["refresh_token"], "first-refresh");
+    assert_eq!(grants[1]["grant_type"], "password");
+    assert_eq!(grants[1]["password"], "hunter2");
+    assert_eq!(requests[2].header("Authorization"), Some("Bearer second"));
+}
+
+#[test]
+fn test_invalid_grant() {
+    let transport = MockTransport::new();
+    transport.respond(Method::Post, TOKEN_URL, 401, r#"{
+        "error": "invalid_grant",
+        "error_description": "The provided authorization grant is invalid."
+    }"#);
+
+    let client = KitsuClient::new(&transport)
+        .auth(Auth::password("user@example.com", "wrong"));
+
+    match client.get_user(1) {
+        Err(Error::Unauthorized(response)) => {
+            assert_eq!(response.errors[0].code.as_deref(), Some("invalid_grant"));
+        },
+        other => panic!("Expected Unauthorized, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_client_credentials() {
+    let transport = MockTransport::new();
+    transport.respond(Method::Post, TOKEN_URL, 200, token_body("first"));
+    transport.respond(Method::Get, "/users/1", 200, USER);
+
+    let auth = Auth::password("user@example.com", "hunter2")
+        .client("id", "secret");
+    KitsuClient::new(&transport).auth(auth).get_user(1).unwrap();
+
+    let requests = transport.requests();
+    let grant: serde_json::Value =
+        serde_json::from_slice(requests[0].body.as_ref().unwrap()).unwrap();
+    assert_eq!(grant["client_id"], "id");
+    assert_eq!(grant["client_secret"], "secret");
+}
+
+#[test]
+fn test_debug_redacts_secrets() {
+    let auth = Auth::token(token("first", now()));
+    let debug = format!("{:?}", auth);
+
+    assert!(!debug.contains("first"));
+}
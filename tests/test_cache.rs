@@ -0,0 +1,342 @@
+#![cfg(feature = "testing")]
+
+extern crate kitsu;
+
+#[macro_use]
+mod common;
+
+use common::ANIME;
+use kitsu::cache::{CacheStore, CachedResponse, FileCacheStore, MemoryCacheStore};
+use kitsu::transport::HttpResponse;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn response(status: u16, headers: &[(&str, &str)], body: &[u8]) -> HttpResponse {
+    HttpResponse {
+        body: body.to_vec(),
+        headers: headers.iter()
+            .map(|&(name, value)| (name.to_owned(), value.to_owned()))
+            .collect(),
+        status,
+    }
+}
+
+fn entry(body: &[u8]) -> CachedResponse {
+    CachedResponse::from_response(&response(
+        200,
+        &[("Cache-Control", "max-age=60")],
+        body,
+    )).unwrap()
+}
+
+/// A new directory for a test, which may be named after the test's module.
+fn directory(name: &str) -> PathBuf {
+    let path = env::temp_dir()
+        .join(format!("kitsu-cache-{}", std::process::id()))
+        .join(name.replace("::", "-"));
+    let _ = fs::remove_dir_all(&path);
+
+    path
+}
+
+#[test]
+fn test_cache_control() {
+    let fresh = entry(ANIME);
+    assert!(fresh.is_fresh());
+
+    let etag = [("ETag", "\"abc\""), ("Cache-Control", "no-cache")];
+    let revalidated = CachedResponse::from_response(&response(200, &etag, ANIME)).unwrap();
+    assert!(!revalidated.is_fresh());
+    assert_eq!(revalidated.etag.as_deref(), Some("\"abc\""));
+
+    let no_store = [("ETag", "\"abc\""), ("Cache-Control", "public, no-store")];
+    assert!(CachedResponse::from_response(&response(200, &no_store, ANIME)).is_none());
+    assert!(CachedResponse::from_response(&response(200, &[], ANIME)).is_none());
+
+    let vary_all = [("Cache-Control", "max-age=60"), ("Vary", "*")];
+    assert!(CachedResponse::from_response(&response(200, &vary_all, ANIME)).is_none());
+}
+
+#[test]
+fn test_memory_store_evicts_least_recently_used() {
+    let store = MemoryCacheStore::new(2);
+    store.put("a", &entry(b"a")).unwrap();
+    store.put("b", &entry(b"b")).unwrap();
+
+    // Using "a" makes "b" the least recently used.
+    assert!(store.get("a").unwrap().is_some());
+    store.put("c", &entry(b"c")).unwrap();
+
+    assert_eq!(store.len(), 2);
+    assert!(store.get("b").unwrap().is_none());
+    assert_eq!(store.get("a").unwrap().unwrap().body, b"a");
+    assert_eq!(store.get("c").unwrap().unwrap().body, b"c");
+
+    store.remove("a").unwrap();
+    assert_eq!(store.len(), 1);
+}
+
+#[test]
+fn test_file_store() {
+    let path = directory("store");
+    let store = FileCacheStore::new(&path);
+    let url = "https://kitsu.io/api/edge/anime/1";
+    let stored = entry(ANIME);
+
+    assert!(store.get(url).unwrap().is_none());
+    store.put(url, &stored).unwrap();
+
+    let reopened = FileCacheStore::new(&path);
+    assert_eq!(reopened.get(url).unwrap(), Some(stored));
+    assert!(reopened.get("https://kitsu.io/api/edge/anime/2").unwrap().is_none());
+
+    reopened.remove(url).unwrap();
+    reopened.remove(url).unwrap();
+    assert!(store.get(url).unwrap().is_none());
+}
+
+bridge_tests! {
+    use kitsu::auth::Auth;
+    use kitsu::cache::HttpCache;
+    use kitsu::model::LibraryEntry;
+    use kitsu::testing::MockTransport;
+    use kitsu::transport::Method;
+    use kitsu::KitsuClient;
+    use std::sync::Arc;
+
+    fn test_fresh_response_is_reused() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond_with(
+            Method::Get,
+            "/anime/1",
+            response(200, &[("Cache-Control", "max-age=300")], ANIME),
+        );
+
+        let client = KitsuClient::new(Arc::clone(&transport))
+            .cache(HttpCache::memory(10));
+        send!(client.get_anime(1)).unwrap();
+        let anime = send!(client.get_anime(1)).unwrap();
+
+        assert_eq!(anime.data.attributes.canonical_title, "Cowboy Bebop");
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    fn test_stale_response_is_revalidated() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond_with(
+                Method::Get,
+                "/anime/1",
+                response(200, &[("ETag", "W/\"v1\""), ("Cache-Control", "no-cache")], ANIME),
+            )
+            .respond_with(
+                Method::Get,
+                "/anime/1",
+                response(304, &[("ETag", "W/\"v1\"")], b""),
+            );
+
+        let client = KitsuClient::new(Arc::clone(&transport))
+            .cache(HttpCache::memory(10));
+        send!(client.get_anime(1)).unwrap();
+        let anime = send!(client.get_anime(1)).unwrap();
+
+        assert_eq!(anime.data.attributes.canonical_title, "Cowboy Bebop");
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].header("If-None-Match"), None);
+        assert_eq!(requests[1].header("If-None-Match"), Some("W/\"v1\""));
+    }
+
+    fn test_not_modified_response_is_fresh_again() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond_with(
+                Method::Get,
+                "/anime/1",
+                response(200, &[("ETag", "\"v1\"")], ANIME),
+            )
+            .respond_with(
+                Method::Get,
+                "/anime/1",
+                response(304, &[("Cache-Control", "max-age=300")], b""),
+            );
+
+        let client = KitsuClient::new(Arc::clone(&transport))
+            .cache(HttpCache::memory(10));
+
+        for _ in 0..3 {
+            let anime = send!(client.get_anime(1)).unwrap();
+            assert_eq!(anime.data.id, "1");
+        }
+
+        // The 304 made the stored response fresh again.
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].header("If-None-Match"), Some("\"v1\""));
+    }
+
+    fn test_bare_not_modified_response_keeps_policy() {
+        let url = "https://kitsu.io/api/edge/anime/1";
+        let cache = HttpCache::memory(10).private(true);
+        let mut stale = CachedResponse::from_response(&response(
+            200,
+            &[("ETag", "\"v1\""), ("Cache-Control", "private, max-age=300")],
+            ANIME,
+        )).unwrap();
+        stale.expires_at = 0;
+        cache.store().put(url, &stale).unwrap();
+
+        let transport = Arc::new(MockTransport::new());
+        transport.respond_with(
+            Method::Get,
+            "/anime/1",
+            response(304, &[("ETag", "\"v1\"")], b""),
+        );
+
+        let client = KitsuClient::new(Arc::clone(&transport))
+            .cache(cache.clone());
+
+        for _ in 0..3 {
+            send!(client.get_anime(1)).unwrap();
+        }
+
+        // The 304 had no freshness of its own, so the stored max-age made the
+        // response fresh again.
+        assert_eq!(transport.requests().len(), 1);
+
+        let stored = cache.store().get(url).unwrap().unwrap();
+        assert!(stored.is_fresh());
+        assert!(stored.policy.private);
+    }
+
+    fn test_uncacheable_response_is_not_stored() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond(Method::Get, "/anime/1", 200, ANIME);
+
+        let client = KitsuClient::new(Arc::clone(&transport))
+            .cache(HttpCache::memory(10));
+        send!(client.get_anime(1)).unwrap();
+        send!(client.get_anime(1)).unwrap();
+
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    fn test_private_response_is_not_stored() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond_with(
+                Method::Get,
+                "/anime/1",
+                response(200, &[("Cache-Control", "private, max-age=300")], ANIME),
+            )
+            .respond_with(
+                Method::Get,
+                "/anime/2",
+                response(
+                    200,
+                    &[("Cache-Control", "max-age=300"), ("Vary", "Accept, Authorization")],
+                    ANIME,
+                ),
+            );
+
+        let cache = HttpCache::memory(10);
+        let stored = |id: u64| {
+            let url = format!("https://kitsu.io/api/edge/anime/{}", id);
+
+            cache.store().get(&url).unwrap().is_some()
+        };
+
+        let client = KitsuClient::new(Arc::clone(&transport)).cache(cache.clone());
+        send!(client.get_anime(1)).unwrap();
+        send!(client.get_anime(2)).unwrap();
+        assert!(!stored(1) && !stored(2));
+
+        let client = KitsuClient::new(Arc::clone(&transport))
+            .cache(cache.clone().private(true));
+        send!(client.get_anime(1)).unwrap();
+        send!(client.get_anime(2)).unwrap();
+        assert!(stored(1) && stored(2));
+    }
+
+    fn test_authorized_requests_bypass_cache() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond_with(
+            Method::Get,
+            "/anime/1",
+            response(200, &[("Cache-Control", "max-age=300")], ANIME),
+        );
+
+        let url = "https://kitsu.io/api/edge/anime/1";
+        let cache = HttpCache::memory(10);
+        let anonymous = KitsuClient::new(Arc::clone(&transport)).cache(cache.clone());
+        send!(anonymous.get_anime(1)).unwrap();
+        assert!(cache.store().get(url).unwrap().is_some());
+
+        // A stored response to an anonymous request is not returned for an
+        // authenticated one, which may see more.
+        let authorized = KitsuClient::new(Arc::clone(&transport))
+            .header("Authorization", "Bearer token")
+            .cache(cache.clone());
+        send!(authorized.get_anime(1)).unwrap();
+        assert_eq!(transport.requests().len(), 2);
+
+        let cache = HttpCache::memory(10);
+        let authorized = KitsuClient::new(Arc::clone(&transport))
+            .auth(Auth::token(common::token("token", common::now())))
+            .cache(cache.clone());
+        send!(authorized.get_anime(1)).unwrap();
+        assert!(cache.store().get(url).unwrap().is_none());
+
+        let authorized = KitsuClient::new(Arc::clone(&transport))
+            .auth(Auth::token(common::token("token", common::now())))
+            .cache(cache.clone().private(true));
+        send!(authorized.get_anime(1)).unwrap();
+        send!(authorized.get_anime(1)).unwrap();
+        assert!(cache.store().get(url).unwrap().is_some());
+        assert_eq!(transport.requests().len(), 4);
+    }
+
+    fn test_update_invalidates_response() {
+        let library_entry = &include_bytes!("fixtures/library_entry.json")[..];
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond_with(
+                Method::Get,
+                "/library-entries/5",
+                response(200, &[("Cache-Control", "max-age=300")], library_entry),
+            )
+            .respond(Method::Patch, "/library-entries/5", 200, library_entry);
+
+        let cache = HttpCache::memory(10);
+        let client = KitsuClient::new(Arc::clone(&transport)).cache(cache.clone());
+        let url = "https://kitsu.io/api/edge/library-entries/5";
+
+        send!(client.get_resource::<LibraryEntry, _>(5, |f| f)).unwrap();
+        assert!(cache.store().get(url).unwrap().is_some());
+
+        send!(client.update_library_entry(5, |f| f.progress(2))).unwrap();
+        assert!(cache.store().get(url).unwrap().is_none());
+    }
+
+    fn test_file_cache_shared_between_clients() {
+        let path = directory(module_path!());
+        let transport = Arc::new(MockTransport::new());
+        transport.respond_with(
+            Method::Get,
+            "/anime/1",
+            response(200, &[("Cache-Control", "max-age=300")], ANIME),
+        );
+
+        let first = KitsuClient::new(Arc::clone(&transport))
+            .cache(HttpCache::new(FileCacheStore::new(&path)));
+        send!(first.get_anime(1)).unwrap();
+
+        let second = KitsuClient::new(Arc::clone(&transport))
+            .cache(HttpCache::new(FileCacheStore::new(&path)));
+        send!(second.get_anime(1)).unwrap();
+
+        assert_eq!(transport.requests().len(), 1);
+    }
+}
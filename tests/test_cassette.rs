@@ -0,0 +1,181 @@
+#![cfg(all(
+    feature = "testing",
+    any(feature = "reqwest", feature = "async-support", feature = "hyper-support"),
+))]
+
+extern crate kitsu;
+
+#[macro_use]
+mod common;
+
+use common::ANIME;
+use kitsu::auth::{Auth, TOKEN_URL};
+use kitsu::testing::{Cassette, MockTransport, Mode};
+use kitsu::transport::{HttpRequest, HttpTransport, Method};
+use kitsu::{Error, KitsuClient};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const MANGA: &[u8] = include_bytes!("fixtures/manga.json");
+const TOKEN: &str = r#"{
+    "access_token": "access-secret",
+    "expires_in": 2592000,
+    "refresh_token": "refresh-secret",
+    "token_type": "bearer"
+}"#;
+
+/// A new path for a cassette, which may be named after the test's module.
+fn path(name: &str) -> PathBuf {
+    let path = env::temp_dir()
+        .join(format!("kitsu-cassettes-{}", std::process::id()))
+        .join(format!("{}.json", name.replace("::", "-")));
+    let _ = fs::remove_file(&path);
+
+    path
+}
+
+/// Records requests for an anime and a manga, sent with secret headers.
+fn record(name: &str) -> PathBuf {
+    let path = path(name);
+    let transport = MockTransport::new();
+    transport.respond(Method::Get, "/anime/1", 200, ANIME);
+    transport.respond(Method::Get, "/manga/1", 200, MANGA);
+
+    let cassette = Cassette::new(&path, transport)
+        .unwrap()
+        .redact("X-Api-Key");
+    assert!(cassette.is_recording());
+
+    for url in &["/anime/1", "/manga/1"] {
+        let mut request = HttpRequest::new(
+            Method::Get,
+            format!("https://kitsu.io/api/edge{}", url),
+        );
+        request.headers = vec![
+            ("Authorization".to_owned(), "Bearer secret".to_owned()),
+            ("X-Api-Key".to_owned(), "secret".to_owned()),
+        ];
+        cassette.send(request).unwrap();
+    }
+
+    path
+}
+
+#[test]
+fn test_redacts_headers() {
+    let path = record("redact");
+    let contents = fs::read_to_string(&path).unwrap();
+
+    assert!(!contents.contains("secret"));
+    assert!(contents.contains("[REDACTED]"));
+}
+
+#[test]
+fn test_replay_missing_file() {
+    let path = path("missing");
+
+    assert!(Cassette::with_mode(&path, MockTransport::new(), Mode::Replay)
+        .is_err());
+}
+
+bridge_tests! {
+    fn test_record_then_replay() {
+        let path = path(&format!("{}-replay", module_path!()));
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond(Method::Get, "/anime/1", 200, ANIME)
+            .respond(Method::Get, "/manga/1", 200, MANGA);
+
+        let cassette = Cassette::new(&path, Arc::clone(&transport))
+            .unwrap()
+            .redact("X-Api-Key");
+        assert!(cassette.is_recording());
+
+        let client = KitsuClient::new(Arc::new(cassette))
+            .header("Authorization", "Bearer secret")
+            .header("X-Api-Key", "secret");
+        send!(client.get_anime(1)).unwrap();
+        send!(client.get_manga(1)).unwrap();
+        assert!(!fs::read_to_string(&path).unwrap().contains("secret"));
+
+        let replayed = Arc::new(MockTransport::new());
+        let cassette = Cassette::new(&path, Arc::clone(&replayed)).unwrap();
+        assert!(!cassette.is_recording());
+
+        let client = KitsuClient::new(Arc::new(cassette));
+        let manga = send!(client.get_manga(1)).unwrap();
+        let anime = send!(client.get_anime(1)).unwrap();
+
+        assert_eq!(anime.data.attributes.canonical_title, "Cowboy Bebop");
+        assert_eq!(manga.data.attributes.canonical_title, "Monster");
+        assert!(replayed.requests().is_empty());
+    }
+
+    fn test_redacts_credentials_and_tokens() {
+        let path = path(&format!("{}-credentials", module_path!()));
+        let transport = Arc::new(MockTransport::new());
+        transport.respond(Method::Post, TOKEN_URL, 200, TOKEN);
+        transport.respond(Method::Get, "/anime/1", 200, ANIME);
+
+        let cassette = Cassette::new(&path, transport).unwrap();
+        let client = KitsuClient::new(Arc::new(cassette))
+            .auth(Auth::password("user@example.com", "hunter2"));
+        send!(client.get_anime(1)).unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("user@example.com"));
+        assert!(!contents.contains("hunter2"));
+        assert!(!contents.contains("access-secret"));
+        assert!(!contents.contains("refresh-secret"));
+
+        let cassette = Cassette::new(&path, Arc::new(MockTransport::new()))
+            .unwrap();
+        let client = KitsuClient::new(Arc::new(cassette))
+            .auth(Auth::password("user@example.com", "hunter2"));
+        let anime = send!(client.get_anime(1)).unwrap();
+
+        assert_eq!(anime.data.attributes.canonical_title, "Cowboy Bebop");
+    }
+
+    fn test_redacts_fields() {
+        let path = path(&format!("{}-fields", module_path!()));
+        let transport = Arc::new(MockTransport::new());
+        transport.respond(Method::Get, "/anime/1", 200, ANIME);
+
+        let cassette = Cassette::new(&path, transport)
+            .unwrap()
+            .redact_field("slug");
+        send!(KitsuClient::new(Arc::new(cassette)).get_anime(1)).unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("cowboy-bebop"));
+        assert!(contents.contains("Cowboy Bebop"));
+        assert!(contents.contains("[REDACTED]"));
+    }
+
+    fn test_interactions_are_used_once() {
+        let path = record(&format!("{}-once", module_path!()));
+        let cassette = Cassette::new(&path, Arc::new(MockTransport::new()))
+            .unwrap();
+        let client = KitsuClient::new(Arc::new(cassette));
+
+        assert!(send!(client.get_anime(1)).is_ok());
+        assert!(matches!(
+            send!(client.get_anime(1)),
+            Err(Error::InvalidRequest { response: None, source: Some(_) }),
+        ));
+    }
+
+    fn test_unmatched_request() {
+        let path = record(&format!("{}-unmatched", module_path!()));
+        let cassette = Cassette::new(&path, Arc::new(MockTransport::new()))
+            .unwrap();
+
+        assert!(matches!(
+            send!(KitsuClient::new(Arc::new(cassette)).get_anime(2)),
+            Err(Error::InvalidRequest { response: None, source: Some(_) }),
+        ));
+    }
+}
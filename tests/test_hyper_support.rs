@@ -9,7 +9,9 @@ extern crate tokio_core;
 use futures::Future;
 use hyper::Client;
 use hyper_tls::HttpsConnector;
+use kitsu::transport::FuturesHttpTransport;
 use kitsu::KitsuHyperRequester;
+use std::time::{Duration, Instant};
 use tokio_core::reactor::Core;
 
 #[ignore]
@@ -139,3 +141,15 @@ fn test_search_users() {
 
     core.run(runner).unwrap();
 }
+
+#[test]
+fn test_sleep_on_event_loop() {
+    let mut core = Core::new().unwrap();
+    let client = Client::new(&core.handle());
+
+    let start = Instant::now();
+    core.run(client.sleep(Duration::from_millis(50))).unwrap();
+
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    assert!(client.sleep(Duration::from_millis(50)).wait().is_err());
+}
@@ -0,0 +1,88 @@
+#![cfg(all(feature = "reqwest-support", feature = "testing"))]
+
+extern crate kitsu;
+extern crate serde_json;
+
+use kitsu::model::{Anime, Genre, RelationshipData, Response, User};
+use kitsu::testing::MockTransport;
+use kitsu::transport::Method;
+use kitsu::{Error, KitsuClient, KitsuReqwestRequester};
+
+const ANIME: &[u8] = include_bytes!("fixtures/anime.json");
+const ANIME_INCLUDE_GENRES: &[u8] =
+    include_bytes!("fixtures/anime_include_genres.json");
+
+fn anime_with_genres() -> Response<Anime> {
+    let transport = MockTransport::new();
+    transport.respond(
+        Method::Get,
+        "/anime/1?include=genres",
+        200,
+        ANIME_INCLUDE_GENRES,
+    );
+
+    KitsuClient::new(&transport)
+        .get_resource(1, |f| f.include("genres"))
+        .unwrap()
+}
+
+#[test]
+fn test_resolve_relationship() {
+    let res = anime_with_genres();
+    let genres: Vec<Genre> = res.data.relationships.genres.as_ref().unwrap()
+        .resolve(&res)
+        .unwrap();
+
+    assert_eq!(genres.len(), 2);
+    assert_eq!(genres[0].attributes.name, "Action");
+    assert_eq!(genres[1].attributes.slug, "sci-fi");
+}
+
+#[test]
+fn test_relationship_identifiers() {
+    let res = anime_with_genres();
+    let genres = res.data.relationships.genres.as_ref().unwrap();
+
+    assert!(matches!(genres.data, Some(RelationshipData::Many(_))));
+    assert_eq!(genres.identifiers().len(), 2);
+    assert_eq!(genres.identifiers()[1].id, "13");
+    assert_eq!(genres.identifiers()[1].kind, "genres");
+    let castings = res.data.relationships.castings.as_ref().unwrap();
+    assert!(castings.identifiers().is_empty());
+}
+
+#[test]
+fn test_included_store() {
+    let res = anime_with_genres();
+
+    assert_eq!(res.included.len(), 3);
+    assert_eq!(res.included.all::<Genre>().unwrap().len(), 2);
+    assert!(res.included.get::<Genre>("13").unwrap().is_some());
+    assert!(res.included.get::<Genre>("5").unwrap().is_none());
+    assert!(res.included.get::<User>("1").unwrap().is_none());
+    assert_eq!(res.included.identifiers()[2].kind, "castings");
+}
+
+#[test]
+fn test_included_decode_error() {
+    let res = anime_with_genres();
+    let mut value = serde_json::to_value(&res).unwrap();
+    value["included"][0]["attributes"]["name"] = serde_json::Value::Null;
+    let res: Response<Anime> = serde_json::from_value(value).unwrap();
+
+    assert!(matches!(res.included.get::<Genre>("1"), Err(Error::Decode(_))));
+}
+
+#[test]
+fn test_without_include() {
+    let transport = MockTransport::new();
+    transport.respond(Method::Get, "/anime/1", 200, ANIME);
+
+    let res = KitsuClient::new(&transport).get_anime(1).unwrap();
+    let genres: Vec<Genre> = res.data.relationships.genres.as_ref().unwrap()
+        .resolve(&res)
+        .unwrap();
+
+    assert!(res.included.is_empty());
+    assert!(genres.is_empty());
+}
@@ -0,0 +1,108 @@
+#![cfg(all(feature = "reqwest-support", feature = "testing"))]
+
+extern crate kitsu;
+extern crate serde_json;
+
+use kitsu::model::{LibraryEntryStatus, LibraryMedia, Type};
+use kitsu::testing::MockTransport;
+use kitsu::transport::{HttpRequest, Method};
+use kitsu::{Error, KitsuClient, KitsuReqwestRequester};
+
+const LIBRARY_ENTRIES: &[u8] = include_bytes!("fixtures/library_entries.json");
+const LIBRARY_ENTRY: &[u8] = include_bytes!("fixtures/library_entry.json");
+
+fn body(request: &HttpRequest) -> serde_json::Value {
+    serde_json::from_slice(request.body.as_ref().unwrap()).unwrap()
+}
+
+#[test]
+fn test_get_library_entries() {
+    let transport = MockTransport::new();
+    transport.respond(
+        Method::Get,
+        "/library-entries?filter[userId]=1&filter[status]=current",
+        200,
+        LIBRARY_ENTRIES,
+    );
+
+    let res = KitsuClient::new(&transport)
+        .get_library_entries(1, |f| f.filter("status", "current"))
+        .unwrap();
+
+    assert_eq!(res.data.len(), 3);
+    assert_eq!(res.data[0].kind, Type::LibraryEntry);
+    assert_eq!(res.data[0].attributes.status, LibraryEntryStatus::Current);
+    assert_eq!(res.data[1].attributes.rating_twenty, Some(18));
+    assert_eq!(res.data[2].attributes.status, LibraryEntryStatus::OnHold);
+}
+
+#[test]
+fn test_create_library_entry() {
+    let transport = MockTransport::new();
+    transport.respond(Method::Post, "/library-entries", 201, LIBRARY_ENTRY);
+
+    let res = KitsuClient::new(&transport)
+        .create_library_entry(1, LibraryMedia::Anime(12), |e| e
+            .status(LibraryEntryStatus::Current)
+            .progress(5)
+            .notes("Watching with friends"))
+        .unwrap();
+
+    assert_eq!(res.data.id, "1");
+    assert_eq!(res.data.attributes.progress, 5);
+
+    let requests = transport.requests();
+    assert_eq!(
+        requests[0].header("Content-Type"),
+        Some("application/vnd.api+json"),
+    );
+
+    let body = body(&requests[0]);
+    assert_eq!(body["data"]["type"], "libraryEntries");
+    assert_eq!(body["data"]["attributes"], serde_json::json!({
+        "notes": "Watching with friends",
+        "progress": 5,
+        "status": "current",
+    }));
+    assert_eq!(body["data"]["relationships"]["media"]["data"]["type"], "anime");
+    assert_eq!(body["data"]["relationships"]["media"]["data"]["id"], "12");
+    assert_eq!(body["data"]["relationships"]["user"]["data"]["id"], "1");
+}
+
+#[test]
+fn test_update_library_entry() {
+    let transport = MockTransport::new();
+    transport.respond(Method::Patch, "/library-entries/1", 200, LIBRARY_ENTRY);
+
+    KitsuClient::new(&transport)
+        .update_library_entry(1, |e| e.progress(6).rating_twenty(16))
+        .unwrap();
+
+    let body = body(&transport.requests()[0]);
+    assert_eq!(body["data"]["id"], "1");
+    assert_eq!(body["data"]["attributes"], serde_json::json!({
+        "progress": 6,
+        "ratingTwenty": 16,
+    }));
+    assert!(body["data"].get("relationships").is_none());
+}
+
+#[test]
+fn test_delete_library_entry() {
+    let transport = MockTransport::new();
+    transport.respond(Method::Delete, "/library-entries/1", 204, "");
+
+    assert!(KitsuClient::new(&transport).delete_library_entry(1).is_ok());
+    assert_eq!(transport.requests()[0].body, None);
+}
+
+#[test]
+fn test_create_library_entry_unauthorized() {
+    let transport = MockTransport::new();
+    transport.respond(Method::Post, "/library-entries", 401, "");
+
+    let res = KitsuClient::new(&transport)
+        .create_library_entry(1, LibraryMedia::Manga(1), |e| e);
+
+    assert!(matches!(res, Err(Error::Unauthorized(_))));
+}
@@ -0,0 +1,66 @@
+extern crate kitsu;
+extern crate serde_json;
+
+use kitsu::model::{Anime, Genre, Response};
+use serde_json::json;
+
+const ANIME: &[u8] = include_bytes!("fixtures/anime.json");
+const ANIME_SEARCH: &[u8] = include_bytes!("fixtures/anime_search.json");
+
+fn genres(meta: serde_json::Value) -> Response<Vec<Genre>> {
+    serde_json::from_value(json!({
+        "data": [],
+        "links": {},
+        "meta": meta,
+    })).unwrap()
+}
+
+#[test]
+fn test_meta_count() {
+    let res: Response<Vec<Anime>> = serde_json::from_slice(ANIME_SEARCH).unwrap();
+
+    assert_eq!(res.meta.count, Some(2));
+    assert_eq!(res.total_count(), Some(2));
+    assert!(res.meta.extra.is_empty());
+}
+
+#[test]
+fn test_meta_missing() {
+    let res: Response<Anime> = serde_json::from_slice(ANIME).unwrap();
+
+    assert!(res.meta.is_empty());
+    assert_eq!(res.total_count(), None);
+    assert_eq!(res.total_pages(10), None);
+}
+
+#[test]
+fn test_meta_extra() {
+    let res = genres(json!({ "count": 45, "statusCounts": { "current": 3 } }));
+
+    assert_eq!(res.meta.count, Some(45));
+    assert_eq!(res.meta.extra["statusCounts"]["current"], 3);
+}
+
+#[test]
+fn test_total_pages() {
+    let res = genres(json!({ "count": 45 }));
+
+    assert_eq!(res.total_pages(20), Some(3));
+    assert_eq!(res.total_pages(15), Some(3));
+    assert_eq!(res.total_pages(1), Some(45));
+    assert_eq!(res.total_pages(0), None);
+    assert_eq!(genres(json!({ "count": 0 })).total_pages(20), Some(0));
+}
+
+#[test]
+fn test_meta_round_trip() {
+    let res = genres(json!({ "count": 45, "statusCounts": { "current": 3 } }));
+    let value = serde_json::to_value(&res).unwrap();
+
+    assert_eq!(value["meta"], json!({ "count": 45, "statusCounts": { "current": 3 } }));
+
+    let res: Response<Anime> = serde_json::from_slice(ANIME).unwrap();
+    let value = serde_json::to_value(&res).unwrap();
+
+    assert!(value.get("meta").is_none());
+}
@@ -0,0 +1,181 @@
+#![cfg(all(
+    feature = "testing",
+    any(feature = "reqwest", feature = "async-support", feature = "hyper-support"),
+))]
+
+extern crate kitsu;
+
+#[macro_use]
+mod common;
+
+use common::ANIME;
+use kitsu::middleware::Middleware;
+use kitsu::testing::MockTransport;
+use kitsu::transport::{HttpRequest, HttpResponse, Method};
+use kitsu::{Error, KitsuClient, Result};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Records the hooks that were run, and optionally short-circuits or fails
+/// requests.
+#[derive(Debug, Default)]
+struct Recorder {
+    events: Arc<Mutex<Vec<String>>>,
+    fail: bool,
+    name: &'static str,
+    respond: bool,
+}
+
+impl Recorder {
+    fn new(name: &'static str, events: &Arc<Mutex<Vec<String>>>) -> Self {
+        Recorder {
+            events: Arc::clone(events),
+            name,
+            ..Recorder::default()
+        }
+    }
+
+    fn record(&self, event: &str) {
+        self.events.lock().unwrap().push(format!("{} {}", self.name, event));
+    }
+}
+
+impl Middleware for Recorder {
+    fn before(&self, request: &mut HttpRequest)
+        -> Result<Option<HttpResponse>> {
+        self.record("before");
+        request.headers.push(("X-Middleware".to_owned(), self.name.to_owned()));
+
+        if self.fail {
+            return Err(Error::InvalidRequest {
+                response: None,
+                source: Some("refused".into()),
+            });
+        }
+
+        if self.respond {
+            return Ok(Some(HttpResponse {
+                body: ANIME.to_vec(),
+                headers: Vec::new(),
+                status: 200,
+            }));
+        }
+
+        Ok(None)
+    }
+
+    fn after(
+        &self,
+        _: &HttpRequest,
+        response: &mut HttpResponse,
+        _: Duration,
+    ) -> Result<()> {
+        self.record("after");
+        response.headers.push(("X-Seen-By".to_owned(), self.name.to_owned()));
+
+        Ok(())
+    }
+
+    fn on_error(&self, _: &HttpRequest, _: &Error) {
+        self.record("error");
+    }
+}
+
+fn events(events: &Arc<Mutex<Vec<String>>>) -> Vec<String> {
+    events.lock().unwrap().clone()
+}
+
+bridge_tests! {
+    fn test_hooks_run_in_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let transport = Arc::new(MockTransport::new());
+        transport.respond(Method::Get, "/anime/1", 200, ANIME);
+
+        let client = KitsuClient::new(Arc::clone(&transport))
+            .middleware(Recorder::new("outer", &log))
+            .middleware(Recorder::new("inner", &log));
+        send!(client.get_anime(1)).unwrap();
+
+        assert_eq!(
+            events(&log),
+            ["outer before", "inner before", "inner after", "outer after"],
+        );
+
+        let request = &transport.requests()[0];
+        let headers = request.headers.iter()
+            .filter(|(name, _)| name == "X-Middleware")
+            .map(|(_, value)| value.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(headers, ["outer", "inner"]);
+    }
+
+    fn test_short_circuit() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let transport = Arc::new(MockTransport::new());
+
+        let client = KitsuClient::new(Arc::clone(&transport))
+            .middleware(Recorder::new("outer", &log))
+            .middleware(Recorder {
+                respond: true,
+                ..Recorder::new("cached", &log)
+            })
+            .middleware(Recorder::new("skipped", &log));
+        let anime = send!(client.get_anime(1)).unwrap();
+
+        assert_eq!(anime.data.attributes.canonical_title, "Cowboy Bebop");
+        assert_eq!(
+            events(&log),
+            ["outer before", "cached before", "cached after", "outer after"],
+        );
+        assert!(transport.requests().is_empty());
+    }
+
+    fn test_errors() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let transport = Arc::new(MockTransport::new());
+
+        let client = KitsuClient::new(Arc::clone(&transport))
+            .middleware(Recorder::new("outer", &log))
+            .middleware(Recorder {
+                fail: true,
+                ..Recorder::new("refusing", &log)
+            });
+        assert!(send!(client.get_anime(1)).is_err());
+        assert_eq!(events(&log), ["outer before", "refusing before", "outer error"]);
+        assert!(transport.requests().is_empty());
+
+        log.lock().unwrap().clear();
+
+        // No response was registered, so the transport fails the request.
+        let client = KitsuClient::new(Arc::clone(&transport))
+            .middleware(Recorder::new("only", &log));
+        assert!(send!(client.get_anime(2)).is_err());
+        assert_eq!(events(&log), ["only before", "only error"]);
+    }
+}
+
+#[cfg(feature = "reqwest")]
+#[test]
+fn test_applies_to_pages() {
+    use kitsu::builder::AnimeSearch;
+    use kitsu::model::Anime;
+    use kitsu::KitsuReqwestRequester;
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let transport = MockTransport::new();
+    transport.respond(
+        Method::Get,
+        "/anime",
+        200,
+        &include_bytes!("fixtures/anime_search.json")[..],
+    );
+
+    let client = KitsuClient::new(&transport)
+        .middleware(Recorder::new("pages", &log));
+    let pages = client
+        .search_pages::<Anime, _>(AnimeSearch::default())
+        .collect::<Vec<_>>();
+
+    assert_eq!(pages.len(), 1);
+    assert_eq!(events(&log), ["pages before", "pages after"]);
+}
@@ -0,0 +1,247 @@
+#![cfg(all(feature = "reqwest-support", feature = "testing"))]
+
+extern crate kitsu;
+
+use kitsu::model::{AgeRating, AnimeType, MangaType, Status, Type};
+use kitsu::testing::MockTransport;
+use kitsu::transport::{HttpResponse, Method};
+use kitsu::{Error, KitsuClient, KitsuReqwestRequester};
+
+const ANIME: &[u8] = include_bytes!("fixtures/anime.json");
+const ANIME_SEARCH: &[u8] = include_bytes!("fixtures/anime_search.json");
+const CHARACTER: &[u8] = include_bytes!("fixtures/character.json");
+const CHARACTERS_SEARCH: &[u8] =
+    include_bytes!("fixtures/characters_search.json");
+const ERROR_NOT_FOUND: &[u8] = include_bytes!("fixtures/error_not_found.json");
+const MANGA: &[u8] = include_bytes!("fixtures/manga.json");
+const MANGA_SEARCH: &[u8] = include_bytes!("fixtures/manga_search.json");
+const PRODUCER: &[u8] = include_bytes!("fixtures/producer.json");
+const USER: &[u8] = include_bytes!("fixtures/user.json");
+const USERS_SEARCH: &[u8] = include_bytes!("fixtures/users_search.json");
+
+fn transport(target: &str, body: &[u8]) -> MockTransport {
+    let transport = MockTransport::new();
+    transport.respond(Method::Get, target, 200, body);
+
+    transport
+}
+
+#[test]
+fn test_get_anime() {
+    let transport = transport("/anime/1", ANIME);
+    let res = KitsuClient::new(&transport).get_anime(1).unwrap();
+    let anime = res.data;
+
+    assert_eq!(anime.id, "1");
+    assert_eq!(anime.kind, Type::Anime);
+    assert_eq!(anime.attributes.canonical_title, "Cowboy Bebop");
+    assert_eq!(anime.attributes.age_rating, Some(AgeRating::R));
+    assert_eq!(anime.attributes.kind, AnimeType::TV);
+    assert_eq!(anime.attributes.status, Some(Status::Finished));
+    assert_eq!(anime.attributes.episode_count, Some(26));
+    assert_eq!(anime.attributes.rating_frequencies.rating_5_0, 8342);
+    assert_eq!(anime.url(), "https://kitsu.io/anime/cowboy-bebop");
+    assert_eq!(
+        anime.youtube_url().as_deref(),
+        Some("https://www.youtube.com/watch?v=qig4KOK2R2g"),
+    );
+}
+
+#[test]
+fn test_get_character() {
+    let transport = transport("/characters/1", CHARACTER);
+    let res = KitsuClient::new(&transport).get_character(1).unwrap();
+
+    assert_eq!(res.data.id, "1");
+    assert_eq!(res.data.kind, Type::Character);
+    assert_eq!(res.data.attributes.name, "Spike Spiegel");
+    assert_eq!(res.data.attributes.mal_id, 1);
+}
+
+#[test]
+fn test_get_manga() {
+    let transport = transport("/manga/1", MANGA);
+    let res = KitsuClient::new(&transport).get_manga(1).unwrap();
+
+    assert_eq!(res.data.id, "1");
+    assert_eq!(res.data.kind, Type::Manga);
+    assert_eq!(res.data.attributes.canonical_title, "Monster");
+    assert_eq!(res.data.attributes.kind, MangaType::Manga);
+    assert_eq!(res.data.attributes.chapter_count, Some(162));
+}
+
+#[test]
+fn test_get_producer() {
+    let transport = transport("/producers/1", PRODUCER);
+    let res = KitsuClient::new(&transport).get_producer(1).unwrap();
+
+    assert_eq!(res.data.id, "1");
+    assert_eq!(res.data.kind, Type::Producer);
+    assert_eq!(res.data.attributes.name, "Sunrise");
+}
+
+#[test]
+fn test_get_user() {
+    let transport = transport("/users/1", USER);
+    let res = KitsuClient::new(&transport).get_user(1).unwrap();
+
+    assert_eq!(res.data.id, "1");
+    assert_eq!(res.data.kind, Type::Users);
+    assert_eq!(res.data.attributes.name, "vikhyat");
+    assert_eq!(res.data.url(), "https://kitsu.io/users/vikhyat");
+}
+
+#[test]
+fn test_search_anime() {
+    let transport = transport(
+        "/anime?filter[text]=non%20non%20biyori",
+        ANIME_SEARCH,
+    );
+    let res = KitsuClient::new(&transport)
+        .search_anime(|f| f.filter("text", "non non biyori"))
+        .unwrap();
+
+    assert_eq!(res.data.len(), 2);
+    assert_eq!(res.data[0].attributes.canonical_title, "Non Non Biyori");
+    assert!(res.links.contains_key("first"));
+}
+
+#[test]
+fn test_search_characters() {
+    let transport = transport("/characters?filter[name]=bebop", CHARACTERS_SEARCH);
+    let res = KitsuClient::new(&transport)
+        .search_characters(|f| f.filter("name", "bebop"))
+        .unwrap();
+
+    assert_eq!(res.data.len(), 2);
+    assert_eq!(res.data[1].attributes.name, "Faye Valentine");
+}
+
+#[test]
+fn test_search_manga() {
+    let transport = transport("/manga?filter[text]=orange", MANGA_SEARCH);
+    let res = KitsuClient::new(&transport)
+        .search_manga(|f| f.filter("text", "orange"))
+        .unwrap();
+
+    assert_eq!(res.data.len(), 1);
+    assert_eq!(res.data[0].attributes.canonical_title, "Orange");
+}
+
+#[test]
+fn test_search_users() {
+    let transport = transport("/users?filter[name]=Bob", USERS_SEARCH);
+    let res = KitsuClient::new(&transport)
+        .search_users(|f| f.filter("name", "Bob"))
+        .unwrap();
+
+    assert_eq!(res.data.len(), 1);
+    assert_eq!(res.data[0].attributes.name, "Bob");
+}
+
+#[test]
+fn test_query_order_is_ignored() {
+    let transport = transport(
+        "/anime?page[offset]=10&page[limit]=5&filter[text]=bebop",
+        ANIME_SEARCH,
+    );
+    let res = KitsuClient::new(&transport)
+        .search_anime(|f| f.filter("text", "bebop").limit(5).offset(10));
+
+    assert!(res.is_ok());
+}
+
+#[test]
+fn test_request_headers() {
+    let transport = transport("/anime/1", ANIME);
+    let client = KitsuClient::new(&transport)
+        .user_agent("kitsu-tests")
+        .header("X-Test", "1");
+    client.get_anime(1).unwrap();
+
+    let requests = transport.requests();
+
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, Method::Get);
+    assert_eq!(requests[0].url, "https://kitsu.io/api/edge/anime/1");
+    assert_eq!(requests[0].header("user-agent"), Some("kitsu-tests"));
+    assert_eq!(requests[0].header("X-Test"), Some("1"));
+}
+
+#[test]
+fn test_base_url() {
+    let transport = MockTransport::new().base_url("http://localhost/api/");
+    transport.respond(Method::Get, "/anime/1", 200, ANIME);
+
+    let client = KitsuClient::new(&transport).base_url("http://localhost/api/");
+
+    assert!(client.get_anime(1).is_ok());
+}
+
+#[test]
+fn test_not_found() {
+    let transport = MockTransport::new();
+    transport.respond(Method::Get, "/anime/0", 404, ERROR_NOT_FOUND);
+
+    match KitsuClient::new(&transport).get_anime(0) {
+        Err(Error::NotFound(response)) => {
+            assert_eq!(response.status, 404);
+            assert_eq!(response.errors.len(), 1);
+            assert_eq!(
+                response.errors[0].title.as_deref(),
+                Some("Record not found"),
+            );
+        },
+        other => panic!("Expected NotFound, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_rate_limited() {
+    let transport = MockTransport::new();
+    transport.respond_with(Method::Get, "/anime/1", HttpResponse {
+        body: Vec::new(),
+        headers: vec![("Retry-After".to_owned(), "30".to_owned())],
+        status: 429,
+    });
+
+    match KitsuClient::new(&transport).get_anime(1) {
+        Err(Error::RateLimited { retry_after, .. }) => {
+            assert_eq!(retry_after.map(|delay| delay.as_secs()), Some(30));
+        },
+        other => panic!("Expected RateLimited, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_responses_in_order() {
+    let transport = MockTransport::new();
+    transport
+        .respond(Method::Get, "/anime/1", 503, "")
+        .respond(Method::Get, "/anime/1", 200, ANIME);
+    let client = KitsuClient::new(&transport);
+
+    assert!(matches!(client.get_anime(1), Err(Error::Server(_))));
+    assert!(client.get_anime(1).is_ok());
+    assert!(client.get_anime(1).is_ok());
+}
+
+#[test]
+fn test_unmatched_request() {
+    let transport = MockTransport::new();
+
+    assert!(matches!(
+        KitsuClient::new(&transport).get_anime(1),
+        Err(Error::InvalidRequest { response: None, source: Some(_) }),
+    ));
+}
+
+#[test]
+fn test_decode_error() {
+    let transport = transport("/anime/1", b"{\"data\": {}}");
+
+    assert!(matches!(
+        KitsuClient::new(&transport).get_anime(1),
+        Err(Error::Decode(_)),
+    ));
+}
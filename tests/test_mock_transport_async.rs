@@ -0,0 +1,81 @@
+#![cfg(all(feature = "async-support", feature = "testing"))]
+
+extern crate kitsu;
+extern crate tokio;
+
+use kitsu::auth::{Auth, Token, TOKEN_URL};
+use kitsu::testing::MockTransport;
+use kitsu::transport::Method;
+use kitsu::{Error, KitsuAsyncRequester, KitsuClient};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const ANIME: &[u8] = include_bytes!("fixtures/anime.json");
+const ERROR_NOT_FOUND: &[u8] = include_bytes!("fixtures/error_not_found.json");
+const USERS_SEARCH: &[u8] = include_bytes!("fixtures/users_search.json");
+
+#[tokio::test]
+async fn test_get_anime() {
+    let transport = MockTransport::new();
+    transport.respond(Method::Get, "/anime/1", 200, ANIME);
+
+    let res = KitsuClient::new(&transport).get_anime(1).await.unwrap();
+
+    assert_eq!(res.data.id, "1");
+    assert_eq!(res.data.attributes.canonical_title, "Cowboy Bebop");
+}
+
+#[tokio::test]
+async fn test_search_users() {
+    let transport = MockTransport::new();
+    transport.respond(Method::Get, "/users?filter[name]=Bob", 200, USERS_SEARCH);
+
+    let res = KitsuClient::new(&transport)
+        .search_users(|f| f.filter("name", "Bob"))
+        .await
+        .unwrap();
+
+    assert_eq!(res.data[0].attributes.name, "Bob");
+}
+
+#[tokio::test]
+async fn test_not_found() {
+    let transport = MockTransport::new();
+    transport.respond(Method::Get, "/anime/0", 404, ERROR_NOT_FOUND);
+
+    match KitsuClient::new(&transport).get_anime(0).await {
+        Err(Error::NotFound(response)) => assert_eq!(response.status, 404),
+        other => panic!("Expected NotFound, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_refresh_on_unauthorized() {
+    let token = Token {
+        access_token: "first".to_owned(),
+        created_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs(),
+        expires_in: 2_592_000,
+        refresh_token: Some("refresh".to_owned()),
+        scope: None,
+        token_type: "bearer".to_owned(),
+    };
+    let transport = MockTransport::new();
+    transport.respond(Method::Post, TOKEN_URL, 200, r#"{
+        "access_token": "second",
+        "expires_in": 2592000,
+        "refresh_token": "refresh",
+        "token_type": "bearer"
+    }"#);
+    transport
+        .respond(Method::Get, "/anime/1", 401, "")
+        .respond(Method::Get, "/anime/1", 200, ANIME);
+
+    let client = KitsuClient::new(&transport).auth(Auth::token(token));
+    client.get_anime(1).await.unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests[2].header("Authorization"), Some("Bearer second"));
+    assert!(!client.auth_token().unwrap().unwrap().is_expired());
+}
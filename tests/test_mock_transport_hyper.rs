@@ -0,0 +1,105 @@
+#![cfg(all(feature = "hyper-support", feature = "testing"))]
+
+extern crate futures;
+extern crate kitsu;
+
+use futures::Future;
+use kitsu::auth::{Auth, MemoryTokenStore, TOKEN_URL};
+use kitsu::testing::MockTransport;
+use kitsu::transport::Method;
+use kitsu::{Error, KitsuClient, KitsuHyperRequester};
+use std::sync::Arc;
+
+mod common;
+
+use common::{now, token};
+
+const ANIME: &[u8] = include_bytes!("fixtures/anime.json");
+const ERROR_NOT_FOUND: &[u8] = include_bytes!("fixtures/error_not_found.json");
+const USERS_SEARCH: &[u8] = include_bytes!("fixtures/users_search.json");
+
+#[test]
+fn test_get_anime() {
+    let transport = Arc::new(MockTransport::new());
+    transport.respond(Method::Get, "/anime/1", 200, ANIME);
+
+    let res = KitsuClient::new(transport).get_anime(1).wait().unwrap();
+
+    assert_eq!(res.data.id, "1");
+    assert_eq!(res.data.attributes.canonical_title, "Cowboy Bebop");
+}
+
+#[test]
+fn test_search_users() {
+    let transport = Arc::new(MockTransport::new());
+    transport.respond(Method::Get, "/users?filter[name]=Bob", 200, USERS_SEARCH);
+
+    let res = KitsuClient::new(transport)
+        .search_users(|f| f.filter("name", "Bob"))
+        .wait()
+        .unwrap();
+
+    assert_eq!(res.data[0].attributes.name, "Bob");
+}
+
+#[test]
+fn test_not_found() {
+    let transport = Arc::new(MockTransport::new());
+    transport.respond(Method::Get, "/anime/0", 404, ERROR_NOT_FOUND);
+
+    match KitsuClient::new(transport).get_anime(0).wait() {
+        Err(Error::NotFound(response)) => assert_eq!(response.status, 404),
+        other => panic!("Expected NotFound, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_refresh_on_unauthorized() {
+    let transport = Arc::new(MockTransport::new());
+    transport.respond(Method::Post, TOKEN_URL, 200, r#"{
+        "access_token": "second",
+        "expires_in": 2592000,
+        "refresh_token": "refresh",
+        "token_type": "bearer"
+    }"#);
+    transport
+        .respond(Method::Get, "/anime/1", 401, "")
+        .respond(Method::Get, "/anime/1", 200, ANIME);
+
+    let client = KitsuClient::new(Arc::clone(&transport))
+        .auth(Auth::token(token("first", now())));
+    client.get_anime(1).wait().unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests[2].header("Authorization"), Some("Bearer second"));
+    assert!(!client.auth_token().unwrap().unwrap().is_expired());
+}
+
+#[test]
+fn test_password_grant_after_failed_refresh() {
+    let transport = Arc::new(MockTransport::new());
+    transport
+        .respond(Method::Post, TOKEN_URL, 400, r#"{"error": "invalid_grant"}"#)
+        .respond(Method::Post, TOKEN_URL, 200, r#"{
+            "access_token": "second",
+            "expires_in": 2592000,
+            "token_type": "bearer"
+        }"#);
+    transport
+        .respond(Method::Get, "/anime/1", 401, "")
+        .respond(Method::Get, "/anime/1", 200, ANIME);
+
+    let auth = Auth::password("user@example.com", "hunter2")
+        .store(MemoryTokenStore::with_token(token("revoked", now())));
+    KitsuClient::new(Arc::clone(&transport))
+        .auth(auth)
+        .get_anime(1)
+        .wait()
+        .unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 4);
+    let grant = String::from_utf8_lossy(requests[2].body.as_ref().unwrap());
+    assert!(grant.contains(r#""grant_type":"password""#));
+    assert_eq!(requests[3].header("Authorization"), Some("Bearer second"));
+}
@@ -0,0 +1,122 @@
+#![cfg(all(feature = "reqwest-support", feature = "testing"))]
+
+extern crate kitsu;
+extern crate serde_json;
+
+use kitsu::builder::{AnimeSearch, AnimeSortField, Query, Search, Sort};
+use kitsu::model::{Anime, Genre};
+use kitsu::testing::MockTransport;
+use kitsu::transport::Method;
+use kitsu::{Error, KitsuClient, KitsuReqwestRequester};
+
+mod common;
+
+use common::{genre_pages, page};
+
+#[test]
+fn test_search_pages() {
+    let transport = genre_pages();
+    let pages = KitsuClient::new(&transport)
+        .search_pages::<Genre, _>(Search::default())
+        .page_size(2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(pages.len(), 3);
+    assert_eq!(pages[1].data[0].id, "3");
+    assert_eq!(pages[2].data.len(), 1);
+}
+
+#[test]
+fn test_search_all() {
+    let transport = genre_pages();
+    let genres = KitsuClient::new(&transport)
+        .search_all::<Genre, _>(Search::default().limit(2))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let ids = genres.iter().map(|genre| genre.id.as_str()).collect::<Vec<_>>();
+
+    assert_eq!(ids, ["1", "2", "3", "4", "5"]);
+    assert_eq!(transport.requests().len(), 3);
+}
+
+#[test]
+fn test_search_all_with_typed_builder() {
+    let transport = MockTransport::new();
+    transport.respond(
+        Method::Get,
+        "/anime?filter[text]=bebop&sort=-averageRating&page[limit]=20",
+        200,
+        &include_bytes!("fixtures/anime_search.json")[..],
+    );
+
+    let search = AnimeSearch::default()
+        .text("bebop")
+        .sort_by(Sort::desc(AnimeSortField::AverageRating));
+    let anime = KitsuClient::new(&transport)
+        .search_all::<Anime, _>(search)
+        .page_size(20)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(anime.len(), 2);
+}
+
+#[test]
+fn test_search_pages_with_query() {
+    let transport = genre_pages();
+    let query = Query::parse("page[limit]=2").unwrap();
+    let pages = KitsuClient::new(&transport)
+        .search_pages::<Genre, _>(query)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(pages.len(), 3);
+}
+
+#[test]
+fn test_pages_are_lazy() {
+    let transport = genre_pages();
+    let client = KitsuClient::new(&transport);
+    let mut genres = client.search_all::<Genre, _>(Search::default()).page_size(2);
+
+    assert!(transport.requests().is_empty());
+    assert_eq!(genres.next().unwrap().unwrap().id, "1");
+    assert_eq!(genres.next().unwrap().unwrap().id, "2");
+    assert_eq!(transport.requests().len(), 1);
+    assert_eq!(genres.next().unwrap().unwrap().id, "3");
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[test]
+fn test_max_items() {
+    let transport = genre_pages();
+    let genres = KitsuClient::new(&transport)
+        .search_all::<Genre, _>(Search::default())
+        .page_size(2)
+        .max_items(3)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(genres.len(), 3);
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[test]
+fn test_stops_after_error() {
+    let transport = MockTransport::new();
+    transport.respond(
+        Method::Get,
+        "/genres",
+        200,
+        page(&[1], Some("/genres?page[offset]=1")),
+    );
+    transport.respond(Method::Get, "/genres?page[offset]=1", 503, "");
+
+    let client = KitsuClient::new(&transport);
+    let mut genres = client.search_all::<Genre, _>(Search::default());
+
+    assert!(genres.next().unwrap().is_ok());
+    assert!(matches!(genres.next(), Some(Err(Error::Server(_)))));
+    assert!(genres.next().is_none());
+}
@@ -0,0 +1,141 @@
+#![cfg(all(feature = "async-support", feature = "testing"))]
+
+extern crate futures_util;
+extern crate kitsu;
+extern crate serde_json;
+extern crate tokio;
+
+use futures_util::{StreamExt, TryStreamExt};
+use kitsu::builder::Search;
+use kitsu::model::Genre;
+use kitsu::testing::MockTransport;
+use kitsu::transport::Method;
+use kitsu::{KitsuAsyncRequester, KitsuClient};
+
+mod common;
+
+use common::{capped_genre_pages, genre_pages, page};
+
+#[tokio::test]
+async fn test_search_pages() {
+    let transport = genre_pages();
+    let client = KitsuClient::new(&transport);
+    let pages = client
+        .search_pages::<Genre, _>(Search::default())
+        .page_size(2)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+    assert_eq!(pages.len(), 3);
+    assert_eq!(pages[1].data[0].id, "3");
+    assert_eq!(pages[2].data.len(), 1);
+    assert_eq!(transport.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_search_all_max_items() {
+    let transport = genre_pages();
+    let client = KitsuClient::new(&transport);
+    let ids = client
+        .search_all::<Genre, _>(Search::default())
+        .page_size(2)
+        .max_items(3)
+        .map_ok(|genre| genre.id)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+    assert_eq!(ids, ["1", "2", "3"]);
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_prefetch_requests_ahead_in_order() {
+    let transport = genre_pages();
+    let client = KitsuClient::new(&transport);
+    let mut pages = client
+        .search_pages::<Genre, _>(Search::default())
+        .page_size(2)
+        .prefetch(2);
+
+    let first = pages.next().await.unwrap().unwrap();
+    assert_eq!(first.data[0].id, "1");
+
+    let urls = transport.requests()
+        .into_iter()
+        .map(|request| request.url)
+        .collect::<Vec<_>>();
+    assert_eq!(urls.len(), 3);
+    assert!(urls[0].ends_with("page[limit]=2&page[offset]=0"));
+    assert!(urls[1].ends_with("page[limit]=2&page[offset]=2"));
+    assert!(urls[2].ends_with("page[limit]=2&page[offset]=4"));
+
+    let rest = pages.try_collect::<Vec<_>>().await.unwrap();
+    assert_eq!(rest.len(), 2);
+    assert_eq!(rest[0].data[0].id, "3");
+    assert_eq!(rest[1].data[0].id, "5");
+}
+
+#[tokio::test]
+async fn test_prefetch_stops_after_last_page() {
+    let transport = genre_pages();
+    let client = KitsuClient::new(&transport);
+    let ids = client
+        .search_all::<Genre, _>(Search::default())
+        .page_size(2)
+        .prefetch(3)
+        .map_ok(|genre| genre.id)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+    // The requests past the last page fail, but are dropped unseen.
+    assert_eq!(ids, ["1", "2", "3", "4", "5"]);
+}
+
+#[tokio::test]
+async fn test_stream_stops_after_error() {
+    let transport = MockTransport::new();
+    transport
+        .respond(
+            Method::Get,
+            "/genres?page[limit]=2",
+            200,
+            page(&[1, 2], Some("/genres?page[limit]=2&page[offset]=2")),
+        )
+        .respond(
+            Method::Get,
+            "/genres?page[limit]=2&page[offset]=2",
+            500,
+            "",
+        );
+
+    let client = KitsuClient::new(&transport);
+    let mut pages = client
+        .search_pages::<Genre, _>(Search::default())
+        .page_size(2);
+
+    assert!(pages.next().await.unwrap().is_ok());
+    assert!(pages.next().await.unwrap().is_err());
+    assert!(pages.next().await.is_none());
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_prefetch_page_size_above_maximum() {
+    let transport = capped_genre_pages();
+    let client = KitsuClient::new(&transport);
+    let ids = client
+        .search_all::<Genre, _>(Search::default())
+        .page_size(50)
+        .prefetch(1)
+        .map_ok(|genre| genre.id)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+    assert_eq!(ids.len(), 25);
+    assert_eq!(ids[20], "21");
+    assert!(transport.requests()[1].url.ends_with("page[limit]=20&page[offset]=20"));
+}
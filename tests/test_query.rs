@@ -0,0 +1,116 @@
+extern crate kitsu;
+extern crate serde_json;
+
+use kitsu::builder::{AnimeSearch, Query, Search};
+use kitsu::Error;
+use serde_json::json;
+
+const NEXT: &str = "https://kitsu.io/api/edge/manga?fields%5Bmanga%5D=slug%2CcanonicalTitle\
+                    &filter%5Btext%5D=orange&include=genres&page%5Blimit%5D=10\
+                    &page%5Boffset%5D=10&sort=-averageRating%2Cid";
+
+#[test]
+fn test_parse_next_link() {
+    let query = Query::parse(NEXT).unwrap();
+
+    assert_eq!(query.fields["manga"], ["slug", "canonicalTitle"]);
+    assert_eq!(query.filters["text"], "orange");
+    assert_eq!(query.include, ["genres"]);
+    assert_eq!(query.limit, Some(10));
+    assert_eq!(query.offset, Some(10));
+    assert_eq!(query.sort, ["-averageRating", "id"]);
+    assert!(query.other.is_empty());
+}
+
+#[test]
+fn test_parse_forms() {
+    let expected = Query::parse("filter[text]=orange&page[limit]=5").unwrap();
+
+    assert_eq!(Query::parse("/manga?filter[text]=orange&page[limit]=5").unwrap(), expected);
+    assert_eq!(Query::parse("&filter[text]=orange&page[limit]=5#top").unwrap(), expected);
+    assert_eq!("filter%5Btext%5D=orange&page%5Blimit%5D=5".parse::<Query>().unwrap(), expected);
+    assert_eq!(Query::parse("https://kitsu.io/api/edge/manga").unwrap(), Query::default());
+}
+
+#[test]
+fn test_parse_decodes_values() {
+    let query = Query::parse("filter[text]=cowboy+bebop&filter[ageRating]=R17%2B").unwrap();
+
+    assert_eq!(query.filters["text"], "cowboy bebop");
+    assert_eq!(query.filters["ageRating"], "R17+");
+}
+
+#[test]
+fn test_parse_invalid_number() {
+    match Query::parse("page[limit]=ten") {
+        Err(Error::InvalidRequest { response: None, .. }) => {},
+        other => panic!("expected an invalid request: {:?}", other),
+    }
+}
+
+#[test]
+fn test_render() {
+    let query = Query::parse(NEXT).unwrap();
+
+    assert_eq!(
+        query.to_string(),
+        "filter[text]=orange&include=genres&fields[manga]=slug,canonicalTitle\
+         &sort=-averageRating,id&page[limit]=10&page[offset]=10",
+    );
+    assert_eq!(Query::default().to_string(), "");
+}
+
+#[test]
+fn test_render_encodes_reserved_characters() {
+    let mut query = Query::default();
+    query.filters.insert("text".to_owned(), "a&b=c+d".to_owned());
+
+    assert_eq!(query.to_string(), "filter[text]=a%26b%3Dc%2Bd");
+    assert_eq!(Query::parse(&query.to_string()).unwrap(), query);
+}
+
+#[test]
+fn test_other_parameters_preserved() {
+    let query = Query::parse("filter[text]=bebop&page[number]=2&page[size]=5").unwrap();
+
+    assert_eq!(query.other["page[number]"], "2");
+    assert_eq!(query.other["page[size]"], "5");
+    assert_eq!(
+        query.to_string(),
+        "filter[text]=bebop&page[number]=2&page[size]=5",
+    );
+}
+
+#[test]
+fn test_search_round_trip() {
+    let search = AnimeSearch::default()
+        .text("bebop")
+        .include("genres")
+        .limit(20)
+        .offset(40);
+    let rendered = Search::from(search).0;
+
+    let query = Query::parse(&rendered).unwrap();
+    assert_eq!(Search::from(query.clone()).0, rendered);
+    assert_eq!(Search::from(AnimeSearch::from(query)).0, rendered);
+}
+
+#[test]
+fn test_serde_round_trip() {
+    let query = Query::parse(NEXT).unwrap();
+    let value = serde_json::to_value(&query).unwrap();
+
+    assert_eq!(value, json!({
+        "fields": { "manga": ["slug", "canonicalTitle"] },
+        "filters": { "text": "orange" },
+        "include": ["genres"],
+        "limit": 10,
+        "offset": 10,
+        "sort": ["-averageRating", "id"],
+    }));
+    assert_eq!(serde_json::from_value::<Query>(value).unwrap(), query);
+    assert_eq!(
+        serde_json::from_value::<Query>(json!({ "limit": 5 })).unwrap(),
+        Query { limit: Some(5), ..Query::default() },
+    );
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "testing")]
+
+extern crate kitsu;
+
+#[macro_use]
+mod common;
+
+use common::rounded_secs;
+use kitsu::rate_limit::RateLimiter;
+use std::time::Duration;
+
+#[test]
+fn test_reserve() {
+    let limiter = RateLimiter::new(1.0, 2);
+    let shared = limiter.clone();
+
+    assert_eq!(limiter.reserve(), Duration::from_secs(0));
+    assert_eq!(shared.reserve(), Duration::from_secs(0));
+    assert_eq!(rounded_secs(&[limiter.reserve(), shared.reserve()]), [1, 2]);
+}
+
+#[test]
+#[should_panic(expected = "burst")]
+fn test_zero_burst_panics() {
+    RateLimiter::new(1.0, 0);
+}
+
+bridge_tests! {
+    use common::anime_transport;
+    use kitsu::retry::RetryPolicy;
+    use kitsu::testing::MockTransport;
+    use kitsu::transport::Method;
+    use kitsu::KitsuClient;
+    use std::sync::Arc;
+
+    fn test_burst_is_not_delayed() {
+        let transport = anime_transport();
+        let client = KitsuClient::new(Arc::clone(&transport))
+            .rate_limit(RateLimiter::new(1.0, 3));
+
+        for _ in 0..3 {
+            send!(client.get_anime(1)).unwrap();
+        }
+
+        assert!(transport.sleeps().is_empty());
+    }
+
+    fn test_waits_once_bucket_is_empty() {
+        let transport = anime_transport();
+        let client = KitsuClient::new(Arc::clone(&transport))
+            .rate_limit(RateLimiter::new(1.0, 1));
+
+        for _ in 0..4 {
+            send!(client.get_anime(1)).unwrap();
+        }
+
+        // The first request takes the only token, and each of the other
+        // three waits a second longer than the one before it.
+        assert_eq!(rounded_secs(&transport.sleeps()), [1, 2, 3]);
+        assert_eq!(transport.requests().len(), 4);
+    }
+
+    fn test_budget_shared_between_clients() {
+        let transport = anime_transport();
+        let limiter = RateLimiter::new(1.0, 2);
+        let first = KitsuClient::new(Arc::clone(&transport))
+            .rate_limit(limiter.clone());
+        let second = KitsuClient::new(Arc::clone(&transport))
+            .rate_limit(limiter);
+
+        for _ in 0..2 {
+            send!(first.get_anime(1)).unwrap();
+            send!(second.get_anime(1)).unwrap();
+        }
+
+        assert_eq!(rounded_secs(&transport.sleeps()), [1, 2]);
+    }
+
+    fn test_retries_count_against_limit() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond(Method::Get, "/anime/1", 503, "")
+            .respond(Method::Get, "/anime/1", 200, common::ANIME);
+
+        let policy = RetryPolicy::new()
+            .base_delay(Duration::from_millis(100))
+            .jitter(false);
+        let client = KitsuClient::new(Arc::clone(&transport))
+            .rate_limit(RateLimiter::new(1.0, 1))
+            .retry(policy);
+
+        send!(client.get_anime(1)).unwrap();
+
+        // The retry waits for its delay, and then for the limiter.
+        let sleeps = transport.sleeps();
+        assert_eq!(sleeps[0], Duration::from_millis(100));
+        assert_eq!(rounded_secs(&sleeps[1..]), [1]);
+        assert_eq!(transport.requests().len(), 2);
+    }
+}
@@ -0,0 +1,161 @@
+#![cfg(all(feature = "reqwest-support", feature = "testing"))]
+
+extern crate kitsu;
+extern crate serde_json;
+
+use kitsu::model::{
+    Anime,
+    Character,
+    Genre,
+    LibraryEntry,
+    Manga,
+    Producer,
+    Relationship,
+    Response,
+    User,
+};
+use serde::de::DeserializeOwned;
+use kitsu::testing::MockTransport;
+use kitsu::transport::Method;
+use kitsu::{Error, KitsuClient, KitsuReqwestRequester};
+
+const ANIME: &[u8] = include_bytes!("fixtures/anime.json");
+const CHARACTER: &[u8] = include_bytes!("fixtures/character.json");
+const GENRES: &[u8] = include_bytes!("fixtures/genres.json");
+const LIBRARY_ENTRY: &[u8] = include_bytes!("fixtures/library_entry.json");
+const MANGA: &[u8] = include_bytes!("fixtures/manga.json");
+const PRODUCER: &[u8] = include_bytes!("fixtures/producer.json");
+const USER: &[u8] = include_bytes!("fixtures/user.json");
+
+fn relationship(json: &str) -> Relationship {
+    serde_json::from_str(json).unwrap()
+}
+
+/// Decodes a fixture with the relationships block removed.
+fn without_relationships<T: DeserializeOwned>(fixture: &[u8]) -> Response<T> {
+    let mut value: serde_json::Value = serde_json::from_slice(fixture).unwrap();
+    value["data"].as_object_mut().unwrap().remove("relationships");
+
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn test_fetch_related() {
+    let transport = MockTransport::new();
+    transport.respond(Method::Get, "/anime/1", 200, ANIME);
+    transport.respond(Method::Get, "/anime/1/genres", 200, GENRES);
+    let client = KitsuClient::new(&transport);
+
+    let anime = client.get_anime(1).unwrap();
+    let genres: Response<Vec<Genre>> = client
+        .fetch_related(anime.data.relationships.genres.as_ref().unwrap())
+        .unwrap();
+
+    assert_eq!(genres.data.len(), 2);
+    assert_eq!(genres.data[0].attributes.name, "Action");
+    assert_eq!(
+        transport.requests()[1].url,
+        "https://kitsu.io/api/edge/anime/1/genres",
+    );
+}
+
+#[test]
+fn test_fetch_related_wrong_model() {
+    let transport = MockTransport::new();
+    transport.respond(Method::Get, "/anime/1/genres", 200, GENRES);
+
+    let anime: Response<Anime> =
+        serde_json::from_slice(ANIME).unwrap();
+    let genres = anime.data.relationships.genres.unwrap();
+    let res = KitsuClient::new(&transport).fetch_related::<Genre>(&genres);
+
+    assert!(matches!(res, Err(Error::Decode(_))));
+}
+
+#[test]
+fn test_to_one_identifier() {
+    let relationship = relationship(r#"{
+        "links": {"self": "self", "related": "related"},
+        "data": {"type": "anime", "id": "1"}
+    }"#);
+    let identifier = relationship.identifier().unwrap();
+
+    assert_eq!(identifier.kind, "anime");
+    assert_eq!(identifier.id, "1");
+    assert_eq!(relationship.identifiers().len(), 1);
+}
+
+#[test]
+fn test_to_many_identifiers() {
+    let relationship = relationship(r#"{
+        "links": {"self": "self", "related": "related"},
+        "data": [{"type": "genres", "id": "1"}, {"type": "genres", "id": "2"}]
+    }"#);
+
+    assert!(relationship.identifier().is_none());
+    assert_eq!(relationship.identifiers().len(), 2);
+}
+
+#[test]
+fn test_empty_to_one() {
+    let relationship = relationship(r#"{
+        "links": {"self": "self", "related": "related"},
+        "data": null
+    }"#);
+
+    assert!(relationship.data.is_none());
+    assert!(relationship.identifiers().is_empty());
+}
+
+#[test]
+fn test_model_relationships() {
+    let character: Response<Character> =
+        serde_json::from_slice(CHARACTER).unwrap();
+    let manga: Response<Manga> =
+        serde_json::from_slice(MANGA).unwrap();
+    let producer: Response<Producer> =
+        serde_json::from_slice(PRODUCER).unwrap();
+    let entry: Response<LibraryEntry> =
+        serde_json::from_slice(LIBRARY_ENTRY).unwrap();
+
+    assert_eq!(
+        character.data.relationships.primary_media.unwrap().links.related,
+        "https://kitsu.io/api/edge/characters/1/primaryMedia",
+    );
+    assert_eq!(
+        manga.data.relationships.manga_characters.unwrap().links.related,
+        "https://kitsu.io/api/edge/manga/1/mangaCharacters",
+    );
+    let productions = producer.data.relationships.anime_productions.unwrap();
+    assert!(productions.data.is_none());
+    assert!(entry.data.relationships.user.is_some());
+}
+
+#[test]
+fn test_missing_relationships() {
+    let mut character: serde_json::Value =
+        serde_json::from_slice(CHARACTER).unwrap();
+    character["data"]["relationships"] = serde_json::json!({});
+    let character: Response<Character> =
+        serde_json::from_value(character).unwrap();
+
+    assert!(character.data.relationships.castings.is_none());
+    assert!(character.data.relationships.primary_media.is_none());
+}
+
+#[test]
+fn test_models_without_relationships() {
+    let anime = without_relationships::<Anime>(ANIME);
+    let character = without_relationships::<Character>(CHARACTER);
+    let entry = without_relationships::<LibraryEntry>(LIBRARY_ENTRY);
+    let manga = without_relationships::<Manga>(MANGA);
+    let producer = without_relationships::<Producer>(PRODUCER);
+    let user = without_relationships::<User>(USER);
+
+    assert!(anime.data.relationships.genres.is_none());
+    assert!(character.data.relationships.primary_media.is_none());
+    assert!(entry.data.relationships.media.is_none());
+    assert!(manga.data.relationships.genres.is_none());
+    assert!(producer.data.relationships.anime_productions.is_none());
+    assert!(user.data.relationships.waifu.is_none());
+}
@@ -57,7 +57,7 @@ fn test_search_anime() {
     let client = Client::new();
     let res = client.search_anime(|f| f.filter("text", "non non biyori")).unwrap();
 
-    assert!(res.data.len() > 0);
+    assert!(!res.data.is_empty());
 }
 
 #[ignore]
@@ -66,7 +66,7 @@ fn test_search_manga() {
     let client = Client::new();
     let res = client.search_manga(|f| f.filter("text", "orange")).unwrap();
 
-    assert!(res.data.len() > 0);
+    assert!(!res.data.is_empty());
 }
 
 #[ignore]
@@ -75,5 +75,5 @@ fn test_search_users() {
     let client = Client::new();
     let res = client.search_users(|f| f.filter("name", "vikhyat")).unwrap();
 
-    assert!(res.data.len() > 0);
+    assert!(!res.data.is_empty());
 }
@@ -0,0 +1,138 @@
+#![cfg(all(
+    feature = "testing",
+    any(feature = "reqwest", feature = "async-support", feature = "hyper-support"),
+))]
+
+extern crate kitsu;
+
+#[macro_use]
+mod common;
+
+use common::ANIME;
+use kitsu::retry::RetryPolicy;
+use kitsu::testing::MockTransport;
+use kitsu::transport::{HttpResponse, Method};
+use kitsu::{Error, KitsuClient};
+use std::sync::Arc;
+use std::time::Duration;
+
+const LIBRARY_ENTRY: &[u8] = include_bytes!("fixtures/library_entry.json");
+
+fn policy() -> RetryPolicy {
+    RetryPolicy::new()
+        .base_delay(Duration::from_millis(100))
+        .jitter(false)
+}
+
+fn retry_after(status: u16, seconds: &str) -> HttpResponse {
+    HttpResponse {
+        body: Vec::new(),
+        headers: vec![("Retry-After".to_owned(), seconds.to_owned())],
+        status,
+    }
+}
+
+bridge_tests! {
+    fn test_retries_transient_errors() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond(Method::Get, "/anime/1", 503, "")
+            .respond(Method::Get, "/anime/1", 502, "")
+            .respond(Method::Get, "/anime/1", 200, ANIME);
+
+        let client = KitsuClient::new(Arc::clone(&transport)).retry(policy());
+        let anime = send!(client.get_anime(1)).unwrap();
+
+        assert_eq!(anime.data.id, "1");
+        assert_eq!(transport.requests().len(), 3);
+        assert_eq!(
+            transport.sleeps(),
+            [Duration::from_millis(100), Duration::from_millis(200)],
+        );
+    }
+
+    fn test_gives_up_after_max_attempts() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond(Method::Get, "/anime/1", 503, "");
+
+        let client = KitsuClient::new(Arc::clone(&transport))
+            .retry(policy().max_attempts(2));
+
+        match send!(client.get_anime(1)) {
+            Err(Error::Server(ref response)) => assert_eq!(response.status, 503),
+            other => panic!("expected a server error: {:?}", other),
+        }
+        assert_eq!(transport.requests().len(), 2);
+        assert_eq!(transport.sleeps(), [Duration::from_millis(100)]);
+    }
+
+    fn test_no_retry_without_policy_or_for_client_errors() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond(Method::Get, "/anime/1", 503, "")
+            .respond(Method::Get, "/anime/2", 404, "");
+
+        let client = KitsuClient::new(Arc::clone(&transport));
+        assert!(send!(client.get_anime(1)).is_err());
+
+        let client = KitsuClient::new(Arc::clone(&transport)).retry(policy());
+        assert!(send!(client.get_anime(2)).is_err());
+
+        assert_eq!(transport.requests().len(), 2);
+        assert!(transport.sleeps().is_empty());
+    }
+
+    fn test_only_retries_get_by_default() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond(Method::Delete, "/library-entries/5", 503, "");
+
+        let client = KitsuClient::new(Arc::clone(&transport)).retry(policy());
+        assert!(send!(client.delete_library_entry(5)).is_err());
+        assert_eq!(transport.requests().len(), 1);
+
+        transport
+            .respond(Method::Patch, "/library-entries/5", 503, "")
+            .respond(Method::Patch, "/library-entries/5", 200, LIBRARY_ENTRY);
+
+        let client = KitsuClient::new(Arc::clone(&transport))
+            .retry(policy().methods(&[Method::Get, Method::Patch]));
+        assert!(send!(client.update_library_entry(5, |f| f.progress(3))).is_ok());
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    fn test_honors_retry_after() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond_with(Method::Get, "/anime/1", retry_after(429, "1"))
+            .respond_with(Method::Get, "/anime/1", retry_after(503, "0"))
+            .respond(Method::Get, "/anime/1", 200, ANIME);
+
+        let client = KitsuClient::new(Arc::clone(&transport)).retry(policy());
+
+        assert!(send!(client.get_anime(1)).is_ok());
+        assert_eq!(transport.requests().len(), 3);
+        assert_eq!(
+            transport.sleeps(),
+            [Duration::from_secs(1), Duration::from_secs(0)],
+        );
+    }
+
+    fn test_retry_after_beyond_max_delay() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond_with(Method::Get, "/anime/1", retry_after(429, "120"))
+            .respond(Method::Get, "/anime/1", 200, ANIME);
+
+        let client = KitsuClient::new(Arc::clone(&transport))
+            .retry(RetryPolicy::new().max_delay(Duration::from_secs(60)));
+
+        match send!(client.get_anime(1)) {
+            Err(Error::RateLimited { retry_after, .. }) => {
+                assert_eq!(retry_after, Some(Duration::from_secs(120)));
+            },
+            other => panic!("expected a rate limit error: {:?}", other),
+        }
+        assert_eq!(transport.requests().len(), 1);
+        assert!(transport.sleeps().is_empty());
+    }
+}
@@ -0,0 +1,245 @@
+extern crate kitsu;
+extern crate serde;
+extern crate serde_json;
+
+use kitsu::builder::{
+    AnimeSearch,
+    AnimeSortField,
+    CharacterSearch,
+    CharacterSortField,
+    MangaSearch,
+    MangaSortField,
+    Search,
+    Sort,
+    SortDirection,
+    UserSearch,
+    UserSortField,
+};
+use kitsu::model::{
+    AgeRating,
+    AnimeType,
+    LibraryEntryStatus,
+    MangaType,
+    Season,
+    Type,
+    WaifuOrHusbando,
+};
+
+fn query<S: Into<Search>>(search: S) -> String {
+    search.into().0
+}
+
+#[test]
+fn test_anime_search() {
+    let search = AnimeSearch::default()
+        .text("cowboy bebop")
+        .season(Season::Spring)
+        .season_year(1998)
+        .subtype(AnimeType::TV)
+        .categories(&["space", "action"])
+        .streamers(&["Crunchyroll", "Hulu"]);
+
+    assert_eq!(
+        query(search),
+        "&filter[text]=cowboy%20bebop&filter[season]=spring\
+         &filter[seasonYear]=1998&filter[subtype]=TV\
+         &filter[categories]=space,action&filter[streamers]=Crunchyroll,Hulu",
+    );
+}
+
+#[test]
+fn test_age_rating_encodes_plus() {
+    let search = AnimeSearch::default()
+        .age_rating(&[AgeRating::PG13, AgeRating::R17Plus]);
+
+    assert_eq!(query(search), "&filter[ageRating]=PG-13,R17%2B");
+}
+
+#[test]
+fn test_average_rating_ranges() {
+    assert_eq!(
+        query(AnimeSearch::default().average_rating(80..)),
+        "&filter[averageRating]=80..",
+    );
+    assert_eq!(
+        query(AnimeSearch::default().average_rating(..=90)),
+        "&filter[averageRating]=..90",
+    );
+    assert_eq!(
+        query(AnimeSearch::default().average_rating(70..80)),
+        "&filter[averageRating]=70..79",
+    );
+    assert_eq!(
+        query(MangaSearch::default().average_rating(..)),
+        "&filter[averageRating]=..",
+    );
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn test_average_rating_empty_ranges_match_nothing() {
+    assert_eq!(
+        query(AnimeSearch::default().average_rating(..0)),
+        "&filter[averageRating]=1..0",
+    );
+    assert_eq!(
+        query(AnimeSearch::default().average_rating(80..10)),
+        "&filter[averageRating]=1..0",
+    );
+    assert_eq!(
+        query(MangaSearch::default().average_rating(50..50)),
+        "&filter[averageRating]=1..0",
+    );
+}
+
+#[test]
+fn test_subtypes_match_the_api() {
+    assert_eq!(
+        query(AnimeSearch::default().subtype(AnimeType::ONA)),
+        "&filter[subtype]=ONA",
+    );
+    assert_eq!(
+        query(MangaSearch::default().subtype(MangaType::OEL)),
+        "&filter[subtype]=oel",
+    );
+    assert_eq!(
+        query(AnimeSearch::default().age_rating(&[AgeRating::TvY7, AgeRating::R18])),
+        "&filter[ageRating]=TV-Y7,R18",
+    );
+}
+
+#[test]
+fn test_names_match_serialization() {
+    fn serialized<T: serde::Serialize>(value: &T) -> String {
+        serde_json::to_value(value).unwrap().as_str().unwrap().to_owned()
+    }
+
+    for season in &[Season::Fall, Season::Spring, Season::Summer, Season::Winter] {
+        assert_eq!(season.as_str(), serialized(season));
+    }
+    for status in &[LibraryEntryStatus::Completed, LibraryEntryStatus::OnHold] {
+        assert_eq!(status.as_str(), serialized(status));
+    }
+    for kind in &[Type::Character, Type::LibraryEntry, Type::Users] {
+        assert_eq!(kind.as_str(), serialized(kind));
+    }
+    for kind in &[WaifuOrHusbando::Husbando, WaifuOrHusbando::Waifu] {
+        assert_eq!(kind.as_str(), serialized(kind));
+    }
+    assert_eq!(AgeRating::R17Plus.as_str(), serialized(&AgeRating::R17Plus));
+}
+
+#[test]
+fn test_manga_search() {
+    let search = MangaSearch::default()
+        .text("orange")
+        .subtype(MangaType::Manga)
+        .categories(&["romance"]);
+
+    assert_eq!(
+        query(search),
+        "&filter[text]=orange&filter[subtype]=manga&filter[categories]=romance",
+    );
+}
+
+#[test]
+fn test_character_and_user_search() {
+    assert_eq!(
+        query(CharacterSearch::default().name("Spike").slug("spike-spiegel")),
+        "&filter[name]=Spike&filter[slug]=spike-spiegel",
+    );
+    assert_eq!(
+        query(UserSearch::default().name("Bob").slug("bob")),
+        "&filter[name]=Bob&filter[slug]=bob",
+    );
+}
+
+#[test]
+fn test_search_methods_on_typed_builders() {
+    let search = AnimeSearch::default()
+        .season(Season::Winter)
+        .filter("episodeCount", "12")
+        .include("genres")
+        .fields("anime", &["canonicalTitle"])
+        .sort("-averageRating")
+        .limit(20)
+        .offset(40);
+
+    assert_eq!(
+        query(search),
+        "&filter[season]=winter&filter[episodeCount]=12&include=genres\
+         &fields[anime]=canonicalTitle&sort=-averageRating\
+         &page[limit]=20&page[offset]=40",
+    );
+}
+
+#[test]
+fn test_sort_single_key() {
+    assert_eq!(Sort::asc(AnimeSortField::PopularityRank).to_string(), "popularityRank");
+    assert_eq!(Sort::desc(MangaSortField::ChapterCount).to_string(), "-chapterCount");
+}
+
+#[test]
+fn test_sort_multiple_keys() {
+    let sort = Sort::desc(AnimeSortField::AverageRating)
+        .then_asc(AnimeSortField::StartDate)
+        .then(AnimeSortField::Id, SortDirection::Descending);
+
+    assert_eq!(sort.to_string(), "-averageRating,startDate,-id");
+    assert_eq!(sort.keys().len(), 3);
+    assert_eq!(sort.keys()[1], (AnimeSortField::StartDate, SortDirection::Ascending));
+}
+
+#[test]
+fn test_sort_by_on_builders() {
+    assert_eq!(
+        query(AnimeSearch::default()
+            .text("bebop")
+            .sort_by(Sort::asc(AnimeSortField::PopularityRank)
+                .then_desc(AnimeSortField::UserCount))),
+        "&filter[text]=bebop&sort=popularityRank,-userCount",
+    );
+    assert_eq!(
+        query(CharacterSearch::default().sort_by(Sort::asc(CharacterSortField::Name))),
+        "&sort=name",
+    );
+    assert_eq!(
+        query(UserSearch::default().sort_by(Sort::desc(UserSortField::FollowersCount))),
+        "&sort=-followersCount",
+    );
+    assert_eq!(
+        query(Search::default().sort_by(Sort::desc(MangaSortField::UserCount))),
+        "&sort=-userCount",
+    );
+}
+
+#[cfg(all(feature = "reqwest-support", feature = "testing"))]
+mod requests {
+    use kitsu::model::{AgeRating, Season};
+    use kitsu::testing::MockTransport;
+    use kitsu::transport::Method;
+    use kitsu::{KitsuClient, KitsuReqwestRequester};
+
+    const ANIME_SEARCH: &[u8] = include_bytes!("fixtures/anime_search.json");
+
+    #[test]
+    fn test_search_anime_typed() {
+        let transport = MockTransport::new();
+        transport.respond(
+            Method::Get,
+            "/anime?filter[season]=winter&filter[seasonYear]=2018\
+             &filter[ageRating]=G,PG",
+            200,
+            ANIME_SEARCH,
+        );
+
+        let res = KitsuClient::new(&transport)
+            .search_anime(|f| f
+                .season(Season::Winter)
+                .season_year(2018)
+                .age_rating(&[AgeRating::G, AgeRating::PG]))
+            .unwrap();
+
+        assert_eq!(res.data.len(), 2);
+    }
+}
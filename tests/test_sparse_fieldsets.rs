@@ -0,0 +1,92 @@
+#![cfg(all(feature = "reqwest-support", feature = "testing"))]
+
+extern crate kitsu;
+
+use kitsu::model::{Anime, Image, Partial};
+use kitsu::testing::MockTransport;
+use kitsu::transport::Method;
+use kitsu::{Error, KitsuClient, KitsuReqwestRequester};
+
+const ANIME: &[u8] = include_bytes!("fixtures/anime.json");
+const ANIME_SEARCH_SPARSE: &[u8] =
+    include_bytes!("fixtures/anime_search_sparse.json");
+
+fn search() -> Vec<Partial<Anime>> {
+    let transport = MockTransport::new();
+    transport.respond(
+        Method::Get,
+        "/anime?filter[text]=non%20non%20biyori&fields[anime]=canonicalTitle,posterImage",
+        200,
+        ANIME_SEARCH_SPARSE,
+    );
+
+    KitsuClient::new(&transport)
+        .search_resources(|f| f
+            .filter("text", "non non biyori")
+            .fields("anime", &["canonicalTitle", "posterImage"]))
+        .unwrap()
+        .data
+}
+
+#[test]
+fn test_partial_attributes() {
+    let anime = search();
+
+    assert_eq!(anime.len(), 2);
+    assert_eq!(anime[0].kind, "anime");
+    assert_eq!(
+        anime[0].attribute::<String>("canonicalTitle").unwrap().as_deref(),
+        Some("Non Non Biyori"),
+    );
+    assert!(anime[0].attribute::<Image>("posterImage").unwrap().is_some());
+    assert!(!anime[0].has_attribute("synopsis"));
+    assert_eq!(anime[0].attribute::<String>("synopsis").unwrap(), None);
+}
+
+#[test]
+fn test_partial_attribute_decode_error() {
+    let anime = search();
+
+    assert!(matches!(
+        anime[0].attribute::<u64>("canonicalTitle"),
+        Err(Error::Decode(_)),
+    ));
+}
+
+#[test]
+fn test_partial_into_full_missing_attributes() {
+    let anime = search().remove(0);
+
+    assert!(matches!(anime.into_full(), Err(Error::Decode(_))));
+}
+
+#[test]
+fn test_partial_into_full() {
+    let transport = MockTransport::new();
+    transport.respond(Method::Get, "/anime/1", 200, ANIME);
+
+    let partial = KitsuClient::new(&transport)
+        .get_resource::<Partial<Anime>, _>(1, |f| f)
+        .unwrap();
+    let anime = partial.data.into_full().unwrap();
+
+    assert_eq!(anime.attributes.canonical_title, "Cowboy Bebop");
+    let genres = anime.relationships.genres.unwrap();
+    assert!(genres.links.related.ends_with("/genres"));
+}
+
+#[test]
+fn test_get_resource_fields() {
+    let transport = MockTransport::new();
+    transport.respond(
+        Method::Get,
+        "/anime/1?fields[anime]=slug",
+        200,
+        ANIME,
+    );
+
+    let res = KitsuClient::new(&transport)
+        .get_resource::<Partial<Anime>, _>(1, |f| f.fields("anime", &["slug"]));
+
+    assert!(res.is_ok());
+}
@@ -0,0 +1,166 @@
+#![cfg(all(feature = "reqwest-support", feature = "testing"))]
+
+extern crate kitsu;
+extern crate serde_json;
+
+use kitsu::auth::{Auth, FileTokenStore, TokenStore, TOKEN_URL};
+use kitsu::testing::MockTransport;
+use kitsu::transport::Method;
+use kitsu::{Error, KitsuClient, KitsuReqwestRequester};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+
+mod common;
+
+use common::{now, token};
+
+const USER: &[u8] = include_bytes!("fixtures/user.json");
+
+fn path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir()
+        .join(format!("kitsu-token-store-{}", std::process::id()))
+        .join(name);
+    let _ = fs::remove_file(&path);
+
+    path
+}
+
+#[test]
+fn test_missing_file() {
+    let store = FileTokenStore::new(path("missing.json"));
+
+    assert!(store.load().unwrap().is_none());
+}
+
+#[test]
+fn test_round_trip() {
+    let store = FileTokenStore::new(path("round_trip.json"));
+    let first = token("first", now());
+    store.save(&first).unwrap();
+
+    assert_eq!(store.load().unwrap(), Some(first));
+
+    store.save(&token("second", now())).unwrap();
+    assert_eq!(store.load().unwrap().unwrap().access_token, "second");
+}
+
+/// The names of the files in a directory.
+fn files(directory: &Path) -> Vec<String> {
+    fs::read_dir(directory).unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect()
+}
+
+#[test]
+fn test_concurrent_saves() {
+    let store = Arc::new(FileTokenStore::new(path("concurrent/token.json")));
+
+    let threads = (0..8)
+        .map(|index| {
+            let store = Arc::clone(&store);
+
+            thread::spawn(move || {
+                for _ in 0..10 {
+                    store.save(&token(&index.to_string(), now())).unwrap();
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    assert!(store.load().unwrap().is_some());
+    assert_eq!(files(store.path().parent().unwrap()), ["token.json"]);
+}
+
+#[test]
+fn test_failed_save_removes_temporary_file() {
+    // A directory can not be replaced by renaming a file over it.
+    let path = path("failed/token.json");
+    fs::create_dir_all(path.join("occupied")).unwrap();
+
+    assert!(FileTokenStore::new(&path).save(&token("first", now())).is_err());
+    assert_eq!(files(path.parent().unwrap()), ["token.json"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_file_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let store = FileTokenStore::new(path("permissions.json"));
+    store.save(&token("first", now())).unwrap();
+
+    let mode = fs::metadata(store.path()).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
+
+#[test]
+fn test_invalid_file() {
+    let path = path("invalid.json");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "not json").unwrap();
+
+    let transport = MockTransport::new();
+    let client = KitsuClient::new(&transport)
+        .auth(Auth::from_store(FileTokenStore::new(&path)));
+
+    assert!(matches!(client.get_user(1), Err(Error::TokenStore(_))));
+    assert!(transport.requests().is_empty());
+}
+
+#[test]
+fn test_refreshed_token_is_saved() {
+    let path = path("refreshed.json");
+    let mut expired = token("first", now());
+    expired.created_at -= 2_592_000;
+    FileTokenStore::new(&path).save(&expired).unwrap();
+
+    let transport = MockTransport::new();
+    transport.respond(
+        Method::Post,
+        TOKEN_URL,
+        200,
+        serde_json::to_vec(&token("second", now())).unwrap(),
+    );
+    transport.respond(Method::Get, "/users/1", 200, USER);
+
+    let client = KitsuClient::new(&transport)
+        .auth(Auth::from_store(FileTokenStore::new(&path)));
+    client.get_user(1).unwrap();
+
+    let saved = FileTokenStore::new(&path).load().unwrap().unwrap();
+    assert_eq!(saved.access_token, "second");
+}
+
+#[test]
+fn test_shared_between_clients() {
+    let path = path("shared.json");
+    let transport = MockTransport::new();
+    transport.respond(
+        Method::Post,
+        TOKEN_URL,
+        200,
+        serde_json::to_vec(&token("first", now())).unwrap(),
+    );
+    transport.respond(Method::Get, "/users/1", 200, USER);
+
+    let first = KitsuClient::new(&transport).auth(
+        Auth::password("user@example.com", "hunter2")
+            .store(FileTokenStore::new(&path)),
+    );
+    let second = KitsuClient::new(&transport).auth(
+        Auth::password("user@example.com", "hunter2")
+            .store(FileTokenStore::new(&path)),
+    );
+    first.get_user(1).unwrap();
+    second.get_user(1).unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[2].header("Authorization"), Some("Bearer first"));
+}
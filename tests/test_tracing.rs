@@ -0,0 +1,226 @@
+#![cfg(all(
+    feature = "testing",
+    feature = "tracing",
+    any(feature = "reqwest", feature = "async-support", feature = "hyper-support"),
+))]
+
+extern crate kitsu;
+extern crate tracing;
+extern crate tracing_core;
+
+#[macro_use]
+mod common;
+
+use common::ANIME;
+use kitsu::retry::RetryPolicy;
+use kitsu::testing::MockTransport;
+use kitsu::transport::Method;
+use kitsu::KitsuClient;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::DefaultGuard;
+use tracing::{Event, Metadata, Subscriber};
+use tracing_core::span::Current;
+
+type Fields = BTreeMap<String, String>;
+
+/// The spans and events recorded by a `Recorder`, in the order they were
+/// created, and the spans that are currently entered.
+#[derive(Clone, Default)]
+struct Recorded {
+    entered: Arc<Mutex<Vec<Id>>>,
+    events: Arc<Mutex<Vec<Fields>>>,
+    spans: Arc<Mutex<Vec<(&'static Metadata<'static>, Fields)>>>,
+}
+
+impl Recorded {
+    fn spans(&self) -> Vec<Fields> {
+        self.spans.lock().unwrap()
+            .iter()
+            .filter(|(metadata, _)| metadata.name() == "kitsu.request")
+            .map(|(_, fields)| fields.clone())
+            .collect()
+    }
+
+    fn events(&self) -> Vec<Fields> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+/// A subscriber recording the fields of every span and event.
+struct Recorder(Recorded);
+
+struct Visitor<'a>(&'a mut Fields);
+
+impl<'a> Visit for Visitor<'a> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.insert(field.name().to_owned(), format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_owned(), value.to_owned());
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields::new();
+        span.record(&mut Visitor(&mut fields));
+
+        let mut spans = self.0.spans.lock().unwrap();
+        spans.push((span.metadata(), fields));
+
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut spans = self.0.spans.lock().unwrap();
+        let index = span.into_u64() as usize - 1;
+        values.record(&mut Visitor(&mut spans[index].1));
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields::new();
+        event.record(&mut Visitor(&mut fields));
+        self.0.events.lock().unwrap().push(fields);
+    }
+
+    fn enter(&self, span: &Id) {
+        self.0.entered.lock().unwrap().push(span.clone());
+    }
+
+    fn exit(&self, _: &Id) {
+        self.0.entered.lock().unwrap().pop();
+    }
+
+    fn current_span(&self) -> Current {
+        match self.0.entered.lock().unwrap().last() {
+            Some(span) => {
+                let spans = self.0.spans.lock().unwrap();
+                let metadata = spans[span.into_u64() as usize - 1].0;
+
+                Current::new(span.clone(), metadata)
+            },
+            None => Current::none(),
+        }
+    }
+}
+
+fn record() -> (Recorded, DefaultGuard) {
+    let recorded = Recorded::default();
+    let guard = tracing::subscriber::set_default(Recorder(recorded.clone()));
+
+    (recorded, guard)
+}
+
+bridge_tests! {
+    fn test_request_span() {
+        let (recorded, _guard) = record();
+        let transport = Arc::new(MockTransport::new());
+        transport.respond(Method::Get, "/anime/1", 200, ANIME);
+
+        send!(KitsuClient::new(Arc::clone(&transport)).get_anime(1)).unwrap();
+
+        let spans = recorded.spans();
+        assert_eq!(spans.len(), 1);
+
+        let span = &spans[0];
+        assert_eq!(span["method"], "GET");
+        assert_eq!(span["path"], "/anime/1");
+        assert_eq!(span["query"], "");
+        assert_eq!(span["resource"], "anime");
+        assert_eq!(span["status"], "200");
+        assert_eq!(span["bytes"], ANIME.len().to_string());
+        assert_eq!(span["attempt"], "1");
+        assert!(span.contains_key("decode_micros"));
+        assert!(!span.contains_key("error"));
+    }
+
+    fn test_failed_request_span() {
+        let (recorded, _guard) = record();
+        let transport = Arc::new(MockTransport::new());
+        transport.respond(Method::Get, "/anime/1", 404, "");
+
+        let client = KitsuClient::new(Arc::clone(&transport));
+        assert!(send!(client.get_anime(1)).is_err());
+        assert!(send!(client.get_anime(2)).is_err());
+
+        let spans = recorded.spans();
+        assert_eq!(spans[0]["status"], "404");
+        assert!(spans[0]["error"].starts_with("Resource not found"));
+        assert!(!spans[1].contains_key("status"));
+        assert!(spans[1]["error"].starts_with("Invalid request"));
+    }
+
+    fn test_retry_attempts() {
+        let (recorded, _guard) = record();
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond(Method::Get, "/anime/1", 503, "")
+            .respond(Method::Get, "/anime/1", 200, ANIME);
+
+        let policy = RetryPolicy::new()
+            .base_delay(Duration::from_millis(100))
+            .jitter(false);
+        let client = KitsuClient::new(Arc::clone(&transport)).retry(policy);
+        send!(client.get_anime(1)).unwrap();
+
+        // The retry is recorded in the request's span, which is only current
+        // while a future of an asynchronous bridge is polled within it.
+        let spans = recorded.spans();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0]["attempt"], "2");
+        assert_eq!(spans[0]["status"], "200");
+
+        let events = recorded.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["message"], "retrying request");
+        assert_eq!(events[0]["attempt"], "2");
+        assert_eq!(events[0]["delay_ms"], "100");
+    }
+}
+
+#[cfg(feature = "reqwest")]
+#[test]
+fn test_span_per_page() {
+    use kitsu::builder::Search;
+    use kitsu::model::Genre;
+    use kitsu::KitsuReqwestRequester;
+
+    let (recorded, _guard) = record();
+    let transport = MockTransport::new();
+    transport
+        .respond(
+            Method::Get,
+            "/genres?page[limit]=1",
+            200,
+            &br#"{"data": [], "links": {"next": "https://kitsu.io/api/edge/genres?page[limit]=1&page[offset]=1"}}"#[..],
+        )
+        .respond(
+            Method::Get,
+            "/genres?page[limit]=1&page[offset]=1",
+            200,
+            &br#"{"data": [], "links": {}}"#[..],
+        );
+
+    let pages = KitsuClient::new(&transport)
+        .search_pages::<Genre, _>(Search::default().limit(1))
+        .collect::<Vec<_>>();
+    assert_eq!(pages.len(), 2);
+
+    let spans = recorded.spans();
+    assert_eq!(spans.len(), 2);
+    assert_eq!(spans[0]["resource"], "genres");
+    assert_eq!(spans[0]["query"], "page[limit]=1");
+    assert_eq!(spans[1]["query"], "page[limit]=1&page[offset]=1");
+}